@@ -1,75 +1,51 @@
-use crate::MANAGERS;
-use js_sys::Reflect;
-use wasm_bindgen::prelude::*;
-
-#[wasm_bindgen (js_name = rawGetThresholds)]
-pub fn get_thresholds(test_info_id: &str) -> Result<JsValue, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
-
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
-
-    let thresholds = js_sys::Array::new();
-
-    for tr in manager.test_info.thresholds.iter() {
-        let obj = js_sys::Object::new();
-
-        Reflect::set(
-            &obj,
-            &JsValue::from_str("metric"),
-            &JsValue::from_str(tr.metric.as_str()),
-        )?;
-
-        Reflect::set(
-            &obj,
-            &JsValue::from_str("source"),
-            &JsValue::from_str(tr.source.as_str()),
-        )?;
+use crate::{
+    anomalies::Anomaly,
+    intervals::checks::CheckCollection,
+    manager::TestInfoManager,
+    thresholds::{AbortDecision, ThresholdResult},
+    types,
+};
+
+// Typed read access to the managed data, used by both native callers and the
+// wasm adapter
+impl TestInfoManager {
+    pub fn intervals(&self) -> &[types::Interval] {
+        &self.test_info.intervals
+    }
 
-        match &tr.abort_on_fail {
-            Some(abort_on_fail) => {
-                Reflect::set(
-                    &obj,
-                    &JsValue::from_str("abortOnFail"),
-                    &JsValue::from_bool(*abort_on_fail),
-                )?;
-            }
-            None => {}
-        }
+    pub fn summary(&self) -> Option<&types::Interval> {
+        self.test_info.summary.as_ref()
+    }
 
-        match &tr.delay_abort_eval {
-            Some(delay_abort_eval) => {
-                Reflect::set(
-                    &obj,
-                    &JsValue::from_str("delayAbortEval"),
-                    &JsValue::from_str(delay_abort_eval),
-                )?;
-            }
-            None => {}
-        }
+    pub fn checks(&self) -> &CheckCollection {
+        &self.checks
+    }
 
-        thresholds.push(&obj);
+    pub fn thresholds(&self) -> &[types::Threshold] {
+        &self.test_info.thresholds
     }
 
-    Ok(JsValue::from(thresholds))
-}
+    pub fn threshold_results(&self) -> &[ThresholdResult] {
+        &self.threshold_results
+    }
 
-#[wasm_bindgen (js_name = rawGetMessages)]
-pub fn get_messages(test_info_id: &str) -> Result<JsValue, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+    pub fn abort_decision(&self) -> Option<&AbortDecision> {
+        self.abort_decision.as_ref()
+    }
 
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
+    pub fn console_messages(&self) -> &[types::ConsoleMessage] {
+        &self.test_info.console_messages
+    }
 
-    let messages = js_sys::Array::new();
+    pub fn messages(&self) -> &[String] {
+        &self.test_info.messages
+    }
 
-    for message in manager.test_info.messages.iter() {
-        messages.push(&JsValue::from_str(message.as_str()));
+    pub fn locations(&self) -> &[String] {
+        &self.locations
     }
 
-    Ok(JsValue::from(messages))
-}
\ No newline at end of file
+    pub fn anomalies(&self) -> &[Anomaly] {
+        &self.anomalies
+    }
+}
@@ -0,0 +1,195 @@
+use serde::Serialize;
+
+use crate::{
+    comparison::error_rate_sign, manager::TestInfoManager, sketch::sink_label_value, states::Topic,
+    types,
+};
+
+// Latency and throughput deviations are at least this fraction of the window
+// mean, so that a very steady window doesn't flag tiny changes
+const MIN_RELATIVE_DEVIATION: f64 = 0.05;
+
+// Error rate deviations are at least one percentage point, otherwise a single
+// failure after a run of none would be flagged
+const MIN_ERROR_RATE_DEVIATION: f64 = 0.01;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnomalyOptions {
+    // Number of preceding intervals each interval is compared against
+    pub window_size: usize,
+    // Fewer preceding intervals than this and nothing is flagged
+    pub min_window_size: usize,
+    // How many standard deviations worse than the window mean a value must be
+    pub z_score_threshold: f64,
+}
+
+impl Default for AnomalyOptions {
+    fn default() -> AnomalyOptions {
+        AnomalyOptions {
+            window_size: 10,
+            min_window_size: 5,
+            z_score_threshold: 3.0,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum AnomalyKind {
+    LatencySpike,
+    ErrorRateSpike,
+    ThroughputDrop,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Anomaly {
+    pub sink_name: String,
+    pub label: String,
+    pub location: String,
+    pub kind: AnomalyKind,
+    pub period: i32,
+    pub value: f64,
+    // Mean of the preceding window
+    pub expected: f64,
+    // Signed so that positive is always worse
+    pub z_score: f64,
+}
+
+impl TestInfoManager {
+    pub fn set_anomaly_options(&mut self, options: AnomalyOptions) {
+        self.anomaly_options = options;
+
+        let periods: Vec<i32> = self.test_info.intervals.iter().map(|x| x.period).collect();
+        self.update_anomalies(&periods);
+    }
+
+    // Detects anomalies in the intervals for the given periods, replacing any
+    // found in them before. Intervals after a late arrival keep the anomalies
+    // found against the window they originally had.
+    pub fn update_anomalies(&mut self, periods: &[i32]) {
+        let mut anomalies = self.anomalies.clone();
+        anomalies.retain(|x| !periods.contains(&x.period));
+
+        for period in periods.iter() {
+            let intervals = &self.test_info.intervals;
+
+            if let Some(index) = intervals.iter().position(|x| x.period == *period) {
+                anomalies.extend(detect_anomalies(
+                    &intervals[..index],
+                    &intervals[index],
+                    &self.anomaly_options,
+                ));
+            }
+        }
+
+        anomalies.sort_by(|a, b| {
+            (a.period, &a.sink_name, &a.label).cmp(&(b.period, &b.sink_name, &b.label))
+        });
+
+        self.replace_if_changed(Topic::Anomalies, |x| &mut x.anomalies, anomalies);
+    }
+}
+
+// Compares each monitored sink label in the interval against its values in
+// the preceding intervals, which must be sorted by period, using a rolling
+// z-score
+pub fn detect_anomalies(
+    preceding: &[types::Interval],
+    interval: &types::Interval,
+    options: &AnomalyOptions,
+) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+
+    for (sink_name, sink) in interval.sinks.iter() {
+        let (label, kind, sign) = match monitored_label(sink_name, sink) {
+            Some(monitored) => monitored,
+            None => continue,
+        };
+
+        let value = match monitored_value(sink, label) {
+            Some(value) if value.is_finite() => value,
+            _ => continue,
+        };
+
+        let window: Vec<f64> = preceding
+            .iter()
+            .rev()
+            .filter_map(|x| x.sinks.get(sink_name))
+            .filter_map(|x| monitored_value(x, label))
+            .filter(|x| x.is_finite())
+            .take(options.window_size)
+            .collect();
+
+        if window.len() < options.min_window_size.max(2) {
+            continue;
+        }
+
+        let mean = window.iter().sum::<f64>() / window.len() as f64;
+        let variance =
+            window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (window.len() - 1) as f64;
+
+        let min_deviation = match kind {
+            AnomalyKind::ErrorRateSpike => MIN_ERROR_RATE_DEVIATION,
+            _ => mean.abs() * MIN_RELATIVE_DEVIATION,
+        };
+        let deviation = variance.sqrt().max(min_deviation);
+
+        if deviation <= 0.0 {
+            continue;
+        }
+
+        let z_score = sign * (value - mean) / deviation;
+
+        if z_score >= options.z_score_threshold {
+            anomalies.push(Anomaly {
+                sink_name: sink_name.to_string(),
+                label: label.to_string(),
+                location: sink_name.split("::").next().unwrap_or_default().to_string(),
+                kind,
+                period: interval.period,
+                value,
+                expected: mean,
+                z_score,
+            });
+        }
+    }
+
+    anomalies
+}
+
+// The label watched for each kind of sink, and whether an increase (1) or a
+// decrease (-1) in it is bad
+fn monitored_label(
+    sink_name: &str,
+    sink: &types::Sink,
+) -> Option<(&'static str, AnomalyKind, f64)> {
+    match sink.type_.enum_value() {
+        Ok(types::SinkType::Trend) => Some(("p(95)", AnomalyKind::LatencySpike, 1.0)),
+        Ok(types::SinkType::Rate) => {
+            error_rate_sign(sink_name).map(|sign| ("rate", AnomalyKind::ErrorRateSpike, sign))
+        }
+        Ok(types::SinkType::Counter) => Some(("rate", AnomalyKind::ThroughputDrop, -1.0)),
+        _ => None,
+    }
+}
+
+// Streamed rate sinks may only have pass and total counts
+fn monitored_value(sink: &types::Sink, label: &str) -> Option<f64> {
+    match (sink.type_.enum_value(), label) {
+        (Ok(types::SinkType::Rate), "rate") => match sink.labels.get("rate") {
+            Some(rate) => Some(*rate),
+            None => {
+                let total = *sink.labels.get("total")?;
+                let pass = *sink.labels.get("pass")?;
+
+                if total > 0.0 {
+                    Some(pass / total)
+                } else {
+                    None
+                }
+            }
+        },
+        _ => sink_label_value(sink, label),
+    }
+}
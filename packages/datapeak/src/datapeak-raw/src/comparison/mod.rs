@@ -0,0 +1,249 @@
+pub mod significance;
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{
+    comparison::significance::{test_significance, SignificanceResult},
+    manager::TestInfoManager,
+    sketch::sink_label_value,
+    types,
+};
+
+#[derive(Debug, Clone)]
+pub struct ComparisonOptions {
+    // Relative increase in a p(95) that is flagged as a regression, eg. 0.1
+    // allows the candidate to be up to 10% slower
+    pub p95_tolerance: f64,
+    // Increase in an error rate, as a fraction of all requests, that is
+    // flagged as a regression
+    pub error_rate_tolerance: f64,
+    // p-value below which a difference is reported as significant
+    pub significance_level: f64,
+}
+
+impl Default for ComparisonOptions {
+    fn default() -> ComparisonOptions {
+        ComparisonOptions {
+            p95_tolerance: 0.1,
+            error_rate_tolerance: 0.01,
+            significance_level: 0.05,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelDelta {
+    pub sink_name: String,
+    pub label: String,
+    pub baseline: f64,
+    pub candidate: f64,
+    pub absolute_delta: f64,
+    // None when the baseline is zero
+    pub relative_delta: Option<f64>,
+    pub regression: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    // Deltas for every label of the sinks in both summaries
+    pub deltas: Vec<LabelDelta>,
+    // Sinks that are only in one of the summaries
+    pub baseline_only: Vec<String>,
+    pub candidate_only: Vec<String>,
+    pub regressed: bool,
+    // Whether trend and rate differences are likely to be real, only given
+    // when the intervals of both runs are available
+    pub significance: Vec<SignificanceResult>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlignedPoint {
+    pub period: i32,
+    pub baseline: Option<f64>,
+    pub candidate: Option<f64>,
+}
+
+// Values of one sink label in two runs, paired by period
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlignedTimeSeries {
+    pub name: String,
+    pub data: Vec<AlignedPoint>,
+}
+
+impl TestInfoManager {
+    // Compares this run, as the baseline, against a candidate run
+    pub fn compare(&self, candidate: &TestInfoManager, options: &ComparisonOptions) -> Comparison {
+        let mut comparison = compare_summaries(self.summary(), candidate.summary(), options);
+        comparison.significance = test_significance(self, candidate, options);
+
+        comparison
+    }
+
+    // Gets a time series for a path of the form <sink>::<label> from both runs
+    pub fn aligned_time_series(
+        &self,
+        candidate: &TestInfoManager,
+        sink_path: &str,
+    ) -> Result<AlignedTimeSeries, String> {
+        align_time_series(self.intervals(), candidate.intervals(), sink_path)
+    }
+}
+
+// Compares two exported TestInfo messages
+pub fn compare_bytes(
+    baseline: &[u8],
+    candidate: &[u8],
+    options: &ComparisonOptions,
+) -> Result<Comparison, String> {
+    let baseline = TestInfoManager::from_bytes(baseline)?;
+    let candidate = TestInfoManager::from_bytes(candidate)?;
+
+    Ok(baseline.compare(&candidate, options))
+}
+
+pub fn compare_summaries(
+    baseline: Option<&types::Interval>,
+    candidate: Option<&types::Interval>,
+    options: &ComparisonOptions,
+) -> Comparison {
+    let empty = types::Interval::new();
+    let baseline = baseline.unwrap_or(&empty);
+    let candidate = candidate.unwrap_or(&empty);
+
+    // Sorted so that deltas are in a stable order
+    let baseline_sinks: BTreeMap<&String, &types::Sink> = baseline.sinks.iter().collect();
+    let candidate_sinks: BTreeMap<&String, &types::Sink> = candidate.sinks.iter().collect();
+
+    let mut comparison = Comparison::default();
+
+    for (sink_name, baseline_sink) in baseline_sinks.iter() {
+        let candidate_sink = match candidate_sinks.get(sink_name) {
+            Some(candidate_sink) => candidate_sink,
+            None => {
+                comparison.baseline_only.push(sink_name.to_string());
+                continue;
+            }
+        };
+
+        let labels: BTreeMap<&String, &f64> = baseline_sink.labels.iter().collect();
+
+        for (label, baseline_value) in labels {
+            let candidate_value = match candidate_sink.labels.get(label) {
+                Some(candidate_value) => *candidate_value,
+                None => continue,
+            };
+
+            let mut delta = label_delta(sink_name, label, *baseline_value, candidate_value);
+            delta.regression = is_regression(&delta, baseline_sink, options);
+
+            comparison.regressed |= delta.regression;
+            comparison.deltas.push(delta);
+        }
+    }
+
+    comparison.candidate_only = candidate_sinks
+        .keys()
+        .filter(|x| !baseline_sinks.contains_key(*x))
+        .map(|x| x.to_string())
+        .collect();
+
+    comparison
+}
+
+fn label_delta(sink_name: &str, label: &str, baseline: f64, candidate: f64) -> LabelDelta {
+    let absolute_delta = candidate - baseline;
+
+    LabelDelta {
+        sink_name: sink_name.to_string(),
+        label: label.to_string(),
+        baseline,
+        candidate,
+        absolute_delta,
+        relative_delta: if baseline != 0.0 {
+            Some(absolute_delta / baseline.abs())
+        } else {
+            None
+        },
+        regression: false,
+    }
+}
+
+// Only latency percentiles and error rates are judged, as whether other
+// labels getting bigger is worse depends on the test
+fn is_regression(delta: &LabelDelta, sink: &types::Sink, options: &ComparisonOptions) -> bool {
+    match (sink.type_.enum_value(), delta.label.as_str()) {
+        (Ok(types::SinkType::Trend), "p(95)") => match delta.relative_delta {
+            Some(relative_delta) => relative_delta > options.p95_tolerance,
+            None => delta.candidate > 0.0,
+        },
+        (Ok(types::SinkType::Rate), "rate") => match error_rate_sign(&delta.sink_name) {
+            Some(sign) => sign * delta.absolute_delta > options.error_rate_tolerance,
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+// Checks record the rate of passes, so their error rate falls as the rate
+// rises, while "*_failed" metrics record the error rate directly
+pub fn error_rate_sign(sink_name: &str) -> Option<f64> {
+    let metric = sink_name.split("::").nth(1)?;
+
+    if metric == "checks" {
+        Some(-1.0)
+    } else if metric.ends_with("_failed") {
+        Some(1.0)
+    } else {
+        None
+    }
+}
+
+pub fn align_time_series(
+    baseline: &[types::Interval],
+    candidate: &[types::Interval],
+    sink_path: &str,
+) -> Result<AlignedTimeSeries, String> {
+    let (sink_name, label) = match sink_path.rsplit_once("::") {
+        Some(parts) if parts.0.contains("::") => parts,
+        _ => return Err("sink path must be of the form <sink>::<label>".to_string()),
+    };
+
+    let value = |interval: &types::Interval| {
+        interval
+            .sinks
+            .get(sink_name)
+            .and_then(|sink| sink_label_value(sink, label))
+    };
+
+    // map<period, (baseline, candidate)>
+    let mut periods: BTreeMap<i32, (Option<f64>, Option<f64>)> = BTreeMap::new();
+
+    for interval in baseline.iter() {
+        periods.entry(interval.period).or_default().0 = value(interval);
+    }
+    for interval in candidate.iter() {
+        periods.entry(interval.period).or_default().1 = value(interval);
+    }
+
+    if periods.values().all(|(a, b)| a.is_none() && b.is_none()) {
+        return Err(format!("Sink path {} not found in either run", sink_path));
+    }
+
+    Ok(AlignedTimeSeries {
+        name: sink_name.to_string(),
+        data: periods
+            .into_iter()
+            .map(|(period, (baseline, candidate))| AlignedPoint {
+                period,
+                baseline,
+                candidate,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{
+    comparison::ComparisonOptions, manager::TestInfoManager, sketch::sink_label_value, types,
+};
+
+// Trend labels tested using their per interval values as samples
+const TREND_LABELS: [&str; 2] = ["avg", "p(95)"];
+
+// Below this many intervals in either run the normal approximation used by
+// the Mann-Whitney U test isn't reliable
+const MIN_INTERVAL_SAMPLES: usize = 3;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SignificanceTest {
+    MannWhitneyU,
+    TwoProportionZ,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignificanceResult {
+    pub sink_name: String,
+    pub label: String,
+    pub test: SignificanceTest,
+    // U for the candidate, or z for proportions
+    pub statistic: f64,
+    pub p_value: f64,
+    // Probability that a candidate interval is higher than a baseline one for
+    // Mann-Whitney U, or the difference in proportions for rates
+    pub effect: f64,
+    pub significant: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestOutcome {
+    pub statistic: f64,
+    pub p_value: f64,
+    pub effect: f64,
+}
+
+// Tests whether the trend sinks and rates in both runs differ, trend labels are
+// sampled once per interval so that a run is judged on its variability over
+// time rather than the single summary value
+pub fn test_significance(
+    baseline: &TestInfoManager,
+    candidate: &TestInfoManager,
+    options: &ComparisonOptions,
+) -> Vec<SignificanceResult> {
+    let (baseline_summary, candidate_summary) = match (baseline.summary(), candidate.summary()) {
+        (Some(baseline_summary), Some(candidate_summary)) => (baseline_summary, candidate_summary),
+        _ => return Vec::new(),
+    };
+
+    let sinks: BTreeMap<&String, &types::Sink> = baseline_summary.sinks.iter().collect();
+
+    let mut results = Vec::new();
+
+    for (sink_name, baseline_sink) in sinks {
+        let candidate_sink = match candidate_summary.sinks.get(sink_name) {
+            Some(candidate_sink) if candidate_sink.type_ == baseline_sink.type_ => candidate_sink,
+            _ => continue,
+        };
+
+        let mut push = |label: &str, test: SignificanceTest, outcome: Option<TestOutcome>| {
+            if let Some(outcome) = outcome {
+                results.push(SignificanceResult {
+                    sink_name: sink_name.to_string(),
+                    label: label.to_string(),
+                    test,
+                    statistic: outcome.statistic,
+                    p_value: outcome.p_value,
+                    effect: outcome.effect,
+                    significant: outcome.p_value < options.significance_level,
+                });
+            }
+        };
+
+        match baseline_sink.type_.enum_value() {
+            Ok(types::SinkType::Trend) => {
+                for label in TREND_LABELS.iter() {
+                    let baseline_samples = interval_samples(baseline.intervals(), sink_name, label);
+                    let candidate_samples =
+                        interval_samples(candidate.intervals(), sink_name, label);
+
+                    if baseline_samples.len() < MIN_INTERVAL_SAMPLES
+                        || candidate_samples.len() < MIN_INTERVAL_SAMPLES
+                    {
+                        continue;
+                    }
+
+                    push(
+                        label,
+                        SignificanceTest::MannWhitneyU,
+                        mann_whitney_u(&baseline_samples, &candidate_samples),
+                    );
+                }
+            }
+            Ok(types::SinkType::Rate) => {
+                let counts = |sink: &types::Sink| {
+                    Some((*sink.labels.get("pass")?, *sink.labels.get("total")?))
+                };
+
+                if let (Some(baseline_counts), Some(candidate_counts)) =
+                    (counts(baseline_sink), counts(candidate_sink))
+                {
+                    push(
+                        "rate",
+                        SignificanceTest::TwoProportionZ,
+                        two_proportion_z_test(baseline_counts, candidate_counts),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    results
+}
+
+fn interval_samples(intervals: &[types::Interval], sink_name: &str, label: &str) -> Vec<f64> {
+    intervals
+        .iter()
+        .filter_map(|interval| interval.sinks.get(sink_name))
+        .filter_map(|sink| sink_label_value(sink, label))
+        .filter(|x| x.is_finite())
+        .collect()
+}
+
+// Two sided Mann-Whitney U test using the normal approximation, with
+// corrections for ties and continuity
+pub fn mann_whitney_u(baseline: &[f64], candidate: &[f64]) -> Option<TestOutcome> {
+    if baseline.is_empty() || candidate.is_empty() {
+        return None;
+    }
+
+    let n1 = baseline.len() as f64;
+    let n2 = candidate.len() as f64;
+    let n = n1 + n2;
+
+    // (value, is candidate)
+    let mut values: Vec<(f64, bool)> = baseline
+        .iter()
+        .map(|x| (*x, false))
+        .chain(candidate.iter().map(|x| (*x, true)))
+        .collect();
+    values.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // Tied values are given the mean of their ranks
+    let mut candidate_rank_sum = 0.0;
+    let mut tie_correction = 0.0;
+    let mut start = 0;
+
+    while start < values.len() {
+        let mut end = start + 1;
+        while end < values.len() && values[end].0 == values[start].0 {
+            end += 1;
+        }
+
+        let rank = (start + end + 1) as f64 / 2.0;
+        let ties = (end - start) as f64;
+
+        candidate_rank_sum += rank * values[start..end].iter().filter(|x| x.1).count() as f64;
+        tie_correction += ties.powi(3) - ties;
+
+        start = end;
+    }
+
+    let u = candidate_rank_sum - n2 * (n2 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+
+    let p_value = if variance > 0.0 {
+        let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+        2.0 * (1.0 - normal_cdf(z))
+    } else {
+        // Every value is the same
+        1.0
+    };
+
+    Some(TestOutcome {
+        statistic: u,
+        p_value: p_value.clamp(0.0, 1.0),
+        effect: u / (n1 * n2),
+    })
+}
+
+// Two sided test for a difference between the pass proportions of two rates,
+// given as (pass, total)
+pub fn two_proportion_z_test(baseline: (f64, f64), candidate: (f64, f64)) -> Option<TestOutcome> {
+    let (pass1, total1) = baseline;
+    let (pass2, total2) = candidate;
+
+    if total1 <= 0.0 || total2 <= 0.0 {
+        return None;
+    }
+
+    let p1 = pass1 / total1;
+    let p2 = pass2 / total2;
+    let pooled = (pass1 + pass2) / (total1 + total2);
+
+    let standard_error = (pooled * (1.0 - pooled) * (1.0 / total1 + 1.0 / total2)).sqrt();
+
+    let (z, p_value) = if standard_error > 0.0 {
+        let z = (p2 - p1) / standard_error;
+        (z, 2.0 * (1.0 - normal_cdf(z.abs())))
+    } else {
+        // Both rates are all passes or all fails
+        (0.0, 1.0)
+    };
+
+    Some(TestOutcome {
+        statistic: z,
+        p_value: p_value.clamp(0.0, 1.0),
+        effect: p2 - p1,
+    })
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+// Abramowitz and Stegun 7.1.26, accurate to about 1.5e-7
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let y = 1.0
+        - (((((1.061405429 * t - 1.453152027) * t) + 1.421413741) * t - 0.284496736) * t
+            + 0.254829592)
+            * t
+            * (-x * x).exp();
+
+    sign * y
+}
@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+
+use crate::{
+    glob::glob_match_any,
+    intervals::{merge::GLOBAL_LOCATION, INTERVAL_PERIOD_SECONDS},
+    manager::TestInfoManager,
+    types,
+};
+
+// A column is a sink without its location and one of its labels
+type Column<'a> = (&'a str, &'a str);
+
+#[derive(Debug, Clone, Default)]
+pub struct CsvOptions {
+    // Globs matched against "<sink>::<label>" column names, where the sink has
+    // no location prefix, eg. "http_req_duration::p(95)" or "*::rate". All
+    // columns are included if empty.
+    pub columns: Vec<String>,
+    // Adds a timestamp column when the start of the run is known
+    pub start_time: Option<DateTime<Utc>>,
+    // Width of each period, 6 seconds if not given
+    pub interval_seconds: Option<u32>,
+}
+
+impl TestInfoManager {
+    // Uses the start time and width of the run where the options don't give
+    // them
+    pub fn export_csv(&self, options: &CsvOptions) -> String {
+        let options = CsvOptions {
+            columns: options.columns.clone(),
+            start_time: options.start_time.or_else(|| self.start_time()),
+            interval_seconds: Some(options.interval_seconds.unwrap_or(self.interval_seconds())),
+        };
+
+        intervals_to_csv(&self.test_info.intervals, &options)
+    }
+}
+
+// Writes one row per period and location, with a column for every selected
+// sink label. Cells are left empty where a location has no value.
+pub fn intervals_to_csv(intervals: &[types::Interval], options: &CsvOptions) -> String {
+    let mut columns: BTreeSet<Column> = BTreeSet::new();
+
+    for interval in intervals.iter() {
+        for (sink_name, sink) in interval.sinks.iter() {
+            let sink_suffix = match sink_name.split_once("::") {
+                Some((_, sink_suffix)) => sink_suffix,
+                None => continue,
+            };
+
+            for label in sink.labels.keys() {
+                if glob_match_any(&options.columns, &format!("{}::{}", sink_suffix, label)) {
+                    columns.insert((sink_suffix, label.as_str()));
+                }
+            }
+        }
+    }
+
+    let mut header = vec!["period".to_string()];
+    if options.start_time.is_some() {
+        header.push("timestamp".to_string());
+    }
+    header.push("location".to_string());
+    header.extend(
+        columns
+            .iter()
+            .map(|(sink_suffix, label)| format!("{}::{}", sink_suffix, label)),
+    );
+
+    let mut csv = String::new();
+    push_row(&mut csv, &header);
+
+    for interval in intervals.iter() {
+        // map<(is not global, location), map<column, value>>
+        let mut location_values: BTreeMap<(bool, &str), BTreeMap<Column, f64>> = BTreeMap::new();
+
+        for (sink_name, sink) in interval.sinks.iter() {
+            let (location, sink_suffix) = match sink_name.split_once("::") {
+                Some(parts) => parts,
+                None => continue,
+            };
+
+            for (label, value) in sink.labels.iter() {
+                if !columns.contains(&(sink_suffix, label.as_str())) {
+                    continue;
+                }
+
+                // Global rows come first for each period
+                location_values
+                    .entry((location != GLOBAL_LOCATION, location))
+                    .or_default()
+                    .insert((sink_suffix, label.as_str()), *value);
+            }
+        }
+
+        for ((_, location), values) in location_values.iter() {
+            let mut row = vec![interval.period.to_string()];
+
+            if let Some(start_time) = options.start_time {
+                row.push(period_timestamp(
+                    start_time,
+                    options.interval_seconds.unwrap_or(INTERVAL_PERIOD_SECONDS),
+                    interval.period,
+                ));
+            }
+
+            row.push(location.to_string());
+
+            for column in columns.iter() {
+                row.push(match values.get(column) {
+                    Some(value) => value.to_string(),
+                    None => String::new(),
+                });
+            }
+
+            push_row(&mut csv, &row);
+        }
+    }
+
+    csv
+}
+
+// Start of the period in RFC 3339 format
+fn period_timestamp(start_time: DateTime<Utc>, interval_seconds: u32, period: i32) -> String {
+    let offset = Duration::seconds(period as i64 * interval_seconds as i64);
+
+    (start_time + offset).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn push_row(csv: &mut String, cells: &[String]) {
+    let cells: Vec<String> = cells.iter().map(|x| escape_cell(x)).collect();
+
+    csv.push_str(&cells.join(","));
+    csv.push('\n');
+}
+
+// Sink names can contain any characters, so quote cells where needed
+fn escape_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{exporters::parse_sink_path, manager::TestInfoManager, types};
+
+impl TestInfoManager {
+    // The start time defaults to the recorded start of the run
+    pub fn export_influx(&self, start_time: Option<DateTime<Utc>>) -> Result<String, String> {
+        let start_time = start_time
+            .or_else(|| self.start_time())
+            .ok_or_else(|| "A start time is required".to_string())?;
+
+        intervals_to_influx(
+            &self.test_info.intervals,
+            start_time,
+            self.interval_seconds(),
+        )
+    }
+}
+
+// Writes a line per sink in every interval, timestamped in nanoseconds at the
+// start of the interval's period
+pub fn intervals_to_influx(
+    intervals: &[types::Interval],
+    start_time: DateTime<Utc>,
+    interval_seconds: u32,
+) -> Result<String, String> {
+    let start_nanos = start_time
+        .timestamp_nanos_opt()
+        .ok_or_else(|| format!("Start time out of range: {}", start_time))?;
+
+    let mut text = String::new();
+
+    for interval in intervals.iter() {
+        let timestamp =
+            start_nanos + interval.period as i64 * interval_seconds as i64 * 1_000_000_000;
+
+        let sinks: BTreeMap<&String, &types::Sink> = interval.sinks.iter().collect();
+
+        for (sink_name, sink) in sinks {
+            let sink_path = match parse_sink_path(sink_name) {
+                Some(sink_path) => sink_path,
+                None => continue,
+            };
+
+            // Tags are sorted by key as recommended for write performance,
+            // empty values aren't allowed
+            let mut tags: Vec<(&str, &str)> = vec![("location", sink_path.location)];
+            tags.extend(sink_path.tags.iter().copied());
+            tags.retain(|(_, value)| !value.is_empty());
+            tags.sort();
+
+            // Influx has no representation for NaN or infinite values
+            let fields: BTreeMap<&String, &f64> = sink
+                .labels
+                .iter()
+                .filter(|(_, value)| value.is_finite())
+                .collect();
+
+            if fields.is_empty() {
+                continue;
+            }
+
+            let mut line = escape(sink_path.metric, &[',', ' ']);
+
+            for (key, value) in tags.iter() {
+                line.push_str(&format!(
+                    ",{}={}",
+                    escape(key, &[',', '=', ' ']),
+                    escape(value, &[',', '=', ' '])
+                ));
+            }
+
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("{}={}", escape(key, &[',', '=', ' ']), value))
+                .collect();
+
+            line.push_str(&format!(" {} {}\n", fields.join(","), timestamp));
+            text.push_str(&line);
+        }
+    }
+
+    Ok(text)
+}
+
+// Escapes the given special characters with a backslash
+fn escape(value: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
@@ -0,0 +1,35 @@
+pub mod csv;
+pub mod influx;
+pub mod openmetrics;
+
+// The parts of a sink name such as "us-east1::checks::check::status is 200"
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkPath<'a> {
+    pub location: &'a str,
+    pub metric: &'a str,
+    // Segments after the metric are tag keys and values, a trailing segment
+    // without a value is given the key "tag"
+    pub tags: Vec<(&'a str, &'a str)>,
+}
+
+pub fn parse_sink_path(sink_name: &str) -> Option<SinkPath<'_>> {
+    let mut segments = sink_name.split("::");
+
+    let location = segments.next()?;
+    let metric = segments.next()?;
+
+    let mut tags = Vec::new();
+
+    while let Some(key) = segments.next() {
+        match segments.next() {
+            Some(value) => tags.push((key, value)),
+            None => tags.push(("tag", key)),
+        }
+    }
+
+    Some(SinkPath {
+        location,
+        metric,
+        tags,
+    })
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    exporters::{parse_sink_path, SinkPath},
+    manager::TestInfoManager,
+    sketch::parse_percentile_label,
+    types,
+};
+
+// Matches the prefix used by k6's own Prometheus output
+const METRIC_PREFIX: &str = "k6_";
+
+// Quantiles given for trend summaries when the sink has a sketch
+const SKETCH_QUANTILES: [f64; 6] = [0.0, 0.5, 0.9, 0.95, 0.99, 1.0];
+
+struct Family {
+    metric_type: &'static str,
+    // Rendered sample lines, grouped by their labels other than the quantile
+    // so that the samples of each label set stay together in a stable order
+    samples: BTreeMap<String, Vec<String>>,
+}
+
+impl TestInfoManager {
+    // Renders the summary and the latest interval as OpenMetrics text, with
+    // a "scope" label of "summary" or "interval" to tell them apart
+    pub fn export_openmetrics(&self) -> String {
+        render_openmetrics(self.test_info.summary.as_ref(), self.test_info.intervals.last())
+    }
+}
+
+pub fn render_openmetrics(
+    summary: Option<&types::Interval>,
+    latest_interval: Option<&types::Interval>,
+) -> String {
+    let mut families: BTreeMap<String, Family> = BTreeMap::new();
+
+    for (scope, interval) in [("summary", summary), ("interval", latest_interval)] {
+        let interval = match interval {
+            Some(interval) => interval,
+            None => continue,
+        };
+
+        for (sink_name, sink) in interval.sinks.iter() {
+            if let Some(sink_path) = parse_sink_path(sink_name) {
+                add_sink(&mut families, scope, &sink_path, sink);
+            }
+        }
+    }
+
+    if let Some(latest_interval) = latest_interval {
+        add_sample(
+            &mut families,
+            &format!("{}period", METRIC_PREFIX),
+            "gauge",
+            "",
+            &[],
+            latest_interval.period as f64,
+        );
+    }
+
+    let mut text = String::new();
+
+    for (name, family) in families.iter() {
+        text.push_str(&format!("# TYPE {} {}\n", name, family.metric_type));
+        for sample in family.samples.values().flatten() {
+            text.push_str(sample);
+            text.push('\n');
+        }
+    }
+
+    text.push_str("# EOF\n");
+    text
+}
+
+fn add_sink(
+    families: &mut BTreeMap<String, Family>,
+    scope: &str,
+    sink_path: &SinkPath,
+    sink: &types::Sink,
+) {
+    let name = format!("{}{}", METRIC_PREFIX, sanitize_name(sink_path.metric));
+
+    let mut labels: Vec<(String, String)> = vec![
+        ("scope".to_string(), scope.to_string()),
+        ("location".to_string(), sink_path.location.to_string()),
+    ];
+    labels.extend(
+        sink_path
+            .tags
+            .iter()
+            .map(|(key, value)| (sanitize_name(key), value.to_string())),
+    );
+
+    match sink.type_.enum_value() {
+        Ok(types::SinkType::Counter) => {
+            if let Some(count) = sink.labels.get("count") {
+                add_sample(families, &name, "counter", "_total", &labels, *count);
+            }
+        }
+        Ok(types::SinkType::Gauge) => {
+            if let Some(value) = sink.labels.get("value") {
+                add_sample(families, &name, "gauge", "", &labels, *value);
+            }
+        }
+        Ok(types::SinkType::Rate) => {
+            let pass = sink.labels.get("pass").copied().unwrap_or(0.0);
+            let fail = match sink.labels.get("fail") {
+                Some(fail) => *fail,
+                None => sink.labels.get("total").copied().unwrap_or(0.0) - pass,
+            };
+
+            // Only the summary counts from the start of the run, so the counts
+            // of the latest interval are gauges in families of their own
+            let (name, metric_type, suffix) = match scope {
+                "summary" => (name, "counter", "_total"),
+                _ => (format!("{}_{}", name, scope), "gauge", ""),
+            };
+
+            for (outcome, value) in [("pass", pass), ("fail", fail)] {
+                let name = format!("{}_{}", name, outcome);
+                add_sample(families, &name, metric_type, suffix, &labels, value);
+            }
+        }
+        Ok(types::SinkType::Trend) => add_trend(families, &name, &labels, sink),
+        Err(_) => {}
+    }
+}
+
+fn add_trend(
+    families: &mut BTreeMap<String, Family>,
+    name: &str,
+    labels: &[(String, String)],
+    sink: &types::Sink,
+) {
+    let mut quantiles: Vec<(f64, f64)> = Vec::new();
+
+    match sink.sketch.as_ref() {
+        Some(sketch) if sketch.count > 0.0 => {
+            for quantile in SKETCH_QUANTILES.iter() {
+                if let Some(value) = sketch.quantile(*quantile) {
+                    quantiles.push((*quantile, value));
+                }
+            }
+        }
+        _ => {
+            for (label, value) in sink.labels.iter() {
+                let quantile = match label.as_str() {
+                    "min" => 0.0,
+                    "max" => 1.0,
+                    _ => match parse_percentile_label(label) {
+                        Some(percentile) => percentile / 100.0,
+                        None => continue,
+                    },
+                };
+
+                quantiles.push((quantile, *value));
+            }
+
+            quantiles.sort_by(|a, b| a.0.total_cmp(&b.0));
+        }
+    }
+
+    for (quantile, value) in quantiles.iter() {
+        let mut quantile_labels = labels.to_vec();
+        quantile_labels.push(("quantile".to_string(), quantile.to_string()));
+
+        add_sample(families, name, "summary", "", &quantile_labels, *value);
+    }
+
+    let (count, sum) = match sink.sketch.as_ref() {
+        Some(sketch) if sketch.count > 0.0 => (Some(sketch.count), Some(sketch.sum)),
+        _ => {
+            let count = sink.labels.get("count").copied();
+            let sum = count.zip(sink.labels.get("avg")).map(|(count, avg)| count * avg);
+            (count, sum)
+        }
+    };
+
+    if let Some(count) = count {
+        add_sample(families, name, "summary", "_count", labels, count);
+    }
+    if let Some(sum) = sum {
+        add_sample(families, name, "summary", "_sum", labels, sum);
+    }
+}
+
+fn add_sample(
+    families: &mut BTreeMap<String, Family>,
+    name: &str,
+    metric_type: &'static str,
+    suffix: &str,
+    labels: &[(String, String)],
+    value: f64,
+) {
+    let family = families.entry(name.to_string()).or_insert(Family {
+        metric_type,
+        samples: BTreeMap::new(),
+    });
+
+    let group = render_labels(labels.iter().filter(|(key, _)| key != "quantile"));
+
+    family.samples.entry(group).or_default().push(format!(
+        "{}{}{} {}",
+        name,
+        suffix,
+        render_labels(labels.iter()),
+        format_value(value)
+    ));
+}
+
+fn render_labels<'a>(labels: impl Iterator<Item = &'a (String, String)>) -> String {
+    let labels: Vec<String> = labels
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect();
+
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", labels.join(","))
+    }
+}
+
+// Metric and label names may only contain letters, digits, underscores and
+// colons, and may not start with a digit
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' })
+        .collect();
+
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+
+    sanitized
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,42 @@
+// Matches text against a glob pattern, where '*' matches any run of characters
+// (including '::' separators) and '?' matches a single character
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let mut pattern_index = 0;
+    let mut text_index = 0;
+
+    // Position of the last '*' and the text position it was tried from, so
+    // that it can be extended if the rest of the pattern fails to match
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while text_index < text.len() {
+        match pattern.get(pattern_index) {
+            Some('*') => {
+                backtrack = Some((pattern_index, text_index));
+                pattern_index += 1;
+            }
+            Some(c) if *c == '?' || *c == text[text_index] => {
+                pattern_index += 1;
+                text_index += 1;
+            }
+            _ => match backtrack {
+                Some((star_index, star_text_index)) => {
+                    pattern_index = star_index + 1;
+                    text_index = star_text_index + 1;
+                    backtrack = Some((star_index, star_text_index + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    // Any remaining pattern must be stars matching nothing
+    pattern[pattern_index..].iter().all(|c| *c == '*')
+}
+
+// Returns if the text matches any of the patterns, or if there are none
+pub fn glob_match_any(patterns: &[String], text: &str) -> bool {
+    patterns.is_empty() || patterns.iter().any(|pattern| glob_match(pattern, text))
+}
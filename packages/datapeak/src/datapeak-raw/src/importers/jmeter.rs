@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use chrono::DateTime;
+
+use crate::{
+    importers::{Sample, SampleBuckets},
+    manager::TestInfoManager,
+    types,
+};
+
+#[derive(Debug, Clone)]
+pub struct JMeterImportOptions {
+    // JMeter results have no location, so every sample is given this one
+    pub location: String,
+}
+
+impl Default for JMeterImportOptions {
+    fn default() -> JMeterImportOptions {
+        JMeterImportOptions {
+            location: "local".to_string(),
+        }
+    }
+}
+
+// Columns that every results file must have, the rest are optional
+const REQUIRED_COLUMNS: [&str; 4] = ["timeStamp", "elapsed", "label", "success"];
+
+impl TestInfoManager {
+    pub fn from_jmeter_csv(
+        reader: impl BufRead,
+        options: &JMeterImportOptions,
+    ) -> Result<TestInfoManager, String> {
+        import_jmeter_csv(reader, options)
+    }
+}
+
+// Reads a JMeter .jtl results file saved as CSV with a header row. Samples are
+// mapped to the metrics k6 would record for the same requests, both overall
+// and for each sampler label. Records are read one at a time, so large files
+// are never held in memory.
+pub fn import_jmeter_csv(
+    mut reader: impl BufRead,
+    options: &JMeterImportOptions,
+) -> Result<TestInfoManager, String> {
+    let mut line = String::new();
+
+    let header =
+        read_record(&mut reader, &mut line)?.ok_or_else(|| "No header row found".to_string())?;
+    let columns: HashMap<&str, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(index, column)| (column.as_str(), index))
+        .collect();
+
+    for column in REQUIRED_COLUMNS.iter() {
+        if !columns.contains_key(column) {
+            return Err(format!("Missing column: {}", column));
+        }
+    }
+
+    let mut buckets = SampleBuckets::default();
+
+    // The header is the first row
+    let mut row = 1;
+
+    while let Some(record) = read_record(&mut reader, &mut line)? {
+        row += 1;
+
+        let cell = |column: &str| -> Option<&str> {
+            columns
+                .get(column)
+                .and_then(|index| record.get(*index))
+                .map(|x| x.as_str())
+        };
+
+        let number = |column: &str| -> Result<Option<f64>, String> {
+            match cell(column) {
+                Some(value) if !value.is_empty() => value
+                    .trim()
+                    .parse::<f64>()
+                    .map(Some)
+                    .map_err(|_| format!("Invalid {} on row {}: {}", column, row, value)),
+                _ => Ok(None),
+            }
+        };
+
+        let time = number("timeStamp")?
+            .and_then(|x| DateTime::from_timestamp_millis(x as i64))
+            .ok_or_else(|| format!("Invalid timeStamp on row {}", row))?;
+        let elapsed =
+            number("elapsed")?.ok_or_else(|| format!("Missing elapsed on row {}", row))?;
+        let label = cell("label").unwrap_or_default();
+        let failed = !cell("success")
+            .unwrap_or_default()
+            .eq_ignore_ascii_case("true");
+
+        let mut push = |sink_suffix: String, sink_type: types::SinkType, value: f64| {
+            buckets.add(Sample {
+                time,
+                sink_name: &format!("{}::{}", options.location, sink_suffix),
+                sink_type,
+                value,
+            })
+        };
+
+        for metric in ["http_req_duration", "http_reqs", "http_req_failed"] {
+            let (sink_type, value) = match metric {
+                "http_req_duration" => (types::SinkType::Trend, elapsed),
+                "http_reqs" => (types::SinkType::Counter, 1.0),
+                _ => (types::SinkType::Rate, if failed { 1.0 } else { 0.0 }),
+            };
+
+            push(metric.to_string(), sink_type, value)?;
+
+            if !label.is_empty() {
+                push(format!("{}::label::{}", metric, label), sink_type, value)?;
+            }
+        }
+
+        if let Some(bytes) = number("bytes")? {
+            push("data_received".to_string(), types::SinkType::Counter, bytes)?;
+        }
+        if let Some(sent_bytes) = number("sentBytes")? {
+            push(
+                "data_sent".to_string(),
+                types::SinkType::Counter,
+                sent_bytes,
+            )?;
+        }
+        if let Some(all_threads) = number("allThreads")? {
+            push("vus".to_string(), types::SinkType::Gauge, all_threads)?;
+        }
+    }
+
+    buckets.build_manager(&[])
+}
+
+// Reads the next CSV record, skipping blank lines. Quoted cells may contain
+// commas, escaped quotes and newlines, so a record can span several lines.
+// `line` is reused between calls to save allocating for every line.
+fn read_record(
+    reader: &mut impl BufRead,
+    line: &mut String,
+) -> Result<Option<Vec<String>>, String> {
+    let mut record: Vec<String> = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+
+    loop {
+        line.clear();
+        if reader.read_line(line).map_err(|e| e.to_string())? == 0 {
+            if quoted {
+                return Err("Unterminated quoted cell".to_string());
+            }
+            return Ok(None);
+        }
+
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if quoted {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        cell.push('"');
+                    }
+                    '"' => quoted = false,
+                    _ => cell.push(c),
+                }
+                continue;
+            }
+
+            match c {
+                '"' => quoted = true,
+                ',' => record.push(std::mem::take(&mut cell)),
+                '\r' | '\n' => {}
+                _ => cell.push(c),
+            }
+        }
+
+        // The record continues on the next line
+        if quoted {
+            continue;
+        }
+
+        record.push(std::mem::take(&mut cell));
+
+        // Skip blank lines
+        if record.len() > 1 || !record[0].is_empty() {
+            return Ok(Some(record));
+        }
+        record.clear();
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::BufRead;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    importers::{Sample, SampleBuckets},
+    manager::TestInfoManager,
+    thresholds::metric_to_sink_suffix,
+    types,
+};
+
+#[derive(Debug, Clone)]
+pub struct K6ImportOptions {
+    // Location used for points without a "load_zone" tag
+    pub location: String,
+}
+
+impl Default for K6ImportOptions {
+    fn default() -> K6ImportOptions {
+        K6ImportOptions {
+            location: "local".to_string(),
+        }
+    }
+}
+
+// A line of k6 `--out json` output
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum Line {
+    Metric { metric: String, data: MetricData },
+    Point { metric: String, data: PointData },
+}
+
+#[derive(Deserialize)]
+struct MetricData {
+    #[serde(rename = "type")]
+    metric_type: String,
+    #[serde(default)]
+    thresholds: Option<Vec<String>>,
+    #[serde(default)]
+    submetrics: Option<Vec<Submetric>>,
+}
+
+#[derive(Deserialize)]
+struct Submetric {
+    // Full name with the tag selector, eg. "http_req_duration{status:200}"
+    name: String,
+    #[serde(default)]
+    tags: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+struct PointData {
+    time: String,
+    value: f64,
+    #[serde(default)]
+    tags: Option<HashMap<String, String>>,
+}
+
+struct Metric {
+    sink_type: types::SinkType,
+    // Sink suffixes of submetrics and the tags a point needs to be included
+    submetrics: Vec<(String, HashMap<String, String>)>,
+}
+
+impl TestInfoManager {
+    pub fn from_k6_json(
+        reader: impl BufRead,
+        options: &K6ImportOptions,
+    ) -> Result<TestInfoManager, String> {
+        import_k6_json(reader, options)
+    }
+}
+
+// Reads newline delimited JSON written by k6 with `--out json`, points are
+// bucketed into intervals and thresholds are taken from the metric lines
+pub fn import_k6_json(
+    reader: impl BufRead,
+    options: &K6ImportOptions,
+) -> Result<TestInfoManager, String> {
+    let mut metrics: HashMap<String, Metric> = HashMap::new();
+    let mut thresholds: Vec<types::Threshold> = Vec::new();
+    let mut buckets = SampleBuckets::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let line: Line = serde_json::from_str(&line)
+            .map_err(|e| format!("Invalid k6 JSON on line {}: {}", index + 1, e))?;
+
+        match line {
+            Line::Metric { metric, data } => {
+                for source in data.thresholds.unwrap_or_default().into_iter() {
+                    let mut threshold = types::Threshold::new();
+                    threshold.metric = metric.to_string();
+                    threshold.source = source;
+                    thresholds.push(threshold);
+                }
+
+                let submetrics = data
+                    .submetrics
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|x| (metric_to_sink_suffix(&x.name), x.tags.unwrap_or_default()))
+                    .collect();
+
+                metrics.insert(
+                    metric,
+                    Metric {
+                        sink_type: parse_metric_type(&data.metric_type)?,
+                        submetrics,
+                    },
+                );
+            }
+            Line::Point { metric, data } => {
+                let definition = metrics.get(&metric).ok_or_else(|| {
+                    format!(
+                        "Point on line {} is for unknown metric {}",
+                        index + 1,
+                        metric
+                    )
+                })?;
+
+                let time = DateTime::parse_from_rfc3339(&data.time)
+                    .map_err(|e| format!("Invalid time on line {}: {}", index + 1, e))?
+                    .with_timezone(&Utc);
+
+                let tags = data.tags.unwrap_or_default();
+
+                let location = match tags.get("load_zone") {
+                    Some(load_zone) if !load_zone.is_empty() => load_zone.as_str(),
+                    _ => options.location.as_str(),
+                };
+
+                for sink_suffix in sink_suffixes(&metric, definition, &tags) {
+                    buckets.add(Sample {
+                        time,
+                        sink_name: &format!("{}::{}", location, sink_suffix),
+                        sink_type: definition.sink_type,
+                        value: data.value,
+                    })?;
+                }
+            }
+        }
+    }
+
+    buckets.build_manager(&thresholds)
+}
+
+fn parse_metric_type(metric_type: &str) -> Result<types::SinkType, String> {
+    match metric_type {
+        "counter" => Ok(types::SinkType::Counter),
+        "gauge" => Ok(types::SinkType::Gauge),
+        "rate" => Ok(types::SinkType::Rate),
+        "trend" => Ok(types::SinkType::Trend),
+        _ => Err(format!("Invalid metric type: {}", metric_type)),
+    }
+}
+
+// The sinks a point contributes to, a set as a submetric may name the same
+// sink as a check
+fn sink_suffixes(
+    metric: &str,
+    definition: &Metric,
+    tags: &HashMap<String, String>,
+) -> BTreeSet<String> {
+    let mut sink_suffixes = BTreeSet::new();
+    sink_suffixes.insert(metric.to_string());
+
+    if metric == "checks" {
+        if let Some(check) = tags.get("check") {
+            sink_suffixes.insert(format!("checks::check::{}", check));
+        }
+
+        // Nested groups are joined with "::" by k6, which would split the sink
+        // path, so they are written as "parent / child" instead
+        if let Some(group) = tags.get("group") {
+            let group = group.trim_start_matches("::").replace("::", " / ");
+
+            if !group.is_empty() {
+                sink_suffixes.insert(format!("checks::group::{}", group));
+            }
+        }
+
+        if let Some(scenario) = tags.get("scenario") {
+            if !scenario.is_empty() {
+                sink_suffixes.insert(format!("checks::scenario::{}", scenario));
+            }
+        }
+    }
+
+    for (sink_suffix, submetric_tags) in definition.submetrics.iter() {
+        if submetric_tags
+            .iter()
+            .all(|(key, value)| tags.get(key) == Some(value))
+        {
+            sink_suffixes.insert(sink_suffix.to_string());
+        }
+    }
+
+    sink_suffixes
+}
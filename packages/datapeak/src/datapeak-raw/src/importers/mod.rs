@@ -0,0 +1,237 @@
+pub mod jmeter;
+pub mod k6;
+
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    intervals::INTERVAL_PERIOD_SECONDS, manager::TestInfoManager,
+    sketch::DEFAULT_RELATIVE_ACCURACY, types,
+};
+
+// A single measurement read from another tool's output
+#[derive(Debug, Clone)]
+pub struct Sample<'a> {
+    pub time: DateTime<Utc>,
+    // Full sink name including the location, eg. "us-east1::http_reqs"
+    pub sink_name: &'a str,
+    pub sink_type: types::SinkType,
+    pub value: f64,
+}
+
+enum Aggregate {
+    // Sum of the values within the interval
+    Counter(f64),
+    Gauge {
+        value: f64,
+        // Time of the value, so the last one is kept whatever order samples
+        // are read in
+        time: DateTime<Utc>,
+        min: f64,
+        max: f64,
+    },
+    Rate {
+        pass: f64,
+        total: f64,
+    },
+    Trend(types::Sketch),
+}
+
+impl Aggregate {
+    fn new(sink_type: types::SinkType, time: DateTime<Utc>, value: f64) -> Aggregate {
+        match sink_type {
+            types::SinkType::Counter => Aggregate::Counter(value),
+            types::SinkType::Gauge => Aggregate::Gauge {
+                value,
+                time,
+                min: value,
+                max: value,
+            },
+            types::SinkType::Rate => Aggregate::Rate {
+                pass: if value != 0.0 { 1.0 } else { 0.0 },
+                total: 1.0,
+            },
+            types::SinkType::Trend => {
+                let mut sketch = types::Sketch::with_relative_accuracy(DEFAULT_RELATIVE_ACCURACY);
+                sketch.add(value);
+                Aggregate::Trend(sketch)
+            }
+        }
+    }
+
+    fn add(&mut self, time: DateTime<Utc>, value: f64) {
+        match self {
+            Aggregate::Counter(sum) => *sum += value,
+            Aggregate::Gauge {
+                value: last,
+                time: last_time,
+                min,
+                max,
+            } => {
+                if time >= *last_time {
+                    *last = value;
+                    *last_time = time;
+                }
+                *min = min.min(value);
+                *max = max.max(value);
+            }
+            Aggregate::Rate { pass, total } => {
+                if value != 0.0 {
+                    *pass += 1.0;
+                }
+                *total += 1.0;
+            }
+            Aggregate::Trend(sketch) => sketch.add(value),
+        }
+    }
+}
+
+// Buckets samples into intervals as they are read, so that only an aggregate
+// for each sink and period is held rather than every sample. The intervals are
+// then processed like streamed intervals, so that derived fields, global sinks,
+// checks and the summary are calculated exactly as they would be live.
+#[derive(Default)]
+pub struct SampleBuckets {
+    // Periods are counted from the first sample read
+    first_time: Option<DateTime<Utc>>,
+    // Each sink name is stored once and aggregates refer to it by index
+    sink_indexes: HashMap<String, usize>,
+    sinks: Vec<(String, types::SinkType)>,
+    // map<period, map<sink_index, aggregate>>
+    periods: BTreeMap<i32, HashMap<usize, Aggregate>>,
+}
+
+impl SampleBuckets {
+    pub fn add(&mut self, sample: Sample) -> Result<(), String> {
+        let first_time = *self.first_time.get_or_insert(sample.time);
+
+        let milliseconds = (sample.time - first_time).num_milliseconds();
+        let period = milliseconds.div_euclid(INTERVAL_PERIOD_SECONDS as i64 * 1000) as i32;
+
+        let sink_index = match self.sink_indexes.get(sample.sink_name) {
+            Some(sink_index) => *sink_index,
+            None => {
+                let sink_index = self.sinks.len();
+                self.sinks
+                    .push((sample.sink_name.to_string(), sample.sink_type));
+                self.sink_indexes
+                    .insert(sample.sink_name.to_string(), sink_index);
+                sink_index
+            }
+        };
+
+        if self.sinks[sink_index].1 != sample.sink_type {
+            return Err(format!(
+                "Sink {} has samples of different types",
+                sample.sink_name
+            ));
+        }
+
+        match self.periods.entry(period).or_default().entry(sink_index) {
+            Entry::Occupied(mut entry) => entry.get_mut().add(sample.time, sample.value),
+            Entry::Vacant(entry) => {
+                entry.insert(Aggregate::new(sample.sink_type, sample.time, sample.value));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn build_manager(
+        mut self,
+        thresholds: &[types::Threshold],
+    ) -> Result<TestInfoManager, String> {
+        let mut manager = TestInfoManager::new(None);
+
+        for threshold in thresholds.iter() {
+            manager.process_threshold(threshold)?;
+        }
+
+        let (first_time, first_period, last_period) = match (
+            self.first_time,
+            self.periods.keys().next(),
+            self.periods.keys().next_back(),
+        ) {
+            (Some(first_time), Some(first_period), Some(last_period)) => {
+                (first_time, *first_period, *last_period)
+            }
+            _ => return Ok(manager),
+        };
+
+        // Samples read out of order can fall before the first sample read, so
+        // the run starts at the beginning of the earliest period. Periods stay
+        // aligned to the first sample read, as they have already been bucketed.
+        let start_time =
+            first_time + Duration::seconds(first_period as i64 * INTERVAL_PERIOD_SECONDS as i64);
+
+        manager.set_run_info(Some(start_time), Some(INTERVAL_PERIOD_SECONDS))?;
+
+        // Counter counts are running totals, carried through periods without
+        // samples so that rates are calculated against the right count
+        let mut counter_totals: BTreeMap<usize, f64> = BTreeMap::new();
+
+        for period in first_period..=last_period {
+            let mut interval = types::Interval::new();
+            interval.period = period - first_period;
+
+            for (sink_index, aggregate) in self.periods.remove(&period).unwrap_or_default() {
+                let (sink_name, sink_type) = &self.sinks[sink_index];
+
+                if let Aggregate::Counter(sum) = aggregate {
+                    *counter_totals.entry(sink_index).or_insert(0.0) += sum;
+                    continue;
+                }
+
+                interval.sinks.insert(
+                    sink_name.to_string(),
+                    aggregate_sink(*sink_type, &aggregate),
+                );
+            }
+
+            for (sink_index, total) in counter_totals.iter() {
+                let mut sink = types::Sink::new();
+                sink.type_ = types::SinkType::Counter.into();
+                sink.labels.insert("count".to_string(), *total);
+
+                interval
+                    .sinks
+                    .insert(self.sinks[*sink_index].0.to_string(), sink);
+            }
+
+            manager.process_interval(&interval)?;
+        }
+
+        Ok(manager)
+    }
+}
+
+// Builds a sink with the same labels that streamed sinks carry
+fn aggregate_sink(sink_type: types::SinkType, aggregate: &Aggregate) -> types::Sink {
+    let mut sink = types::Sink::new();
+    sink.type_ = sink_type.into();
+
+    match aggregate {
+        Aggregate::Counter(sum) => {
+            sink.labels.insert("count".to_string(), *sum);
+        }
+        Aggregate::Gauge {
+            value, min, max, ..
+        } => {
+            sink.labels.insert("value".to_string(), *value);
+            sink.labels.insert("min".to_string(), *min);
+            sink.labels.insert("max".to_string(), *max);
+        }
+        Aggregate::Rate { pass, total } => {
+            sink.labels.insert("pass".to_string(), *pass);
+            sink.labels.insert("total".to_string(), *total);
+            sink.labels.insert("rate".to_string(), pass / total);
+        }
+        Aggregate::Trend(sketch) => {
+            sink.labels = sketch.trend_labels().into_iter().collect();
+            sink.sketch = protobuf::MessageField::some(sketch.clone());
+        }
+    }
+
+    sink
+}
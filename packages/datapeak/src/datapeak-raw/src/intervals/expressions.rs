@@ -0,0 +1,364 @@
+use crate::{
+    intervals::{
+        merge::GLOBAL_LOCATION,
+        time_series::{downsize, DownsizingMethod, TimeSeries},
+        window::Window,
+    },
+    manager::TestInfoManager,
+    sketch::sink_label_value,
+    types,
+};
+
+// A computed series, e.g. http_req_failed::fail / http_reqs::count
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    // A path of the form <sink>::<label>
+    Path(String),
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Function(Function, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    // Per second change since the previous interval
+    Rate,
+    // Change since the previous interval
+    Delta,
+    Abs,
+}
+
+impl TestInfoManager {
+    // Evaluates an expression for every interval inside the window, e.g.
+    // rate(iterations::count) or us-east1::http_reqs::rate - eu-west1::http_reqs::rate
+    pub fn expression_time_series(
+        &self,
+        expression: &str,
+        downsizing_method: DownsizingMethod,
+        window: &Window,
+    ) -> Result<TimeSeries, String> {
+        let intervals = &self.test_info.intervals;
+        let window_intervals = self.window_periods(window)?.intervals(intervals)?;
+
+        let first = match window_intervals.first() {
+            Some(first) => intervals.partition_point(|x| x.period < first.period),
+            None => 0,
+        };
+
+        // Evaluated from the interval before the window, if there is one, as
+        // rate and delta need it for the first interval inside
+        let before = first.min(1);
+
+        let data: Vec<(f64, f64)> = evaluate_expression(
+            &intervals[first - before..first + window_intervals.len()],
+            expression,
+            self.interval_seconds(),
+        )?
+        .into_iter()
+        .skip(before)
+        .collect();
+
+        Ok(self.with_timestamps(TimeSeries {
+            name: expression.trim().to_string(),
+            data: downsize(&data, downsizing_method),
+            timestamps: None,
+        }))
+    }
+}
+
+// Evaluates an expression once per interval, giving (period, value) pairs.
+// Values are NaN where a path has no value or for a division by zero.
+pub fn evaluate_expression(
+    intervals: &[types::Interval],
+    expression: &str,
+    interval_seconds: u32,
+) -> Result<Vec<(f64, f64)>, String> {
+    let expression = parse_expression(expression)?;
+    let values = evaluate(intervals, &expression, interval_seconds)?;
+
+    Ok(intervals
+        .iter()
+        .zip(values)
+        .map(|(interval, value)| (interval.period as f64, value))
+        .collect())
+}
+
+fn evaluate(
+    intervals: &[types::Interval],
+    expression: &Expression,
+    interval_seconds: u32,
+) -> Result<Vec<f64>, String> {
+    match expression {
+        Expression::Number(number) => Ok(vec![*number; intervals.len()]),
+        Expression::Path(path) => path_values(intervals, path),
+        Expression::Negate(operand) => Ok(evaluate(intervals, operand, interval_seconds)?
+            .into_iter()
+            .map(|x| -x)
+            .collect()),
+        Expression::Binary(operator, left, right) => {
+            let left = evaluate(intervals, left, interval_seconds)?;
+            let right = evaluate(intervals, right, interval_seconds)?;
+
+            Ok(left
+                .into_iter()
+                .zip(right)
+                .map(|(a, b)| match operator {
+                    Operator::Add => a + b,
+                    Operator::Subtract => a - b,
+                    Operator::Multiply => a * b,
+                    Operator::Divide if b == 0.0 => f64::NAN,
+                    Operator::Divide => a / b,
+                })
+                .collect())
+        }
+        Expression::Function(function, operand) => {
+            let values = evaluate(intervals, operand, interval_seconds)?;
+
+            match function {
+                Function::Abs => Ok(values.into_iter().map(f64::abs).collect()),
+                Function::Delta | Function::Rate => Ok((0..values.len())
+                    .map(|index| {
+                        // The first interval has nothing to change from
+                        if index == 0 {
+                            return f64::NAN;
+                        }
+
+                        let delta = values[index] - values[index - 1];
+
+                        match function {
+                            Function::Rate => {
+                                let periods = intervals[index].period - intervals[index - 1].period;
+                                delta / (interval_seconds as f64 * periods as f64)
+                            }
+                            _ => delta,
+                        }
+                    })
+                    .collect()),
+            }
+        }
+    }
+}
+
+// Paths that aren't a sink in the run are looked up in the global location, so
+// that http_reqs::count is global::http_reqs::count
+fn path_values(intervals: &[types::Interval], path: &str) -> Result<Vec<f64>, String> {
+    let (sink_path, label) = match path.rsplit_once("::") {
+        Some(split) => split,
+        None => return Err(format!("Path {} must be of the form <sink>::<label>", path)),
+    };
+
+    let has_sink = |sink_path: &str| {
+        intervals
+            .iter()
+            .any(|interval| interval.sinks.contains_key(sink_path))
+    };
+
+    let global_path = format!("{}::{}", GLOBAL_LOCATION, sink_path);
+
+    let sink_path = if has_sink(sink_path) {
+        sink_path
+    } else if has_sink(&global_path) {
+        global_path.as_str()
+    } else {
+        return Err(format!("Sink path {} not found", sink_path));
+    };
+
+    Ok(intervals
+        .iter()
+        .map(|interval| {
+            interval
+                .sinks
+                .get(sink_path)
+                .and_then(|sink| sink_label_value(sink, label))
+                .unwrap_or(f64::NAN)
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    // A path or function name
+    Word(String),
+    Operator(Operator),
+    OpenParen,
+    CloseParen,
+}
+
+// Sink names can contain '-', so subtraction must be separated by whitespace
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    let is_word_char = |c: char| c.is_alphanumeric() || "_.:-".contains(c);
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        match c {
+            _ if c.is_whitespace() => index += 1,
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Token::Operator(match c {
+                    '+' => Operator::Add,
+                    '-' => Operator::Subtract,
+                    '*' => Operator::Multiply,
+                    _ => Operator::Divide,
+                }));
+                index += 1;
+            }
+            '(' => {
+                tokens.push(Token::OpenParen);
+                index += 1;
+            }
+            ')' => {
+                tokens.push(Token::CloseParen);
+                index += 1;
+            }
+            _ if is_word_char(c) => {
+                let start = index;
+
+                while index < chars.len() && is_word_char(chars[index]) {
+                    index += 1;
+                }
+
+                // Labels like p(95) are part of the path
+                if index < chars.len() && chars[index] == '(' {
+                    let word: String = chars[start..index].iter().collect();
+
+                    if word.contains("::") {
+                        match chars[index..].iter().position(|c| *c == ')') {
+                            Some(end) => index += end + 1,
+                            None => return Err(format!("Unclosed label in {}", word)),
+                        }
+                    }
+                }
+
+                let word: String = chars[start..index].iter().collect();
+
+                tokens.push(match word.parse::<f64>() {
+                    Ok(number) if !word.contains("::") => Token::Number(number),
+                    _ => Token::Word(word),
+                });
+            }
+            _ => return Err(format!("Unexpected character '{}' at {}", c, index)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Parses an expression of paths, numbers, + - * /, parentheses and the
+// functions rate, delta and abs
+pub fn parse_expression(expression: &str) -> Result<Expression, String> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        index: 0,
+    };
+
+    let parsed = parser.expression()?;
+
+    match parser.tokens.get(parser.index) {
+        Some(token) => Err(format!("Unexpected {:?} in expression", token)),
+        None => Ok(parsed),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.index);
+        self.index += 1;
+        token
+    }
+
+    fn peek_operator(&self, operators: &[Operator]) -> Option<Operator> {
+        match self.tokens.get(self.index) {
+            Some(Token::Operator(operator)) if operators.contains(operator) => Some(*operator),
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expression, String> {
+        let mut left = self.term()?;
+
+        while let Some(operator) = self.peek_operator(&[Operator::Add, Operator::Subtract]) {
+            self.index += 1;
+            left = Expression::Binary(operator, Box::new(left), Box::new(self.term()?));
+        }
+
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expression, String> {
+        let mut left = self.unary()?;
+
+        while let Some(operator) = self.peek_operator(&[Operator::Multiply, Operator::Divide]) {
+            self.index += 1;
+            left = Expression::Binary(operator, Box::new(left), Box::new(self.unary()?));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expression, String> {
+        if self.peek_operator(&[Operator::Subtract]).is_some() {
+            self.index += 1;
+            return Ok(Expression::Negate(Box::new(self.unary()?)));
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expression::Number(*number)),
+            Some(Token::OpenParen) => {
+                let expression = self.expression()?;
+                self.close_paren()?;
+                Ok(expression)
+            }
+            Some(Token::Word(word)) if self.tokens.get(self.index) == Some(&Token::OpenParen) => {
+                let function = match word.as_str() {
+                    "rate" => Function::Rate,
+                    "delta" => Function::Delta,
+                    "abs" => Function::Abs,
+                    _ => return Err(format!("Unknown function {}", word)),
+                };
+
+                self.index += 1;
+                let operand = self.expression()?;
+                self.close_paren()?;
+
+                Ok(Expression::Function(function, Box::new(operand)))
+            }
+            Some(Token::Word(word)) if word.contains("::") => Ok(Expression::Path(word.clone())),
+            Some(Token::Word(word)) => {
+                Err(format!("Path {} must be of the form <sink>::<label>", word))
+            }
+            Some(token) => Err(format!("Unexpected {:?} in expression", token)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+
+    fn close_paren(&mut self) -> Result<(), String> {
+        match self.next() {
+            Some(Token::CloseParen) => Ok(()),
+            _ => Err("Expected ) in expression".to_string()),
+        }
+    }
+}
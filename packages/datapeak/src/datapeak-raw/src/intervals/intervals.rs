@@ -1,23 +1,143 @@
-use js_sys::Reflect;
-use wasm_bindgen::prelude::*;
-
-use crate::{manager::TestInfoManager, types, MANAGERS};
-
-const INTERVAL_PERIOD_SECONDS: u32 = 6;
+use crate::{
+    intervals::{
+        checks::collect_checks,
+        merge::{add_global_sinks, GLOBAL_LOCATION},
+    },
+    manager::TestInfoManager,
+    sketch::remove_invalid_sketch,
+    states::Topic,
+    types,
+};
+
+// Width of each period for runs that don't record their own
+pub const INTERVAL_PERIOD_SECONDS: u32 = 6;
 
 impl TestInfoManager {
     pub fn process_interval(&mut self, interval: &types::Interval) -> Result<(), String> {
-        // Check if period is already in the list
-        self.test_info
+        let new_interval = self.calculate_interval_fields(interval)?;
+
+        // Nothing changes if the same interval is received again
+        if self.test_info.intervals.contains(&new_interval) {
+            return Ok(());
+        }
+
+        self.store_interval(new_interval.clone());
+
+        let mut changed_periods = vec![new_interval.period];
+
+        // Intervals can arrive late or out of order, and the counter rates of the
+        // following interval depend on the counts of this one
+        let following_interval = self
+            .test_info
+            .intervals
+            .iter()
+            .find(|x| x.period > new_interval.period)
+            .cloned();
+
+        if let Some(following_interval) = following_interval {
+            let recalculated_interval = self.calculate_interval_fields(&following_interval)?;
+
+            if recalculated_interval != following_interval {
+                changed_periods.push(recalculated_interval.period);
+                self.store_interval(recalculated_interval);
+            }
+        }
+
+        self.latest_period = self.latest_period.max(new_interval.period);
+
+        // Aggregate the summary interval
+        self.update_summary_interval(&changed_periods)?;
+
+        // Add new locations to the list
+        self.update_locations(&new_interval);
+
+        self.update_anomalies(&changed_periods);
+
+        // Checks and thresholds follow the newest interval. Late intervals are
+        // evaluated as well so that their breaches aren't missed, followed by
+        // the newest interval so that the results stay current.
+        let mut evaluated_intervals: Vec<types::Interval> = self
+            .test_info
             .intervals
-            .retain(|x| x.period != interval.period);
+            .iter()
+            .filter(|x| changed_periods.contains(&x.period))
+            .cloned()
+            .collect();
+
+        if let Some(latest_interval) = self.test_info.intervals.last() {
+            if !changed_periods.contains(&latest_interval.period) {
+                evaluated_intervals.push(latest_interval.clone());
+            }
+        }
+
+        // Update the checks
+        let mut checks = self.checks.clone();
+        for evaluated_interval in evaluated_intervals.iter() {
+            collect_checks(&mut checks, evaluated_interval, &self.test_info.thresholds)?;
+        }
+        self.replace_if_changed(Topic::Checks, |x| &mut x.checks, checks);
+
+        // Evaluate thresholds against the new data
+        self.evaluate_thresholds_over(&evaluated_intervals);
+
+        Ok(())
+    }
 
-        // Add extra fields to the interval
+    // Recalculates the derived fields of every interval, e.g. when the width of
+    // the periods changes. The intervals are left unchanged if any of them
+    // can't be recalculated.
+    pub(crate) fn recalculate_intervals(&mut self) -> Result<(), String> {
+        let intervals = self
+            .test_info
+            .intervals
+            .iter()
+            .map(|interval| self.calculate_interval_fields(interval))
+            .collect::<Result<Vec<types::Interval>, String>>()?;
+
+        let changed_periods: Vec<i32> = intervals
+            .iter()
+            .zip(self.test_info.intervals.iter())
+            .filter(|(new_interval, interval)| new_interval != interval)
+            .map(|(new_interval, _)| new_interval.period)
+            .collect();
+
+        if changed_periods.is_empty() {
+            return Ok(());
+        }
+
+        self.test_info.intervals = intervals;
+
+        let version = self.bump_version(Topic::Intervals);
+        for period in changed_periods.iter() {
+            self.interval_versions.insert(*period, version);
+        }
+
+        self.update_summary_interval(&changed_periods)?;
+        self.update_anomalies(&changed_periods);
+
+        if let Some(latest_interval) = self.test_info.intervals.last().cloned() {
+            self.update_checks(&latest_interval)?;
+        }
+
+        self.evaluate_thresholds_over_run();
+
+        Ok(())
+    }
+
+    // Adds the derived fields and global sinks to an interval
+    fn calculate_interval_fields(
+        &self,
+        interval: &types::Interval,
+    ) -> Result<types::Interval, String> {
         let mut new_interval = types::Interval::new();
         new_interval.period = interval.period;
 
-        // Mean is the count divided by the period
         for (sink_name, sink) in interval.sinks.iter() {
+            // Global sinks are recalculated from the location sinks below
+            if sink_name.split("::").next() == Some(GLOBAL_LOCATION) {
+                continue;
+            }
+
             match self.calculate_sink_fields(sink_name, sink, interval.period) {
                 Ok(sink) => {
                     new_interval.sinks.insert(sink_name.to_string(), sink);
@@ -28,36 +148,31 @@ impl TestInfoManager {
             }
         }
 
-        // Find where to insert the new interval so that the list is sorted
+        // Combine the sinks of every location
+        add_global_sinks(&mut new_interval)?;
+
+        Ok(new_interval)
+    }
+
+    // Inserts an interval in period order, replacing any with the same period
+    fn store_interval(&mut self, interval: types::Interval) {
+        let period = interval.period;
+
+        self.test_info.intervals.retain(|x| x.period != period);
+
         let index = self
             .test_info
             .intervals
             .iter()
-            .position(|x| x.period > new_interval.period)
+            .position(|x| x.period > period)
             .unwrap_or(self.test_info.intervals.len());
 
-        // Insert the new interval
-        self.test_info.intervals.insert(index, new_interval);
+        self.test_info.intervals.insert(index, interval);
 
-        if self.latest_period >= interval.period {
-            return Ok(());
-        }
-
-        self.latest_period = interval.period;
-
-        // Estimate summary intervaval
-        self.update_summary_interval();
-
-        // Add new locations to the list
-        self.update_locations(&interval);
-
-        // Update the checks
-        self.update_checks(&interval);
-
-        Ok(())
+        let version = self.bump_version(Topic::Intervals);
+        self.interval_versions.insert(period, version);
     }
 
-    
     fn calculate_sink_fields(
         &self,
         sink_name: &str,
@@ -72,6 +187,11 @@ impl TestInfoManager {
         match sink_type {
             types::SinkType::Counter => self.calculate_counter_sink_fields(sink_name, sink, period),
             types::SinkType::Rate => self.calculate_rate_sink_fields(sink),
+            types::SinkType::Trend => {
+                let mut sink = sink.clone();
+                remove_invalid_sketch(&mut sink);
+                Ok(sink)
+            }
             _ => Ok(sink.clone()),
         }
     }
@@ -83,41 +203,40 @@ impl TestInfoManager {
         period: i32,
     ) -> Result<types::Sink, String> {
         let count = match sink.labels.get("count") {
-            Some(count) => count.clone(),
+            Some(count) => *count,
             None => return Err("Counter value not found".to_string()),
         };
 
         let mut calculated_sink = sink.clone();
+        let interval_seconds = self.interval_seconds() as f64;
 
         calculated_sink
             .labels
-            .insert("mean".to_string(), count / ( INTERVAL_PERIOD_SECONDS as f64 * (period + 1) as f64));
-
-        // If sink period is 0, then rate is the count, else it is the count
-        // of this interval minus the count of the previous interval
-        // divided by the period
-
-        let rate = if period == 0 {
-            count / INTERVAL_PERIOD_SECONDS as f64
-        } else {
-            let previous_interval = self
-                .test_info
-                .intervals
-                .iter()
-                .find(|x| x.period == (period - 1));
-
-            if let Some(previous_interval) = previous_interval {
+            .insert("mean".to_string(), count / (interval_seconds * (period + 1) as f64));
+
+        // Counts are running totals, so the rate is the increase since the
+        // closest earlier interval divided by the time between them. If there is
+        // no earlier interval yet the count is spread over the whole run, and is
+        // recalculated once the earlier interval arrives.
+        let previous_interval = self
+            .test_info
+            .intervals
+            .iter()
+            .rev()
+            .find(|x| x.period < period);
+
+        let rate = match previous_interval {
+            Some(previous_interval) => {
                 let previous_count = previous_interval
                     .sinks
                     .get(sink_name)
                     .and_then(|sink| sink.labels.get("count"))
                     .unwrap_or(&0.0);
 
-                (count - previous_count) / INTERVAL_PERIOD_SECONDS as f64
-            } else {
-                // Assume no previous interval means that the previous interval was 0
-                count
+                (count - previous_count)
+                    / (interval_seconds * (period - previous_interval.period) as f64)
             }
+            None => count / (interval_seconds * (period + 1) as f64),
         };
 
         calculated_sink.labels.insert("rate".to_string(), rate);
@@ -130,12 +249,12 @@ impl TestInfoManager {
         sink: &types::Sink,
     ) -> Result<types::Sink, String> {
         let pass = match sink.labels.get("pass") {
-            Some(pass) => pass.clone(),
+            Some(pass) => *pass,
             None => return Err("Rate pass value not found".to_string()),
         };
 
         let total = match sink.labels.get("total") {
-            Some(total) => total.clone(),
+            Some(total) => *total,
             None => return Err("Rate total value not found".to_string()),
         };
 
@@ -148,47 +267,3 @@ impl TestInfoManager {
         Ok(calculated_sink)
     }
 }
-
-#[wasm_bindgen (js_name = rawGetIntervalsState)]
-pub fn get_intervals_state(test_info_id: &str) -> Result<String, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
-
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
-
-    Ok(manager.intervals_state.to_owned())
-}
-
-impl From<types::Interval> for Result<JsValue, JsValue> {
-    fn from(interval: types::Interval) -> Self {
-        let obj = js_sys::Object::new();
-
-        Reflect::set(
-            &obj,
-            &JsValue::from_str("period"),
-            &JsValue::from_f64(interval.period as f64),
-        )?;
-
-        let sinks = js_sys::Object::new();
-        for (sink_key, sink_value) in interval.sinks.iter() {
-            let sink = js_sys::Object::new();
-
-            let labels = js_sys::Object::new();
-            for (label, label_value) in sink_value.labels.iter() {
-                Reflect::set(
-                    &labels,
-                    &JsValue::from_str(label.as_str()),
-                    &JsValue::from_f64(*label_value),
-                )?;
-            }
-            Reflect::set(&sink, &JsValue::from_str("labels"), &labels)?;
-
-            Reflect::set(&sinks, &JsValue::from_str(sink_key.as_str()), &sink)?;
-        }
-        Reflect::set(&obj, &JsValue::from_str("sinks"), &sinks)?;
-
-        Ok(JsValue::from(obj))
-    }
-}
@@ -0,0 +1,356 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{sketch::merge_sink_sketches, types};
+
+// Synthetic location holding the sinks of all locations combined
+pub const GLOBAL_LOCATION: &str = "global";
+
+// Adds a global sink for every metric in the interval, replacing any that are
+// already present, so that each metric can be queried across all locations.
+// Metrics recorded with different sink types in different locations can't be
+// combined, so they get no global sink.
+pub fn add_global_sinks(interval: &mut types::Interval) -> Result<(), String> {
+    interval
+        .sinks
+        .retain(|sink_name, _| sink_name.split("::").next() != Some(GLOBAL_LOCATION));
+
+    let mut location_sinks: BTreeMap<&str, Vec<&types::Sink>> = BTreeMap::new();
+
+    for (sink_name, sink) in interval.sinks.iter() {
+        if let Some((_, sink_suffix)) = sink_name.split_once("::") {
+            location_sinks.entry(sink_suffix).or_default().push(sink);
+        }
+    }
+
+    let mut global_sinks: Vec<(String, types::Sink)> = Vec::new();
+
+    for (sink_suffix, sinks) in location_sinks.iter() {
+        if sinks.iter().any(|x| x.type_ != sinks[0].type_) {
+            continue;
+        }
+
+        let sink = merge_location_sinks(sinks)
+            .map_err(|err| format!("Error merging locations for {}: {}", sink_suffix, err))?;
+
+        global_sinks.push((format!("{}::{}", GLOBAL_LOCATION, sink_suffix), sink));
+    }
+
+    interval.sinks.extend(global_sinks);
+
+    Ok(())
+}
+
+// Combines the sinks for the same metric from different locations within a
+// single interval into one sink, using the merge rule for the sink type
+pub fn merge_location_sinks(sinks: &[&types::Sink]) -> Result<types::Sink, String> {
+    let first = match sinks.first() {
+        Some(first) => first,
+        None => return Err("No sinks to merge".to_string()),
+    };
+
+    let sink_type = first
+        .type_
+        .enum_value()
+        .map_err(|_| "Unknown sink type".to_string())?;
+
+    if sinks.iter().any(|x| x.type_ != first.type_) {
+        return Err("Cannot merge sinks of different types".to_string());
+    }
+
+    let mut merged = types::Sink::new();
+    merged.type_ = first.type_;
+
+    match sink_type {
+        types::SinkType::Counter => {
+            // Counts and the per second values derived from them can be summed
+            for label in ["count", "rate", "mean"] {
+                if let Some(sum) = sum_label(sinks, label) {
+                    merged.labels.insert(label.to_string(), sum);
+                }
+            }
+        }
+        // The global value is the sum of the location values, so its min and
+        // max are the sums of theirs too, which bound the summed value
+        types::SinkType::Gauge => {
+            for label in ["value", "min", "max"] {
+                if let Some(sum) = sum_label(sinks, label) {
+                    merged.labels.insert(label.to_string(), sum);
+                }
+            }
+        }
+        types::SinkType::Rate => {
+            let pass = sum_label(sinks, "pass").unwrap_or(0.0);
+            let total = sum_label(sinks, "total").unwrap_or(0.0);
+
+            merged.labels.insert("pass".to_string(), pass);
+            merged.labels.insert("fail".to_string(), total - pass);
+            merged.labels.insert("total".to_string(), total);
+            merged.labels.insert(
+                "rate".to_string(),
+                if total > 0.0 { pass / total } else { 0.0 },
+            );
+        }
+        types::SinkType::Trend => merge_trend_sinks(sinks, &mut merged)?,
+    }
+
+    Ok(merged)
+}
+
+// Combines the sinks for the same metric across a run of intervals, which must
+// be given in period order
+pub fn merge_period_sinks(sinks: &[&types::Sink]) -> Result<types::Sink, String> {
+    let (first, rest) = match sinks.split_first() {
+        Some(split) => split,
+        None => return Err("No sinks to merge".to_string()),
+    };
+
+    let mut merge = PeriodSinkMerge::new(first)?;
+
+    for sink in rest.iter() {
+        merge.add(sink)?;
+    }
+
+    Ok(merge.sink())
+}
+
+// Running merge of the sinks for the same metric across a run of intervals, so
+// that each new interval's sink can be merged without revisiting the others.
+// Sinks must be added in period order.
+#[derive(Debug, Clone)]
+pub struct PeriodSinkMerge {
+    type_: protobuf::EnumOrUnknown<types::SinkType>,
+    state: PeriodMergeState,
+}
+
+#[derive(Debug, Clone)]
+enum PeriodMergeState {
+    // Labels of the latest sink
+    Counter(HashMap<String, f64>),
+    Gauge {
+        value: Option<f64>,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Rate {
+        pass: f64,
+        total: f64,
+    },
+    Trend {
+        labels: TrendLabels,
+        // None once a sink without a sketch has been added
+        sketch: Option<Box<types::Sketch>>,
+    },
+}
+
+impl PeriodSinkMerge {
+    pub fn new(sink: &types::Sink) -> Result<PeriodSinkMerge, String> {
+        let state = match sink.type_.enum_value() {
+            Ok(types::SinkType::Counter) => PeriodMergeState::Counter(HashMap::new()),
+            Ok(types::SinkType::Gauge) => PeriodMergeState::Gauge {
+                value: None,
+                min: None,
+                max: None,
+            },
+            Ok(types::SinkType::Rate) => PeriodMergeState::Rate {
+                pass: 0.0,
+                total: 0.0,
+            },
+            Ok(types::SinkType::Trend) => PeriodMergeState::Trend {
+                labels: TrendLabels::default(),
+                sketch: Some(Box::default()),
+            },
+            Err(_) => return Err("Unknown sink type".to_string()),
+        };
+
+        let mut merge = PeriodSinkMerge {
+            type_: sink.type_,
+            state,
+        };
+        merge.add(sink)?;
+
+        Ok(merge)
+    }
+
+    pub fn add(&mut self, sink: &types::Sink) -> Result<(), String> {
+        if sink.type_ != self.type_ {
+            return Err("Cannot merge sinks of different types".to_string());
+        }
+
+        match &mut self.state {
+            // Counter counts are running totals, so the latest count is the total
+            // for the run
+            PeriodMergeState::Counter(labels) => *labels = sink.labels.clone(),
+            PeriodMergeState::Gauge { value, min, max } => {
+                *value = sink.labels.get("value").copied();
+
+                for label in ["value", "min", "max"] {
+                    if let Some(label_value) = sink.labels.get(label).copied() {
+                        *min = Some(min.map_or(label_value, |x| x.min(label_value)));
+                        *max = Some(max.map_or(label_value, |x| x.max(label_value)));
+                    }
+                }
+            }
+            // Pass and total are counted per interval
+            PeriodMergeState::Rate { pass, total } => {
+                *pass += sink.labels.get("pass").copied().unwrap_or(0.0);
+                *total += sink.labels.get("total").copied().unwrap_or(0.0);
+            }
+            PeriodMergeState::Trend { labels, sketch } => {
+                labels.add(&sink.labels, trend_weight(sink));
+
+                // A merge of only some of the samples would be misleading
+                match (sketch.as_mut(), sink.sketch.as_ref()) {
+                    (Some(merged), Some(new_sketch)) if new_sketch.count > 0.0 => {
+                        merged.merge(new_sketch)?
+                    }
+                    _ => *sketch = None,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn sink(&self) -> types::Sink {
+        let mut merged = types::Sink::new();
+        merged.type_ = self.type_;
+
+        match &self.state {
+            PeriodMergeState::Counter(labels) => {
+                merged.labels = labels.clone();
+
+                // The per second rate is the mean over the run
+                if let Some(mean) = labels.get("mean") {
+                    merged.labels.insert("rate".to_string(), *mean);
+                }
+            }
+            PeriodMergeState::Gauge { value, min, max } => {
+                for (label, label_value) in [("value", value), ("min", min), ("max", max)] {
+                    if let Some(label_value) = label_value {
+                        merged.labels.insert(label.to_string(), *label_value);
+                    }
+                }
+            }
+            PeriodMergeState::Rate { pass, total } => {
+                merged.labels.insert("pass".to_string(), *pass);
+                merged.labels.insert("fail".to_string(), total - pass);
+                merged.labels.insert("total".to_string(), *total);
+                merged.labels.insert(
+                    "rate".to_string(),
+                    if *total > 0.0 { pass / total } else { 0.0 },
+                );
+            }
+            PeriodMergeState::Trend { labels, sketch } => match sketch {
+                Some(sketch) if sketch.count > 0.0 => {
+                    merged.labels = sketch.trend_labels().into_iter().collect();
+                    merged.sketch = protobuf::MessageField::some(*sketch.clone());
+                }
+                _ => merged.labels = labels.labels(),
+            },
+        }
+
+        merged
+    }
+}
+
+// Trend sinks are merged through their sketches when every sink has one, so
+// that the percentiles are accurate, otherwise their labels are combined
+fn merge_trend_sinks(sinks: &[&types::Sink], merged: &mut types::Sink) -> Result<(), String> {
+    match merge_sink_sketches(sinks)? {
+        Some(sketch) => {
+            merged.labels = sketch.trend_labels().into_iter().collect();
+            merged.sketch = protobuf::MessageField::some(sketch);
+        }
+        None => {
+            merged.labels = merge_trend_labels(sinks.iter().map(|x| (&x.labels, trend_weight(x))));
+        }
+    }
+
+    Ok(())
+}
+
+// Sinks without a count label are weighted by the samples in their sketch, or
+// equally if they don't have one either
+fn trend_weight(sink: &types::Sink) -> f64 {
+    match sink.sketch.as_ref() {
+        Some(sketch) if sketch.count > 0.0 => sketch.count,
+        _ => 1.0,
+    }
+}
+
+// Trend labels are combined as a weighted mean, apart from min and max. If the
+// sinks carry a sample count it is used as the weight, otherwise each sink is
+// weighted by the weight supplied with it.
+pub fn merge_trend_labels<'a>(
+    labels: impl Iterator<Item = (&'a HashMap<String, f64>, f64)>,
+) -> HashMap<String, f64> {
+    let mut merged = TrendLabels::default();
+
+    for (labels, default_weight) in labels {
+        merged.add(labels, default_weight);
+    }
+
+    merged.labels()
+}
+
+// Running weighted sums of trend labels, see merge_trend_labels
+#[derive(Debug, Clone, Default)]
+pub struct TrendLabels {
+    sums: HashMap<String, (f64, f64)>,
+    min: Option<f64>,
+    max: Option<f64>,
+    count: Option<f64>,
+}
+
+impl TrendLabels {
+    pub fn add(&mut self, labels: &HashMap<String, f64>, default_weight: f64) {
+        let weight = labels.get("count").copied().unwrap_or(default_weight);
+
+        for (label, value) in labels.iter() {
+            match label.as_str() {
+                "min" => self.min = Some(self.min.map_or(*value, |x| x.min(*value))),
+                "max" => self.max = Some(self.max.map_or(*value, |x| x.max(*value))),
+                "count" => self.count = Some(self.count.unwrap_or(0.0) + value),
+                _ => {
+                    let entry = self.sums.entry(label.to_string()).or_insert((0.0, 0.0));
+                    entry.0 += value * weight;
+                    entry.1 += weight;
+                }
+            }
+        }
+    }
+
+    pub fn labels(&self) -> HashMap<String, f64> {
+        let mut merged: HashMap<String, f64> = self
+            .sums
+            .iter()
+            .filter(|(_, (_, weight))| *weight > 0.0)
+            .map(|(label, (sum, weight))| (label.to_string(), sum / weight))
+            .collect();
+
+        if let Some(min) = self.min {
+            merged.insert("min".to_string(), min);
+        }
+        if let Some(max) = self.max {
+            merged.insert("max".to_string(), max);
+        }
+        if let Some(count) = self.count {
+            merged.insert("count".to_string(), count);
+        }
+
+        merged
+    }
+}
+
+fn sum_label(sinks: &[&types::Sink], label: &str) -> Option<f64> {
+    let values: Vec<f64> = sinks
+        .iter()
+        .filter_map(|x| x.labels.get(label).copied())
+        .collect();
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum())
+    }
+}
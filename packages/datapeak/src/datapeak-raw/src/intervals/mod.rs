@@ -1,4 +1,10 @@
-mod summary;
-mod intervals;
+pub mod summary;
+#[allow(clippy::module_inception)]
+pub mod intervals;
 pub mod checks;
-mod time_series;
\ No newline at end of file
+pub mod merge;
+pub mod time_series;
+pub mod expressions;
+pub mod window;
+
+pub use intervals::INTERVAL_PERIOD_SECONDS;
\ No newline at end of file
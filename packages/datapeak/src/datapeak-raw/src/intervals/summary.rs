@@ -1,48 +1,114 @@
-use crate::{MANAGERS,manager::TestInfoManager};
-use uuid::Uuid;
-use wasm_bindgen::prelude::*;
-
-#[wasm_bindgen (js_name = rawGetSummary)]
-pub fn get_summary(test_info_id: &str) -> Result<JsValue, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
-
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
-
-    match &manager.summary {
-        Some(summary) => Result::from(summary.clone()),
-        None => Ok(JsValue::null()),
+use std::collections::BTreeMap;
+
+use crate::{intervals::merge::PeriodSinkMerge, manager::TestInfoManager, states::Topic, types};
+
+// Running aggregates of every sink over the intervals summarised so far, so
+// that a new interval only needs merging into them rather than every interval
+// being aggregated again
+#[derive(Debug, Clone, Default)]
+pub struct SummaryAggregates {
+    interval_count: usize,
+    // Period of the last interval added
+    period: Option<i32>,
+    // Ordered so the merged sinks are inserted deterministically
+    sinks: BTreeMap<String, PeriodSinkMerge>,
+}
+
+impl SummaryAggregates {
+    // Intervals must be added in period order
+    pub fn add_interval(&mut self, interval: &types::Interval) -> Result<(), String> {
+        for (sink_name, sink) in interval.sinks.iter() {
+            let result = match self.sinks.get_mut(sink_name) {
+                Some(merge) => merge.add(sink),
+                None => PeriodSinkMerge::new(sink).map(|merge| {
+                    self.sinks.insert(sink_name.to_string(), merge);
+                }),
+            };
+
+            result.map_err(|err| format!("Error summarising sink {}: {}", sink_name, err))?;
+        }
+
+        self.interval_count += 1;
+        self.period = Some(interval.period);
+
+        Ok(())
+    }
+
+    pub fn summary(&self) -> Option<types::Interval> {
+        let mut summary = types::Interval::new();
+        summary.period = self.period?;
+
+        for (sink_name, merge) in self.sinks.iter() {
+            summary.sinks.insert(sink_name.to_string(), merge.sink());
+        }
+
+        Some(summary)
     }
 }
 
-#[wasm_bindgen (js_name = rawGetSummaryState)]
-pub fn get_summary_state(test_info_id: &str) -> Result<String, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+// Aggregates every sink over the given intervals, which must be sorted by
+// period, into a single interval using the merge rule for each sink type
+pub fn summarise_intervals(
+    intervals: &[types::Interval],
+) -> Result<Option<types::Interval>, String> {
+    let mut aggregates = SummaryAggregates::default();
 
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
+    for interval in intervals.iter() {
+        aggregates.add_interval(interval)?;
+    }
 
-    Ok(manager.summary_state.to_owned())
+    Ok(aggregates.summary())
 }
 
 impl TestInfoManager {
-    // Estimates summary intervals for each of the metrics in the given intervals,
-    // by averaging the values of the metrics over the given intervals.
-    pub fn update_summary_interval(self: &mut TestInfoManager) {
-        if self.test_info.intervals.is_empty() {
-            self.summary = None;
-            return;
-        }
+    // Merges the intervals with the changed periods into the summary interval,
+    // which is stored on the test info so that it is saved with it. If any of
+    // them don't follow the intervals already summarised, eg. because they
+    // arrived late, every interval is aggregated again.
+    pub fn update_summary_interval(&mut self, changed_periods: &[i32]) -> Result<(), String> {
+        let aggregates = &self.summary_aggregates;
+
+        let follows_summarised = aggregates.interval_count + changed_periods.len()
+            == self.test_info.intervals.len()
+            && changed_periods
+                .iter()
+                .all(|period| aggregates.period.is_none_or(|x| *period > x));
+
+        let result = if follows_summarised {
+            let mut periods = changed_periods.to_vec();
+            periods.sort_unstable();
+
+            // The changed intervals are the newest, so are found from the end
+            let intervals = &self.test_info.intervals;
+            let aggregates = &mut self.summary_aggregates;
+
+            periods
+                .iter()
+                .filter_map(|period| intervals.iter().rev().find(|x| x.period == *period))
+                .try_for_each(|interval| aggregates.add_interval(interval))
+        } else {
+            let mut aggregates = SummaryAggregates::default();
 
-        self.summary = match self.test_info.intervals.len() {
-            0 => None,
-            len => Some(self.test_info.intervals[len - 1].clone()),
+            let result = self
+                .test_info
+                .intervals
+                .iter()
+                .try_for_each(|interval| aggregates.add_interval(interval));
+
+            self.summary_aggregates = aggregates;
+            result
         };
 
-        self.summary_state = Uuid::new_v4().to_string();
+        if let Err(err) = result {
+            // Aggregate every interval again on the next update
+            self.summary_aggregates = SummaryAggregates::default();
+            return Err(err);
+        }
+
+        let summary = self.summary_aggregates.summary();
+
+        self.replace_if_changed(Topic::Summary, |x| &mut x.test_info.summary, summary.into());
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,66 +1,75 @@
-use std::iter::FromIterator;
+use std::collections::BTreeSet;
 
-use js_sys::{Array, Reflect};
 use serde::{Serialize, Deserialize};
-use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
 
-use crate::{types, MANAGERS};
-
-#[wasm_bindgen (js_name = rawIntervalTimeSeries)]
-pub fn interval_time_series(
-    test_info_id: &str,
-    sink_path: &str,
-    downsizing_method: JsValue,
-) -> Result<JsValue, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
-
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
-
-    let downsizing_method: DownsizingMethod = serde_wasm_bindgen::from_value(downsizing_method)
-        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
-
-    // Split sink_path at ::
-    let sink_path: Vec<&str> = sink_path.split("::").collect();
+use crate::{
+    glob::glob_match, intervals::window::Window, manager::TestInfoManager,
+    sketch::sink_label_value, types,
+};
+
+// Values of one sink label over time, as (period, value) pairs. Series from the
+// manager also give the start of the period of each point in milliseconds since
+// the unix epoch, when the start of the run is known.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TimeSeries {
+    pub name: String,
+    pub data: Vec<(f64, f64)>,
+    pub timestamps: Option<Vec<f64>>,
+}
 
-    if sink_path.len() < 2 {
-        return Err(JsValue::from_str(
-            "sink path must be of the form <sink>::<label>",
-        ));
+impl TestInfoManager {
+    // Gets a time series for a path of the form <sink>::<label>
+    pub fn interval_time_series(
+        &self,
+        sink_path: &str,
+        downsizing_method: DownsizingMethod,
+    ) -> Result<TimeSeries, String> {
+        self.window_time_series(sink_path, downsizing_method, &Window::default())
     }
 
-    let label = sink_path[sink_path.len() - 1];
-    let binding = sink_path[..sink_path.len() - 1].join("::");
-    let sink_path = binding.as_str();
-
-    let time_series = get_time_series(
-        &manager.test_info.intervals,
-        sink_path,
-        label,
-        downsizing_method,
-    )?;
-
-    let series_object = js_sys::Object::new();
-
-    Reflect::set(
-        &series_object,
-        &JsValue::from_str("name"),
-        &JsValue::from_str(&sink_path),
-    )?;
-
-    Reflect::set(
-        &series_object,
-        &JsValue::from_str("data"),
-
-        &Array::from_iter(time_series.iter().map(|(x_, y)| Array::of2(&JsValue::from_f64(*x_), &JsValue::from_f64(*y)))),
-    )?;
+    // Gets a time series for every sink matching a path of the form
+    // <sink glob>::<label>, e.g. *::http_req_duration::p(95), limited to the
+    // intervals inside the window
+    pub fn query_time_series(
+        &self,
+        pattern: &str,
+        downsizing_method: DownsizingMethod,
+        window: &Window,
+    ) -> Result<Vec<TimeSeries>, String> {
+        let intervals = self
+            .window_periods(window)?
+            .intervals(&self.test_info.intervals)?;
+
+        Ok(query_time_series(intervals, pattern, downsizing_method)?
+            .into_iter()
+            .map(|time_series| self.with_timestamps(time_series))
+            .collect())
+    }
 
-    Ok(JsValue::from(series_object))
+    // Adds the timestamp of each point of a series, if the start of the run is
+    // known. The points keep their periods.
+    pub(crate) fn with_timestamps(&self, time_series: TimeSeries) -> TimeSeries {
+        let start_millis = match self.start_time() {
+            Some(start_time) => start_time.timestamp_millis() as f64,
+            None => return time_series,
+        };
+
+        let interval_millis = self.interval_seconds() as f64 * 1000.0;
+
+        TimeSeries {
+            timestamps: Some(
+                time_series
+                    .data
+                    .iter()
+                    .map(|(period, _)| start_millis + period * interval_millis)
+                    .collect(),
+            ),
+            ..time_series
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum DownsizingMethod {
     FixedIntervals {
@@ -70,12 +79,21 @@ pub enum DownsizingMethod {
         window_size: Option<usize>,
         max_data_points: Option<usize>,
     },
+    // Largest-Triangle-Three-Buckets, keeps the points that most affect the
+    // shape of the line so that spikes survive
+    Lttb {
+        max_data_points: Option<usize>,
+    },
+    // Keeps the lowest and highest point of each bucket
+    MinMax {
+        max_data_points: Option<usize>,
+    },
     // Add more downsizing methods here...
     None,
 }
 
 pub fn get_time_series(
-    intervals: &Vec<types::Interval>,
+    intervals: &[types::Interval],
     sink_path: &str,
     label: &str,
     downsizing_method: DownsizingMethod,
@@ -87,20 +105,124 @@ pub fn get_time_series(
         .map(|interval| interval_to_time_series(interval, sink_path, label))
         .collect();
 
+    Ok(downsize(&intervals, downsizing_method))
+}
+
+pub(crate) fn downsize(data: &[(f64, f64)], downsizing_method: DownsizingMethod) -> Vec<(f64, f64)> {
     match downsizing_method {
         DownsizingMethod::FixedIntervals { max_data_points } => {
-            Ok(fixed_intervals(&intervals, max_data_points))
+            fixed_intervals(data, max_data_points)
         }
         DownsizingMethod::MovingMean {
             window_size,
             max_data_points,
-        } => Ok(moving_mean_points(&intervals, window_size, max_data_points)),
-        DownsizingMethod::None => Ok(intervals),
+        } => moving_mean_points(data, window_size, max_data_points),
+        DownsizingMethod::Lttb { max_data_points } => lttb(data, max_data_points),
+        DownsizingMethod::MinMax { max_data_points } => min_max(data, max_data_points),
+        DownsizingMethod::None => data.to_vec(),
     }
 }
 
+// Gets one time series per sink matching the glob in a path of the form
+// <sink glob>::<label>, sorted by sink name. Every series has a point for each
+// interval, with NaN where the sink or label is missing, and all are
+// downsampled at the same periods so they stay aligned.
+pub fn query_time_series(
+    intervals: &[types::Interval],
+    pattern: &str,
+    downsizing_method: DownsizingMethod,
+) -> Result<Vec<TimeSeries>, String> {
+    let (sink_pattern, label) = match pattern.rsplit_once("::") {
+        Some((sink_pattern, label)) if !sink_pattern.is_empty() && !label.is_empty() => {
+            (sink_pattern, label)
+        }
+        _ => return Err("pattern must be of the form <sink glob>::<label>".to_string()),
+    };
+
+    let sink_names: BTreeSet<&String> = intervals
+        .iter()
+        .flat_map(|interval| interval.sinks.iter())
+        .filter(|(sink_name, sink)| {
+            glob_match(sink_pattern, sink_name) && sink_label_value(sink, label).is_some()
+        })
+        .map(|(sink_name, _)| sink_name)
+        .collect();
+
+    if sink_names.is_empty() {
+        return Err(format!(
+            "No sinks with label {} match {}",
+            label, sink_pattern
+        ));
+    }
+
+    let series: Vec<Vec<(f64, f64)>> = sink_names
+        .iter()
+        .map(|sink_name| {
+            intervals
+                .iter()
+                .map(|interval| {
+                    let value = interval
+                        .sinks
+                        .get(*sink_name)
+                        .and_then(|sink| sink_label_value(sink, label))
+                        .unwrap_or(f64::NAN);
+
+                    (interval.period as f64, value)
+                })
+                .collect()
+        })
+        .collect();
+
+    let data: Vec<Vec<(f64, f64)>> = match downsizing_method {
+        // These pick points by shape, so each series gets a share of the budget
+        // and every series keeps the points picked for any of them. The share
+        // is at least 3 points, unless the budget itself is smaller.
+        DownsizingMethod::Lttb { max_data_points }
+        | DownsizingMethod::MinMax { max_data_points } => {
+            let budget = max_data_points.unwrap_or(1000);
+            let max = (budget / series.len()).max(3).min(budget);
+
+            let indices: BTreeSet<usize> = series
+                .iter()
+                .flat_map(|data| match downsizing_method {
+                    DownsizingMethod::Lttb { .. } => lttb_indices(&gapless(data), max),
+                    _ => min_max_indices(data, max),
+                })
+                .collect();
+
+            series
+                .iter()
+                .map(|data| indices.iter().map(|index| data[*index]).collect())
+                .collect()
+        }
+        // The rest pick points by position alone, so they line up already
+        _ => series
+            .iter()
+            .map(|data| downsize(data, downsizing_method))
+            .collect(),
+    };
+
+    Ok(sink_names
+        .into_iter()
+        .zip(data)
+        .map(|(sink_name, data)| TimeSeries {
+            name: sink_name.to_string(),
+            data,
+            timestamps: None,
+        })
+        .collect())
+}
+
+// Gaps would make every triangle they touch NaN, so they are treated as zero
+// when choosing points
+fn gapless(data: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    data.iter()
+        .map(|(period, value)| (*period, if value.is_nan() { 0.0 } else { *value }))
+        .collect()
+}
+
 pub fn inspect_path_for_labels(
-    intervals: &Vec<types::Interval>,
+    intervals: &[types::Interval],
     sink_path: &str,
     label: &str,
 ) -> Result<(), String> {
@@ -128,7 +250,7 @@ pub fn inspect_path_for_labels(
 
     for interval in intervals.iter().take(10) {
         if let Some(sink) = interval.sinks.get(sink_path) {
-            if sink.labels.contains_key(label) {
+            if sink_label_value(sink, label).is_some() {
                 found_label = true;
                 break;
             }
@@ -151,12 +273,12 @@ fn interval_to_time_series(interval: &types::Interval, sink_path: &str, label: &
         None => return (0.0, 0.0),
     };
 
-    let value = match sink.labels.get(label) {
+    let value = match sink_label_value(sink, label) {
         Some(value) => value,
         None => return (0.0, 0.0),
     };
 
-    (interval.period as f64, *value)
+    (interval.period as f64, value)
 }
 
 fn fixed_intervals(data: &[(f64, f64)], max_data_points: Option<usize>) -> Vec<(f64, f64)> {
@@ -167,6 +289,11 @@ fn fixed_intervals(data: &[(f64, f64)], max_data_points: Option<usize>) -> Vec<(
         return data.to_vec();
     }
 
+    // A single point has no step between points, so keep the first like lttb
+    if max <= 1 {
+        return data.iter().take(max).copied().collect();
+    }
+
     let step = (total - 1) as f64 / (max - 1) as f64;
     let mut result = Vec::with_capacity(max);
 
@@ -193,14 +320,20 @@ fn moving_mean_points(
 
     let max_data_points = max_data_points.unwrap_or(1000);
 
+    if max_data_points == 0 {
+        return result;
+    }
+
     // Dynamically adjust mean window size based on the number of data points
-    let window_size = window_size.unwrap_or(if total < max_data_points {
-        1
-    } else {
-        total / max_data_points
-    });
+    let window_size = window_size
+        .unwrap_or(if total < max_data_points {
+            1
+        } else {
+            total / max_data_points
+        })
+        .max(1);
 
-    if total < window_size || max_data_points == 0 {
+    if total < window_size {
         return result;
     }
 
@@ -211,7 +344,8 @@ fn moving_mean_points(
     while window_end <= total {
         let window_mean = window_sum / (window_size as f64);
         result.push((data[window_start + window_size / 2].0, window_mean));
-        if result.len() >= max_data_points {
+        // The last window ends at the last point
+        if result.len() >= max_data_points || window_end == total {
             break;
         }
         window_sum -= data[window_start].1;
@@ -222,3 +356,121 @@ fn moving_mean_points(
 
     result
 }
+
+fn lttb(data: &[(f64, f64)], max_data_points: Option<usize>) -> Vec<(f64, f64)> {
+    lttb_indices(data, max_data_points.unwrap_or(1000))
+        .into_iter()
+        .map(|index| data[index])
+        .collect()
+}
+
+fn min_max(data: &[(f64, f64)], max_data_points: Option<usize>) -> Vec<(f64, f64)> {
+    min_max_indices(data, max_data_points.unwrap_or(1000))
+        .into_iter()
+        .map(|index| data[index])
+        .collect()
+}
+
+// Indices of the points kept by Largest-Triangle-Three-Buckets, in order
+fn lttb_indices(data: &[(f64, f64)], max: usize) -> Vec<usize> {
+    let total = data.len();
+
+    if total <= max {
+        return (0..total).collect();
+    }
+
+    // The first and last points are always kept, so fewer than 3 points leaves
+    // no buckets to choose from
+    if max < 3 {
+        return vec![0, total - 1].into_iter().take(max).collect();
+    }
+
+    let bucket_size = (total - 2) as f64 / (max - 2) as f64;
+    let mut result = Vec::with_capacity(max);
+
+    result.push(0);
+    let mut selected = 0;
+
+    for bucket in 0..max - 2 {
+        let start = (bucket as f64 * bucket_size) as usize + 1;
+        let end = (((bucket + 1) as f64 * bucket_size) as usize + 1).min(total - 1);
+
+        // The third point of the triangle is the mean of the next bucket
+        let next_end = (((bucket + 2) as f64 * bucket_size) as usize + 1).min(total);
+        let next = &data[end..next_end.max(end + 1)];
+        let next_x = next.iter().map(|p| p.0).sum::<f64>() / next.len() as f64;
+        let next_y = next.iter().map(|p| p.1).sum::<f64>() / next.len() as f64;
+
+        let (x, y) = data[selected];
+        let mut largest_area = -1.0;
+        let mut bucket_selected = start;
+
+        for (index, point) in data.iter().enumerate().take(end).skip(start) {
+            let area = ((x - next_x) * (point.1 - y) - (x - point.0) * (next_y - y)).abs();
+
+            if area > largest_area {
+                largest_area = area;
+                bucket_selected = index;
+            }
+        }
+
+        selected = bucket_selected;
+        result.push(selected);
+    }
+
+    result.push(total - 1);
+
+    result
+}
+
+// Indices of the lowest and highest point in each bucket, in order
+fn min_max_indices(data: &[(f64, f64)], max: usize) -> Vec<usize> {
+    let total = data.len();
+
+    if total <= max {
+        return (0..total).collect();
+    }
+
+    // Gaps have no value to compare
+    let extreme = |start: usize, end: usize, highest: bool| {
+        (start..end)
+            .filter(|index| !data[*index].1.is_nan())
+            .reduce(|a, b| {
+                let b_is_better = if highest {
+                    data[b].1 > data[a].1
+                } else {
+                    data[b].1 < data[a].1
+                };
+
+                if b_is_better {
+                    b
+                } else {
+                    a
+                }
+            })
+    };
+
+    if max < 2 {
+        return extreme(0, total, true).into_iter().take(max).collect();
+    }
+
+    // Each bucket gives two points
+    let buckets = max / 2;
+    let bucket_size = total as f64 / buckets as f64;
+    let mut result = Vec::with_capacity(max);
+
+    for bucket in 0..buckets {
+        let start = (bucket as f64 * bucket_size) as usize;
+        let end = (((bucket + 1) as f64 * bucket_size) as usize).min(total);
+
+        if let (Some(min), Some(max)) = (extreme(start, end, false), extreme(start, end, true)) {
+            // Kept in time order
+            result.push(min.min(max));
+            if min != max {
+                result.push(min.max(max));
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,219 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    intervals::{
+        checks::{collect_checks, CheckCollection},
+        summary::summarise_intervals,
+        time_series::{get_time_series, DownsizingMethod, TimeSeries},
+    },
+    manager::TestInfoManager,
+    types,
+};
+
+// A range of periods, both inclusive, with open ends reaching the start or end
+// of the run
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Window {
+    pub start_period: Option<i32>,
+    pub end_period: Option<i32>,
+    // Limits the window to the periods wholly between these times, which needs
+    // the start of the run to be known
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+impl Window {
+    pub fn new(start_period: Option<i32>, end_period: Option<i32>) -> Window {
+        Window {
+            start_period,
+            end_period,
+            ..Window::default()
+        }
+    }
+
+    // Converts the times of the window to periods, keeping whichever bound is
+    // narrower where both are given
+    pub fn to_periods(
+        &self,
+        run_start_time: Option<DateTime<Utc>>,
+        interval_seconds: u32,
+    ) -> Result<Window, String> {
+        if self.start_time.is_none() && self.end_time.is_none() {
+            return Ok(*self);
+        }
+
+        let run_start_time = run_start_time.ok_or_else(|| {
+            "The start time of the run is needed for a window of times".to_string()
+        })?;
+
+        // Periods since the start of the run
+        let offset = |time: DateTime<Utc>| {
+            (time - run_start_time).num_milliseconds() as f64 / (interval_seconds as f64 * 1000.0)
+        };
+
+        let start_period = match self.start_time {
+            Some(start_time) => Some(max_bound(
+                self.start_period,
+                offset(start_time).ceil() as i32,
+            )),
+            None => self.start_period,
+        };
+        let end_period = match self.end_time {
+            Some(end_time) => Some(min_bound(
+                self.end_period,
+                offset(end_time).floor() as i32 - 1,
+            )),
+            None => self.end_period,
+        };
+
+        if let (Some(start), Some(end)) = (start_period, end_period) {
+            if start > end {
+                return Err("Window doesn't contain a whole period".to_string());
+            }
+        }
+
+        Ok(Window::new(start_period, end_period))
+    }
+
+    // The intervals inside the window, which must be sorted by period. Times
+    // must have been converted to periods first.
+    pub fn intervals<'a>(
+        &self,
+        intervals: &'a [types::Interval],
+    ) -> Result<&'a [types::Interval], String> {
+        if self.start_time.is_some() || self.end_time.is_some() {
+            return Err("Window times must be converted to periods".to_string());
+        }
+
+        if let (Some(start), Some(end)) = (self.start_period, self.end_period) {
+            if start > end {
+                return Err(format!(
+                    "Window start period {} is after end period {}",
+                    start, end
+                ));
+            }
+        }
+
+        let start = match self.start_period {
+            Some(start) => intervals.partition_point(|x| x.period < start),
+            None => 0,
+        };
+        let end = match self.end_period {
+            Some(end) => intervals.partition_point(|x| x.period <= end),
+            None => intervals.len(),
+        };
+
+        Ok(&intervals[start..end.max(start)])
+    }
+}
+
+impl TestInfoManager {
+    // Gets a time series for a path of the form <sink>::<label>, limited to the
+    // intervals inside the window
+    pub fn window_time_series(
+        &self,
+        sink_path: &str,
+        downsizing_method: DownsizingMethod,
+        window: &Window,
+    ) -> Result<TimeSeries, String> {
+        let (sink_path, label) = match sink_path.rsplit_once("::") {
+            Some(split) => split,
+            None => return Err("sink path must be of the form <sink>::<label>".to_string()),
+        };
+
+        let data = get_time_series(
+            self.window_periods(window)?
+                .intervals(&self.test_info.intervals)?,
+            sink_path,
+            label,
+            downsizing_method,
+        )?;
+
+        Ok(self.with_timestamps(TimeSeries {
+            name: sink_path.to_string(),
+            data,
+            timestamps: None,
+        }))
+    }
+
+    pub fn window_summary(&self, window: &Window) -> Result<Option<types::Interval>, String> {
+        summarise_window(
+            &self.test_info.intervals,
+            &self.window_periods(window)?,
+            self.interval_seconds(),
+        )
+    }
+
+    // Check rates over the window, rather than those of the newest interval
+    pub fn window_checks(&self, window: &Window) -> Result<CheckCollection, String> {
+        let mut checks = CheckCollection::new();
+
+        if let Some(summary) = self.window_summary(window)? {
+            collect_checks(&mut checks, &summary, &self.test_info.thresholds)?;
+        }
+
+        Ok(checks)
+    }
+
+    pub(crate) fn window_periods(&self, window: &Window) -> Result<Window, String> {
+        window.to_periods(self.start_time(), self.interval_seconds())
+    }
+}
+
+fn max_bound(bound: Option<i32>, other: i32) -> i32 {
+    bound.map_or(other, |bound| bound.max(other))
+}
+
+fn min_bound(bound: Option<i32>, other: i32) -> i32 {
+    bound.map_or(other, |bound| bound.min(other))
+}
+
+// Aggregates the sinks of the intervals inside the window, which must be
+// sorted by period, into a single interval. Counter counts are running totals,
+// so they are reduced to the increase over the window and their rates to the
+// mean rate over it, excluding anything counted before the window started.
+pub fn summarise_window(
+    intervals: &[types::Interval],
+    window: &Window,
+    interval_seconds: u32,
+) -> Result<Option<types::Interval>, String> {
+    let window_intervals = window.intervals(intervals)?;
+
+    let mut summary = match summarise_intervals(window_intervals)? {
+        Some(summary) => summary,
+        None => return Ok(None),
+    };
+
+    let first_period = window_intervals[0].period;
+    let previous_interval = intervals.iter().rev().find(|x| x.period < first_period);
+
+    // Seconds between the end of the previous interval, or the start of the run,
+    // and the end of the window
+    let seconds = interval_seconds as f64
+        * match previous_interval {
+            Some(previous_interval) => summary.period - previous_interval.period,
+            None => summary.period + 1,
+        } as f64;
+
+    for (sink_name, sink) in summary.sinks.iter_mut() {
+        if !matches!(sink.type_.enum_value(), Ok(types::SinkType::Counter)) {
+            continue;
+        }
+
+        let previous_count = previous_interval
+            .and_then(|x| x.sinks.get(sink_name))
+            .and_then(|x| x.labels.get("count"))
+            .copied()
+            .unwrap_or(0.0);
+
+        if let Some(count) = sink.labels.get_mut("count") {
+            *count -= previous_count;
+
+            let rate = *count / seconds;
+            sink.labels.insert("rate".to_string(), rate);
+            sink.labels.insert("mean".to_string(), rate);
+        }
+    }
+
+    Ok(Some(summary))
+}
@@ -0,0 +1,30 @@
+pub mod intervals;
+pub mod manager;
+pub mod types;
+pub mod states;
+pub mod accessors;
+pub mod console_messages;
+pub mod locations;
+pub mod thresholds;
+pub mod anomalies;
+pub mod sketch;
+pub mod glob;
+pub mod exporters;
+pub mod importers;
+pub mod comparison;
+pub mod run_info;
+
+// The wasm-bindgen functions used by the JS wrappers
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub fn set_panic_hook() {
+    // When the `console_error_panic_hook` feature is enabled, we can call the
+    // `set_panic_hook` function at least once during initialization, and then
+    // we will get better error messages if our code ever panics.
+    //
+    // For more details see
+    // https://github.com/rustwasm/console_error_panic_hook#readme
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+}
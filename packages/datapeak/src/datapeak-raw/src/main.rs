@@ -1,129 +1,354 @@
-mod intervals;
-mod manager;
-mod types;
-mod states;
-mod accessors;
-mod console_messages;
-mod locations;
-
-use lazy_static::lazy_static;
-use protobuf::Message;
-use uuid::Uuid;
-use std::collections::HashMap;
-use std::sync::Mutex;
-use wasm_bindgen::prelude::*;
-
-pub fn set_panic_hook() {
-    // When the `console_error_panic_hook` feature is enabled, we can call the
-    // `set_panic_hook` function at least once during initialization, and then
-    // we will get better error messages if our code ever panics.
-    //
-    // For more details see
-    // https://github.com/rustwasm/console_error_panic_hook#readme
-    #[cfg(feature = "console_error_panic_hook")]
-    console_error_panic_hook::set_once();
+// Command line tool for analysing saved test results, so that stored runs can
+// be checked without a browser. Exits with 1 when any threshold fails over the
+// whole run.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::ExitCode;
+
+use datapeak::{
+    importers::{jmeter::JMeterImportOptions, k6::K6ImportOptions},
+    intervals::merge::GLOBAL_LOCATION,
+    manager::TestInfoManager,
+    thresholds::ThresholdResult,
+    types,
+};
+
+const USAGE: &str = "Usage: datapeak [--streamed | --k6 | --jmeter] [--top <count>] <file>
+
+Loads a saved TestInfo protobuf and prints its summary, threshold results,
+checks and most frequent console messages.
+
+Options:
+  --streamed       The file contains length-delimited StreamedData frames
+  --k6             The file is k6 JSON output, as written by --out json
+  --jmeter         The file is a JMeter .jtl results file saved as CSV
+  --top <count>    Number of console messages to print (default 10)
+  -h, --help       Print this message";
+
+const DEFAULT_TOP_CONSOLE_MESSAGES: usize = 10;
+
+// Labels shown in the summary table for each sink type
+const COUNTER_LABELS: [&str; 2] = ["count", "rate"];
+const GAUGE_LABELS: [&str; 3] = ["value", "min", "max"];
+const RATE_LABELS: [&str; 4] = ["pass", "fail", "total", "rate"];
+const TREND_LABELS: [&str; 6] = ["avg", "min", "med", "max", "p(90)", "p(95)"];
+
+#[derive(PartialEq)]
+enum Format {
+    TestInfo,
+    Streamed,
+    K6Json,
+    JMeterCsv,
 }
 
-pub fn main() {
-    println!("Hello, world!")
+struct Args {
+    path: String,
+    format: Format,
+    top: usize,
 }
 
-lazy_static! {
-    static ref MANAGERS: Mutex<HashMap<String, manager::TestInfoManager>> =
-        Mutex::new(HashMap::new());
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let manager = match load_manager(&args) {
+        Ok(manager) => manager,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::from(2);
+        }
+    };
+
+    // Thresholds are checked against the whole run, as k6 does at the end of a
+    // test, rather than against the last interval received
+    let threshold_results = manager.summary_threshold_results();
+
+    print_summary(&manager);
+    print_thresholds(&threshold_results);
+    print_checks(&manager);
+    print_console_messages(manager.console_messages(), args.top);
+
+    if threshold_results.iter().any(|x| !x.passed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
 }
-#[wasm_bindgen (js_name = rawInitTestData)]
-pub fn init_test_data(test_info: Option<Vec<u8>>) -> Result<JsValue, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
-
-    let test_info_id = Uuid::new_v4().to_string();
-
-    // If test data is provided, parse it
-    match test_info {
-        Some(bytes) => {
-            // Create input stream from bytes
-            let mut input_stream = protobuf::CodedInputStream::from_bytes(&bytes);
-
-            match types::TestInfo::parse_from(&mut input_stream) {
-                Ok(test_info) => {
-                    managers.insert(
-                        test_info_id.to_owned(),
-                        manager::TestInfoManager::new(Some(&test_info)),
-                    );
-                }
-                Err(e) => {
-                    // If there is an error parsing the test info, return an error
-                    return Err(JsValue::from_str(
-                        format!("Error parsing test info from protobuf: {}", e).as_str(),
-                    ));
-                }
+
+// Returns None if help was requested
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut path: Option<String> = None;
+    let mut format = Format::TestInfo;
+    let mut top = DEFAULT_TOP_CONSOLE_MESSAGES;
+
+    let mut args = args;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--streamed" | "--k6" | "--jmeter" if format != Format::TestInfo => {
+                return Err("Only one of --streamed, --k6 and --jmeter can be used".to_string())
             }
-        }
-        None => {
-            // If no test info is provided, create a new test info
-            managers.insert(test_info_id.to_owned(), manager::TestInfoManager::new(None));
+            "--streamed" => format = Format::Streamed,
+            "--k6" => format = Format::K6Json,
+            "--jmeter" => format = Format::JMeterCsv,
+            "--top" => {
+                let value = args.next().ok_or("--top requires a count")?;
+                top = value
+                    .parse()
+                    .map_err(|_| format!("Invalid count for --top: {}", value))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            _ if path.is_some() => return Err(format!("Unexpected argument: {}", arg)),
+            _ => path = Some(arg),
         }
     }
 
-    Ok(JsValue::from_str(test_info_id.as_str()))
+    match path {
+        Some(path) => Ok(Some(Args { path, format, top })),
+        None => Err("No file given".to_string()),
+    }
 }
 
-#[wasm_bindgen (js_name = rawDeleteTestData)]
-pub fn delete_test_data(test_info_id: &str) -> Result<(), JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+fn load_manager(args: &Args) -> Result<TestInfoManager, String> {
+    let bytes = fs::read(&args.path).map_err(|e| format!("Error reading {}: {}", args.path, e))?;
 
-    // Get test data if it exists, otherwise return
-    match managers.remove(test_info_id) {
-        Some(_) => Ok(()),
-        None => Err(JsValue::from_str(
-            format!("No test data found for test_info_id: {}", test_info_id).as_str(),
-        )),
+    match args.format {
+        Format::TestInfo => return TestInfoManager::from_bytes(&bytes),
+        Format::K6Json => {
+            return TestInfoManager::from_k6_json(bytes.as_slice(), &K6ImportOptions::default())
+        }
+        Format::JMeterCsv => {
+            return TestInfoManager::from_jmeter_csv(
+                bytes.as_slice(),
+                &JMeterImportOptions::default(),
+            )
+        }
+        Format::Streamed => {}
     }
+
+    let mut manager = TestInfoManager::new(None);
+    let mut input_stream = protobuf::CodedInputStream::from_bytes(&bytes);
+
+    // Frames are replayed in order, exactly as they would have been streamed
+    while !input_stream.eof().map_err(|e| e.to_string())? {
+        let frame = input_stream
+            .read_bytes()
+            .map_err(|e| format!("Error reading streamed data frame: {}", e))?;
+
+        manager.add_streamed_data(frame)?;
+    }
+
+    Ok(manager)
 }
 
-#[wasm_bindgen (js_name = rawAddStreamedData)]
-pub fn add_streamed_data(test_info_id: &str, bytes: Vec<u8>) -> Result<(), JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+fn print_summary(manager: &TestInfoManager) {
+    println!("Summary");
 
-    // Get test data if it exists, otherwise return
-    let test_info = match managers.get_mut(test_info_id) {
-        Some(test_info) => test_info,
+    let summary = match manager.summary() {
+        Some(summary) => summary,
         None => {
-            return Err(JsValue::from_str(
-                format!("No test data found for test_info_id: {}", test_info_id).as_str(),
-            ))
+            println!("  No intervals\n");
+            return;
         }
     };
 
-    match test_info.add_streamed_data(bytes) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(JsValue::from_str(e.as_str())),
+    let mut rows: Vec<Vec<String>> = vec![vec![
+        "Sink".to_string(),
+        "Type".to_string(),
+        "Values".to_string(),
+    ]];
+
+    let mut sinks: Vec<(&String, &types::Sink)> = summary.sinks.iter().collect();
+    sinks.sort_by_key(|(sink_name, _)| (!is_global(sink_name), *sink_name));
+
+    for (sink_name, sink) in sinks {
+        let (sink_type, labels): (&str, &[&str]) = match sink.type_.enum_value() {
+            Ok(types::SinkType::Counter) => ("counter", &COUNTER_LABELS),
+            Ok(types::SinkType::Gauge) => ("gauge", &GAUGE_LABELS),
+            Ok(types::SinkType::Rate) => ("rate", &RATE_LABELS),
+            Ok(types::SinkType::Trend) => ("trend", &TREND_LABELS),
+            Err(_) => ("unknown", &[]),
+        };
+
+        let values: Vec<String> = labels
+            .iter()
+            .filter_map(|label| {
+                sink.labels
+                    .get(*label)
+                    .map(|value| format!("{}={}", label, format_value(*value)))
+            })
+            .collect();
+
+        rows.push(vec![
+            sink_name.to_string(),
+            sink_type.to_string(),
+            values.join(" "),
+        ]);
     }
+
+    print_table(&rows);
+    println!();
 }
 
-#[wasm_bindgen (js_name = rawAddMessage)]
-pub fn add_message(test_info_id: &str, message: &str) -> Result<(), JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+fn print_thresholds(results: &[ThresholdResult]) {
+    println!("Thresholds");
 
-    // Get test data if it exists, otherwise return
-    let test_info = match managers.get_mut(test_info_id) {
-        Some(test_info) => test_info,
-        None => {
-            return Err(JsValue::from_str(
-                format!("No test data found for test_info_id: {}", test_info_id).as_str(),
-            ))
+    if results.is_empty() {
+        println!("  No thresholds\n");
+        return;
+    }
+
+    let mut rows: Vec<Vec<String>> = vec![vec![
+        "Result".to_string(),
+        "Metric".to_string(),
+        "Source".to_string(),
+        "Location".to_string(),
+        "Value".to_string(),
+    ]];
+
+    for result in results.iter() {
+        rows.push(vec![
+            if result.passed { "pass" } else { "FAIL" }.to_string(),
+            result.metric.to_string(),
+            result.source.to_string(),
+            result.location.to_string(),
+            match (&result.value, &result.error) {
+                (_, Some(error)) => error.to_string(),
+                (Some(value), None) => format_value(*value),
+                (None, None) => "-".to_string(),
+            },
+        ]);
+    }
+
+    print_table(&rows);
+    println!();
+}
+
+fn print_checks(manager: &TestInfoManager) {
+    println!("Checks");
+
+    let checks = manager.checks();
+
+    let mut rows: Vec<Vec<String>> = vec![vec![
+        "Check".to_string(),
+        "Location".to_string(),
+        "Pass".to_string(),
+        "Fail".to_string(),
+        "Rate".to_string(),
+    ]];
+
+    for check in checks
+        .scenarios
+        .iter()
+        .chain(checks.groups.iter())
+        .chain(checks.named.iter())
+    {
+        let rates: BTreeMap<&String, _> = check.rates.iter().collect();
+
+        for (location, rate) in rates {
+            rows.push(vec![
+                check.name.to_string(),
+                location.to_string(),
+                rate.pass.to_string(),
+                rate.fail.to_string(),
+                format!("{:.2}%", rate.rate * 100.0),
+            ]);
         }
-    };
+    }
+
+    if rows.len() == 1 {
+        println!("  No checks\n");
+        return;
+    }
+
+    print_table(&rows);
+    println!();
+}
+
+fn print_console_messages(console_messages: &[types::ConsoleMessage], top: usize) {
+    println!("Console messages");
+
+    if console_messages.is_empty() {
+        println!("  No console messages");
+        return;
+    }
+
+    let mut counted: Vec<(i64, &types::ConsoleMessage)> = console_messages
+        .iter()
+        .map(|x| (x.count.values().map(|count| *count as i64).sum(), x))
+        .collect();
+
+    // Most frequent first, keeping the order received for ties
+    counted.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+
+    let mut rows: Vec<Vec<String>> = vec![vec![
+        "Count".to_string(),
+        "Level".to_string(),
+        "Message".to_string(),
+    ]];
+
+    for (count, console_message) in counted.iter().take(top) {
+        rows.push(vec![
+            count.to_string(),
+            console_message.level.to_string(),
+            console_message.message.to_string(),
+        ]);
+    }
 
-    test_info.test_info.messages.push(message.to_string());
-    test_info.messages_state = Uuid::new_v4().to_string();
+    print_table(&rows);
 
-    Ok(())
+    if counted.len() > top {
+        println!("  ... and {} more", counted.len() - top);
+    }
 }
 
-#[wasm_bindgen (js_name = rawTestInfoIdExists)]
-pub fn test_info_id_exists(test_info_id: &str) -> bool {
-    let managers = MANAGERS.lock().unwrap();
+// Prints rows with their columns padded to the same width, the first row
+// being the header
+fn print_table(rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = Vec::new();
+
+    for row in rows.iter() {
+        for (index, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+
+            match widths.get_mut(index) {
+                Some(max) => *max = (*max).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    for row in rows.iter() {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| format!("{:width$}", cell, width = widths[index]))
+            .collect();
+
+        println!("  {}", cells.join("  ").trim_end());
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.3}", value)
+    }
+}
 
-    managers.contains_key(test_info_id)
+// Global sinks are listed first as they cover every location
+fn is_global(sink_name: &str) -> bool {
+    sink_name.split("::").next() == Some(GLOBAL_LOCATION)
 }
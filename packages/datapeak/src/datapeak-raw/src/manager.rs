@@ -1,26 +1,42 @@
+use std::collections::HashMap;
+
 use protobuf::Message;
-use uuid::Uuid;
-use crate::{types, intervals::checks::CheckCollection};
+use crate::{
+    anomalies::{Anomaly, AnomalyOptions},
+    types,
+    intervals::{checks::CheckCollection, merge::add_global_sinks, summary::SummaryAggregates},
+    sketch::remove_invalid_sketch,
+    states::Topic,
+    thresholds::{AbortDecision, ThresholdResult},
+};
 
 pub struct TestInfoManager {
     pub test_info: types::TestInfo,
 
     pub latest_period: i32,
 
-    pub intervals_state: String,
-    pub console_messages_state: String,
-    pub thresholds_state: String,
+    // Incremented on every change, each topic and item records the version
+    // it was last changed at
+    pub version: u64,
+    pub topic_versions: HashMap<Topic, u64>,
 
-    pub locations: Vec<String>,
-    pub locations_state: String,
+    // map<period, version>
+    pub interval_versions: HashMap<i32, u64>,
+    pub console_message_versions: Vec<u64>,
+    pub threshold_versions: Vec<u64>,
+    pub message_versions: Vec<u64>,
+
+    pub summary_aggregates: SummaryAggregates,
 
-    pub summary: Option<types::Interval>,
-    pub summary_state: String,
+    pub threshold_results: Vec<ThresholdResult>,
+    pub abort_decision: Option<AbortDecision>,
+
+    pub locations: Vec<String>,
 
     pub checks: CheckCollection,
-    pub checks_state: String,
 
-    pub messages_state: String,
+    pub anomalies: Vec<Anomaly>,
+    pub anomaly_options: AnomalyOptions,
 }
 
 impl TestInfoManager {
@@ -34,29 +50,98 @@ impl TestInfoManager {
             // Set most recent interval to -1 so that the first interval (0) will be processed
             latest_period: -1,
 
-            locations: Vec::new(),
-            locations_state: Uuid::new_v4().to_string(),
+            version: 0,
+            topic_versions: HashMap::new(),
+
+            interval_versions: HashMap::new(),
+            console_message_versions: Vec::new(),
+            threshold_versions: Vec::new(),
+            message_versions: Vec::new(),
 
-            intervals_state: Uuid::new_v4().to_string(),
-            console_messages_state: Uuid::new_v4().to_string(),
-            thresholds_state: Uuid::new_v4().to_string(),
-            
-            summary: None,
-            summary_state: Uuid::new_v4().to_string(),
+            summary_aggregates: SummaryAggregates::default(),
+
+            threshold_results: Vec::new(),
+            abort_decision: None,
+
+            // Exported test info includes the locations, which may have been set
+            locations: match test_info {
+                Some(test_info) => test_info.locations.clone(),
+                None => Vec::new(),
+            },
 
             checks: CheckCollection::new(),
-            checks_state: Uuid::new_v4().to_string(),
 
-            messages_state: Uuid::new_v4().to_string(),
+            anomalies: Vec::new(),
+            anomaly_options: AnomalyOptions::default(),
         };
 
+        // Loaded data is all at the first version so it is included in changes
+        // since version 0
+        let version = new_manager.bump_version(Topic::Intervals);
+        new_manager.interval_versions = new_manager
+            .test_info
+            .intervals
+            .iter()
+            .map(|x| (x.period, version))
+            .collect();
+        new_manager.console_message_versions = vec![version; new_manager.test_info.console_messages.len()];
+        new_manager.threshold_versions = vec![version; new_manager.test_info.thresholds.len()];
+        new_manager.message_versions = vec![version; new_manager.test_info.messages.len()];
+
+        // Intervals saved before global sinks were added won't have them
+        for interval in new_manager.test_info.intervals.iter_mut() {
+            interval.sinks.values_mut().for_each(remove_invalid_sketch);
+            let _ = add_global_sinks(interval);
+        }
+
         for interval in new_manager.test_info.intervals.clone().iter() {
-            new_manager.update_locations(&interval);
+            new_manager.update_locations(interval);
         }
 
+        if let Some(interval) = new_manager.test_info.intervals.last() {
+            new_manager.latest_period = interval.period;
+        }
+
+        let periods: Vec<i32> = new_manager.test_info.intervals.iter().map(|x| x.period).collect();
+
+        // Keep the stored summary if the intervals can't be summarised
+        let _ = new_manager.update_summary_interval(&periods);
+
+        new_manager.update_anomalies(&periods);
+
+        if let Some(interval) = new_manager.test_info.intervals.last().cloned() {
+            let _ = new_manager.update_checks(&interval);
+        }
+
+        new_manager.evaluate_thresholds_over_run();
+
         new_manager
     }
 
+    // Creates a manager from a TestInfo encoded as protobuf bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<TestInfoManager, String> {
+        let mut input_stream = protobuf::CodedInputStream::from_bytes(bytes);
+
+        match types::TestInfo::parse_from(&mut input_stream) {
+            Ok(test_info) => Ok(TestInfoManager::new(Some(&test_info))),
+            Err(e) => Err(format!("Error parsing test info from protobuf: {}", e)),
+        }
+    }
+
+    // Gets the test info with the computed summary and locations, so that it
+    // can be loaded again without replaying the streamed data
+    pub fn export_test_info(&self) -> types::TestInfo {
+        let mut test_info = self.test_info.clone();
+        test_info.locations = self.locations.clone();
+        test_info
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        self.export_test_info()
+            .write_to_bytes()
+            .map_err(|e| format!("Error serializing test info to protobuf: {}", e))
+    }
+
     pub fn add_streamed_data(&mut self, bytes: Vec<u8>) -> Result<(), String> {
         // Create input stream from bytes
         let mut input_stream = protobuf::CodedInputStream::from_bytes(&bytes);
@@ -82,19 +167,26 @@ impl TestInfoManager {
                     intervals.push(interval.clone());
                 }
                 Some(types::data_point::Data::ConsoleMessage(console_message)) => {
-                    self.process_console_message(&console_message);
+                    self.process_console_message(console_message);
                 }
                 Some(types::data_point::Data::Threshold(threashold)) => {
-                    self.process_threshold(&threashold);
+                    self.process_threshold(threashold)?;
+                }
+                // Applied before the intervals below, whose counter rates
+                // depend on it
+                Some(types::data_point::Data::RunInfo(run_info)) => {
+                    self.process_run_info(run_info)?;
                 }
                 None => {
-                    return Err(format!("No data found in data point"));
+                    return Err("No data found in data point".to_string());
                 }
             }
         }
 
-        // Sort intervals by period and ensure no duplicates
-        intervals.sort_by(|a, b| a.period.cmp(&b.period));
+        // Sort intervals by period and ensure no duplicates, keeping the most
+        // recently sent interval for each period
+        intervals.reverse();
+        intervals.sort_by_key(|a| a.period);
         intervals.dedup_by(|a, b| a.period == b.period);
 
         // Add intervals to test info
@@ -105,17 +197,46 @@ impl TestInfoManager {
         Ok(())
     }
 
- 
-    fn process_threshold(&mut self, threshold: &types::Threshold) {
+    pub fn add_message(&mut self, message: &str) {
+        self.test_info.messages.push(message.to_string());
+
+        let version = self.bump_version(Topic::Messages);
+        self.message_versions.push(version);
+    }
+
+    pub fn process_threshold(&mut self, threshold: &types::Threshold) -> Result<(), String> {
         // Check if threshold already exists with source
-        self.test_info.thresholds.retain(|x| {
-            x.source != threshold.source && x.metric != threshold.metric
-        });
+        match self
+            .test_info
+            .thresholds
+            .iter()
+            .position(|x| x.source == threshold.source && x.metric == threshold.metric)
+        {
+            Some(index) => {
+                if self.test_info.thresholds[index] == *threshold {
+                    return Ok(());
+                }
 
-        // Append the new threshold
-        self.test_info.thresholds.push(threshold.clone());
+                self.test_info.thresholds[index] = threshold.clone();
+                self.threshold_versions[index] = self.bump_version(Topic::Thresholds);
+            }
+            None => {
+                // Append the new threshold
+                self.test_info.thresholds.push(threshold.clone());
 
-        self.thresholds_state = Uuid::new_v4().to_string();
-    }
+                let version = self.bump_version(Topic::Thresholds);
+                self.threshold_versions.push(version);
+            }
+        }
 
+        // Evaluate the new threshold against the data received so far and
+        // attach it to any checks it applies to
+        self.evaluate_thresholds_over_run();
+
+        if let Some(interval) = self.test_info.intervals.last().cloned() {
+            self.update_checks(&interval)?;
+        }
+
+        Ok(())
+    }
 }
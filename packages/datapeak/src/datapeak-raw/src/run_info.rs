@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{intervals::INTERVAL_PERIOD_SECONDS, manager::TestInfoManager, states::Topic, types};
+
+impl TestInfoManager {
+    // When period 0 started, if the sender recorded it
+    pub fn start_time(&self) -> Option<DateTime<Utc>> {
+        let start_time = self.test_info.start_time.as_ref()?;
+
+        DateTime::from_timestamp(start_time.seconds, start_time.nanos as u32)
+    }
+
+    // Width of each period, runs recorded before it was sent used 6 seconds
+    pub fn interval_seconds(&self) -> u32 {
+        match self.test_info.interval_seconds {
+            0 => INTERVAL_PERIOD_SECONDS,
+            interval_seconds => interval_seconds,
+        }
+    }
+
+    // When the period started, if the start of the run is known
+    pub fn period_start_time(&self, period: i32) -> Option<DateTime<Utc>> {
+        Some(self.start_time()? + Duration::seconds(period as i64 * self.interval_seconds() as i64))
+    }
+
+    // Seconds from the start of the run to the end of the period, if the start
+    // of the run is known. Periods before the start give negative values.
+    pub fn period_end_seconds(&self, period: i32) -> Option<f64> {
+        let elapsed = self.period_start_time(period + 1)? - self.start_time()?;
+
+        Some(elapsed.num_milliseconds() as f64 / 1000.0)
+    }
+
+    pub fn process_run_info(&mut self, run_info: &types::RunInfo) -> Result<(), String> {
+        let start_time = match run_info.start_time.as_ref() {
+            Some(start_time) => Some(
+                DateTime::from_timestamp(start_time.seconds, start_time.nanos as u32)
+                    .ok_or_else(|| format!("Invalid run start time: {}", start_time.seconds))?,
+            ),
+            None => None,
+        };
+
+        let interval_seconds = match run_info.interval_seconds {
+            0 => None,
+            interval_seconds => Some(interval_seconds),
+        };
+
+        self.set_run_info(start_time, interval_seconds)
+    }
+
+    // Records when the run started and the width of its periods, leaving either
+    // unchanged if not given. Intervals already received are recalculated if the
+    // width changes, as counter rates depend on it.
+    pub fn set_run_info(
+        &mut self,
+        start_time: Option<DateTime<Utc>>,
+        interval_seconds: Option<u32>,
+    ) -> Result<(), String> {
+        if interval_seconds == Some(0) {
+            return Err("Interval seconds must be greater than 0".to_string());
+        }
+
+        if let Some(start_time) = start_time {
+            let mut timestamp = protobuf::well_known_types::timestamp::Timestamp::new();
+            timestamp.seconds = start_time.timestamp();
+            timestamp.nanos = start_time.timestamp_subsec_nanos() as i32;
+
+            if self.test_info.start_time.as_ref() != Some(&timestamp) {
+                self.test_info.start_time = protobuf::MessageField::some(timestamp);
+
+                // Time series are plotted against the start time
+                self.bump_version(Topic::Intervals);
+            }
+        }
+
+        let interval_seconds = match interval_seconds {
+            Some(interval_seconds) => interval_seconds,
+            None => return Ok(()),
+        };
+
+        if interval_seconds == self.interval_seconds() {
+            self.test_info.interval_seconds = interval_seconds;
+            return Ok(());
+        }
+
+        let previous_interval_seconds = self.test_info.interval_seconds;
+        self.test_info.interval_seconds = interval_seconds;
+
+        // Keep the previous width if the intervals can't be recalculated, as
+        // they are left as they were
+        if let Err(err) = self.recalculate_intervals() {
+            self.test_info.interval_seconds = previous_interval_seconds;
+            return Err(err);
+        }
+
+        Ok(())
+    }
+}
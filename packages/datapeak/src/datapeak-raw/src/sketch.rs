@@ -0,0 +1,208 @@
+use crate::types;
+
+// Accuracy used for sketches built by datapeak itself, eg. when importing
+pub const DEFAULT_RELATIVE_ACCURACY: f64 = 0.01;
+
+// Labels that are derived from a sketch when trend sinks are merged
+const TREND_PERCENTILES: [(&str, f64); 3] = [("med", 50.0), ("p(90)", 90.0), ("p(95)", 95.0)];
+
+impl types::Sketch {
+    pub fn with_relative_accuracy(relative_accuracy: f64) -> types::Sketch {
+        let mut sketch = types::Sketch::new();
+        sketch.relative_accuracy = relative_accuracy;
+        sketch
+    }
+
+    // Bins are only meaningful for an accuracy strictly between 0 and 1
+    pub fn is_valid(&self) -> bool {
+        self.relative_accuracy > 0.0 && self.relative_accuracy < 1.0
+    }
+
+    fn gamma(&self) -> f64 {
+        (1.0 + self.relative_accuracy) / (1.0 - self.relative_accuracy)
+    }
+
+    fn bin_index(&self, value: f64) -> i32 {
+        (value.ln() / self.gamma().ln()).ceil() as i32
+    }
+
+    // Value in the middle of a bin, within the relative accuracy of every
+    // sample that was added to it
+    fn bin_value(&self, index: i32) -> f64 {
+        let gamma = self.gamma();
+        2.0 * gamma.powi(index) / (gamma + 1.0)
+    }
+
+    pub fn add(&mut self, value: f64) {
+        if value > 0.0 {
+            *self.positive_bins.entry(self.bin_index(value)).or_insert(0.0) += 1.0;
+        } else if value < 0.0 {
+            *self.negative_bins.entry(self.bin_index(-value)).or_insert(0.0) += 1.0;
+        } else {
+            self.zero_count += 1.0;
+        }
+
+        if self.count == 0.0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.count += 1.0;
+        self.sum += value;
+    }
+
+    pub fn merge(&mut self, other: &types::Sketch) -> Result<(), String> {
+        if other.count == 0.0 {
+            return Ok(());
+        }
+
+        if !other.is_valid() {
+            return Err(format!(
+                "Invalid sketch relative accuracy: {}",
+                other.relative_accuracy
+            ));
+        }
+
+        if self.count == 0.0 {
+            *self = other.clone();
+            return Ok(());
+        }
+
+        if self.relative_accuracy != other.relative_accuracy {
+            return Err("Cannot merge sketches with different relative accuracies".to_string());
+        }
+
+        for (index, count) in other.positive_bins.iter() {
+            *self.positive_bins.entry(*index).or_insert(0.0) += count;
+        }
+
+        for (index, count) in other.negative_bins.iter() {
+            *self.negative_bins.entry(*index).or_insert(0.0) += count;
+        }
+
+        self.zero_count += other.zero_count;
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+
+        Ok(())
+    }
+
+    // Returns the value at the given quantile, between 0 and 1
+    pub fn quantile(&self, quantile: f64) -> Option<f64> {
+        if self.count == 0.0 || !self.is_valid() || !(0.0..=1.0).contains(&quantile) {
+            return None;
+        }
+
+        let rank = quantile * (self.count - 1.0);
+        let mut seen = 0.0;
+
+        // Most negative values have the highest indexes
+        let mut negative_bins: Vec<(&i32, &f64)> = self.negative_bins.iter().collect();
+        negative_bins.sort_by(|a, b| b.0.cmp(a.0));
+
+        for (index, count) in negative_bins {
+            seen += count;
+            if seen > rank {
+                return Some((-self.bin_value(*index)).clamp(self.min, self.max));
+            }
+        }
+
+        seen += self.zero_count;
+        if seen > rank {
+            return Some(0.0);
+        }
+
+        let mut positive_bins: Vec<(&i32, &f64)> = self.positive_bins.iter().collect();
+        positive_bins.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (index, count) in positive_bins {
+            seen += count;
+            if seen > rank {
+                return Some(self.bin_value(*index).clamp(self.min, self.max));
+            }
+        }
+
+        Some(self.max)
+    }
+
+    // Recomputes the standard trend labels from the sketch
+    pub fn trend_labels(&self) -> Vec<(String, f64)> {
+        if self.count == 0.0 {
+            return Vec::new();
+        }
+
+        let mut labels = vec![
+            ("min".to_string(), self.min),
+            ("max".to_string(), self.max),
+            ("avg".to_string(), self.sum / self.count),
+            ("count".to_string(), self.count),
+        ];
+
+        for (label, percentile) in TREND_PERCENTILES.iter() {
+            if let Some(value) = self.quantile(percentile / 100.0) {
+                labels.push((label.to_string(), value));
+            }
+        }
+
+        labels
+    }
+}
+
+// Parses percentile labels such as "p(99.9)" into the percentile
+pub fn parse_percentile_label(label: &str) -> Option<f64> {
+    if label == "med" {
+        return Some(50.0);
+    }
+
+    label
+        .strip_prefix("p(")
+        .and_then(|x| x.strip_suffix(')'))
+        .and_then(|x| x.trim().parse::<f64>().ok())
+        .filter(|x| (0.0..=100.0).contains(x))
+}
+
+// Gets the value of a label from a sink. Percentiles are calculated from the
+// sketch when one is present so that any percentile can be requested, and
+// fall back to the precomputed labels otherwise.
+pub fn sink_label_value(sink: &types::Sink, label: &str) -> Option<f64> {
+    if let (Some(sketch), Some(percentile)) = (sink.sketch.as_ref(), parse_percentile_label(label)) {
+        if let Some(value) = sketch.quantile(percentile / 100.0) {
+            return Some(value);
+        }
+    }
+
+    sink.labels.get(label).copied()
+}
+
+// Removes a sketch whose relative accuracy isn't between 0 and 1, as its bins
+// can't be interpreted, so that the sink's plain labels are used instead
+pub fn remove_invalid_sketch(sink: &mut types::Sink) {
+    if sink.sketch.as_ref().is_some_and(|x| !x.is_valid()) {
+        sink.sketch.clear();
+    }
+}
+
+// Merges the sketches of the given sinks if all of them have one, as a merge
+// of only some of the samples would be misleading
+pub fn merge_sink_sketches(sinks: &[&types::Sink]) -> Result<Option<types::Sketch>, String> {
+    let mut merged: Option<types::Sketch> = None;
+
+    for sink in sinks.iter() {
+        let sketch = match sink.sketch.as_ref() {
+            Some(sketch) if sketch.count > 0.0 => sketch,
+            _ => return Ok(None),
+        };
+
+        match merged.as_mut() {
+            Some(merged) => merged.merge(sketch)?,
+            None => merged = Some(sketch.clone()),
+        }
+    }
+
+    Ok(merged)
+}
@@ -1,41 +1,134 @@
-use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::MANAGERS;
+use crate::{manager::TestInfoManager, types};
 
-#[wasm_bindgen (js_name = rawGetLocationsState)]
-pub fn get_location_state(test_info_id: &str) -> Result<String, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Topic {
+    Intervals,
+    Checks,
+    Thresholds,
+    ConsoleMessages,
+    Locations,
+    Messages,
+    Summary,
+    Abort,
+    Anomalies,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 9] = [
+        Topic::Intervals,
+        Topic::Checks,
+        Topic::Thresholds,
+        Topic::ConsoleMessages,
+        Topic::Locations,
+        Topic::Messages,
+        Topic::Summary,
+        Topic::Abort,
+        Topic::Anomalies,
+    ];
 
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Topic::Intervals => "intervals",
+            Topic::Checks => "checks",
+            Topic::Thresholds => "thresholds",
+            Topic::ConsoleMessages => "consoleMessages",
+            Topic::Locations => "locations",
+            Topic::Messages => "messages",
+            Topic::Summary => "summary",
+            Topic::Abort => "abort",
+            Topic::Anomalies => "anomalies",
+        }
+    }
+}
 
-    Ok(manager.locations_state.to_owned())
+// Everything added or modified after a given version
+pub struct Changes {
+    pub version: u64,
+    pub intervals: Vec<types::Interval>,
+    pub console_messages: Vec<types::ConsoleMessage>,
+    pub thresholds: Vec<types::Threshold>,
+    pub messages: Vec<String>,
 }
 
-#[wasm_bindgen (js_name = rawGetThresholdsState)]
-pub fn get_thresholds_state(test_info_id: &str) -> Result<String, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+impl TestInfoManager {
+    // Increments the version of the test info and records it as the version of
+    // the given topic, returning the new version
+    pub fn bump_version(&mut self, topic: Topic) -> u64 {
+        self.version += 1;
+        self.topic_versions.insert(topic, self.version);
+        self.version
+    }
 
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
+    // Replaces the selected value and bumps the version of the topic, but only
+    // if the new value differs from the current one. Returns if it changed.
+    pub fn replace_if_changed<T: PartialEq>(
+        &mut self,
+        topic: Topic,
+        select: impl FnOnce(&mut TestInfoManager) -> &mut T,
+        value: T,
+    ) -> bool {
+        let current = select(self);
 
-    Ok(manager.thresholds_state.to_owned())
-}
+        if *current == value {
+            return false;
+        }
 
+        *current = value;
+        self.bump_version(topic);
 
+        true
+    }
 
-#[wasm_bindgen (js_name = rawGetMessagesState)]
-pub fn get_messages_state(test_info_id: &str) -> Result<String, JsValue> {
-    let mut managers = MANAGERS.lock().unwrap();
+    pub fn topic_version(&self, topic: Topic) -> u64 {
+        self.topic_versions.get(&topic).copied().unwrap_or(0)
+    }
 
-    // Get test data if it exists, otherwise return
-    let manager = managers.get_mut(test_info_id).ok_or_else(|| {
-        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
-    })?;
+    pub fn topic_versions(&self) -> Vec<(Topic, u64)> {
+        Topic::ALL
+            .iter()
+            .map(|topic| (*topic, self.topic_version(*topic)))
+            .collect()
+    }
+
+    // Returns the topics whose version differs from the given snapshot
+    pub fn changed_topics(&self, previous_versions: &[(Topic, u64)]) -> Vec<Topic> {
+        previous_versions
+            .iter()
+            .filter(|(topic, version)| self.topic_version(*topic) != *version)
+            .map(|(topic, _)| *topic)
+            .collect()
+    }
+
+    pub fn changes_since(&self, version: u64) -> Changes {
+        Changes {
+            version: self.version,
+            intervals: self
+                .test_info
+                .intervals
+                .iter()
+                .filter(|x| self.interval_versions.get(&x.period).copied().unwrap_or(0) > version)
+                .cloned()
+                .collect(),
+            console_messages: changed_since(
+                &self.test_info.console_messages,
+                &self.console_message_versions,
+                version,
+            ),
+            thresholds: changed_since(&self.test_info.thresholds, &self.threshold_versions, version),
+            messages: changed_since(&self.test_info.messages, &self.message_versions, version),
+        }
+    }
+}
 
-    Ok(manager.messages_state.to_owned())
+// Filters items by the version in the matching position of their versions
+fn changed_since<T: Clone>(items: &[T], versions: &[u64], version: u64) -> Vec<T> {
+    items
+        .iter()
+        .zip(versions.iter())
+        .filter(|(_, item_version)| **item_version > version)
+        .map(|(item, _)| item.clone())
+        .collect()
 }
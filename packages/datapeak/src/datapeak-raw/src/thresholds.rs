@@ -0,0 +1,342 @@
+use serde::Serialize;
+
+use crate::{
+    intervals::merge::GLOBAL_LOCATION,
+    manager::TestInfoManager,
+    sketch::{parse_percentile_label, sink_label_value},
+    states::Topic,
+    types,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThresholdOperator {
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdExpression {
+    // Name of the sink label the expression is evaluated against, e.g. "p(95)"
+    pub aggregation: String,
+    pub operator: ThresholdOperator,
+    pub value: f64,
+}
+
+impl ThresholdExpression {
+    pub fn evaluate(&self, value: f64) -> bool {
+        match self.operator {
+            ThresholdOperator::LessThan => value < self.value,
+            ThresholdOperator::LessThanOrEqual => value <= self.value,
+            ThresholdOperator::GreaterThan => value > self.value,
+            ThresholdOperator::GreaterThanOrEqual => value >= self.value,
+            ThresholdOperator::Equal => value == self.value,
+            ThresholdOperator::NotEqual => value != self.value,
+        }
+    }
+}
+
+// Parses k6 style threshold expressions such as "p(95)<500" or "rate>0.99"
+pub fn parse_threshold_source(source: &str) -> Result<ThresholdExpression, String> {
+    const OPERATORS: [(&str, ThresholdOperator); 7] = [
+        ("===", ThresholdOperator::Equal),
+        ("==", ThresholdOperator::Equal),
+        ("!=", ThresholdOperator::NotEqual),
+        ("<=", ThresholdOperator::LessThanOrEqual),
+        (">=", ThresholdOperator::GreaterThanOrEqual),
+        ("<", ThresholdOperator::LessThan),
+        (">", ThresholdOperator::GreaterThan),
+    ];
+
+    let operator_start = source
+        .find(['<', '>', '=', '!'])
+        .ok_or_else(|| format!("No operator found in threshold: {}", source))?;
+
+    let (aggregation, rest) = source.split_at(operator_start);
+
+    let (operator_str, operator) = OPERATORS
+        .iter()
+        .find(|(operator_str, _)| rest.starts_with(operator_str))
+        .ok_or_else(|| format!("Invalid operator in threshold: {}", source))?;
+
+    let value_str = rest[operator_str.len()..].trim();
+    let value = value_str
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid value '{}' in threshold: {}", value_str, source))?;
+
+    Ok(ThresholdExpression {
+        aggregation: parse_aggregation(aggregation.trim())?,
+        operator: *operator,
+        value,
+    })
+}
+
+fn parse_aggregation(aggregation: &str) -> Result<String, String> {
+    match aggregation {
+        "count" | "rate" | "value" | "avg" | "min" | "max" | "med" => {
+            return Ok(aggregation.to_string())
+        }
+        _ => {}
+    }
+
+    let percentile = parse_percentile_label(aggregation)
+        .ok_or_else(|| format!("Invalid threshold aggregation: {}", aggregation))?;
+
+    // Normalise so that "p(95.0)" and "p(95)" refer to the same label
+    Ok(format!("p({})", percentile))
+}
+
+// Converts a k6 metric with an optional tag selector into the sink path used
+// after the location, e.g. "checks{check:status is 200}" becomes
+// "checks::check::status is 200"
+pub fn metric_to_sink_suffix(metric: &str) -> String {
+    let (name, tags) = match metric.split_once('{') {
+        Some((name, tags)) => (name, tags.trim_end_matches('}')),
+        None => return metric.trim().to_string(),
+    };
+
+    let mut parts = vec![name.trim().to_string()];
+
+    for tag in tags.split(',') {
+        if let Some((key, value)) = tag.split_once(':') {
+            parts.push(key.trim().to_string());
+            parts.push(value.trim().to_string());
+        }
+    }
+
+    parts.join("::")
+}
+
+// Parses k6 style durations such as "10s", "1m30s" or "500ms" into seconds
+pub fn parse_duration_seconds(duration: &str) -> Result<f64, String> {
+    let duration = duration.trim();
+
+    if duration.is_empty() {
+        return Err("Empty duration".to_string());
+    }
+
+    let mut seconds = 0.0;
+    let mut rest = duration;
+
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+
+        let value = rest[..number_end]
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid duration: {}", duration))?;
+
+        rest = &rest[number_end..];
+
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+
+        seconds += value
+            * match &rest[..unit_end] {
+                "ms" => 0.001,
+                "s" => 1.0,
+                "m" => 60.0,
+                "h" => 3600.0,
+                unit => return Err(format!("Invalid duration unit '{}' in: {}", unit, duration)),
+            };
+
+        rest = &rest[unit_end..];
+    }
+
+    Ok(seconds)
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdResult {
+    pub metric: String,
+    pub source: String,
+    pub location: String,
+    pub period: i32,
+    pub passed: bool,
+    pub value: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AbortDecision {
+    pub metric: String,
+    pub source: String,
+    pub period: i32,
+    pub value: Option<f64>,
+}
+
+impl TestInfoManager {
+    // Evaluates every threshold against the given interval, both per location
+    // and against the global sinks of all locations combined. A failure is kept
+    // once recorded, along with the value and period that failed, so that a
+    // breach isn't hidden by later intervals that pass.
+    pub fn evaluate_thresholds(&mut self, interval: &types::Interval) {
+        self.evaluate_thresholds_over(std::slice::from_ref(interval));
+    }
+
+    // Evaluates every threshold against each interval received so far, in
+    // period order, eg. when data is loaded or a threshold is added
+    pub fn evaluate_thresholds_over_run(&mut self) {
+        let intervals = self.test_info.intervals.clone();
+        self.evaluate_thresholds_over(&intervals);
+    }
+
+    // Evaluates every threshold against each of the given intervals in turn,
+    // only bumping the version once if the results changed
+    pub(crate) fn evaluate_thresholds_over(&mut self, intervals: &[types::Interval]) {
+        let previous_results = self.threshold_results.clone();
+
+        for interval in intervals.iter() {
+            let new_results = self.interval_threshold_results(interval);
+
+            for new_result in new_results.iter() {
+                match self.threshold_results.iter_mut().find(|x| {
+                    x.metric == new_result.metric
+                        && x.source == new_result.source
+                        && x.location == new_result.location
+                }) {
+                    Some(existing_result) => {
+                        // Keep the first breach
+                        if !existing_result.passed && existing_result.error.is_none() {
+                            continue;
+                        }
+
+                        *existing_result = new_result.clone();
+                    }
+                    None => self.threshold_results.push(new_result.clone()),
+                }
+            }
+
+            self.update_abort_decision(interval.period, &new_results);
+        }
+
+        if self.threshold_results != previous_results {
+            self.bump_version(Topic::Thresholds);
+        }
+    }
+
+    // Evaluates every threshold against the summary of the whole run, rather
+    // than interval by interval
+    pub fn summary_threshold_results(&self) -> Vec<ThresholdResult> {
+        match self.test_info.summary.as_ref() {
+            Some(summary) => self.interval_threshold_results(summary),
+            None => Vec::new(),
+        }
+    }
+
+    fn interval_threshold_results(&self, interval: &types::Interval) -> Vec<ThresholdResult> {
+        let mut results: Vec<ThresholdResult> = Vec::new();
+
+        for threshold in self.test_info.thresholds.iter() {
+            let new_result =
+                |location: &str, value: Option<f64>, error: Option<String>| ThresholdResult {
+                    metric: threshold.metric.to_string(),
+                    source: threshold.source.to_string(),
+                    location: location.to_string(),
+                    period: interval.period,
+                    passed: false,
+                    value,
+                    error,
+                };
+
+            let expression = match parse_threshold_source(&threshold.source) {
+                Ok(expression) => expression,
+                Err(err) => {
+                    results.push(new_result(GLOBAL_LOCATION, None, Some(err)));
+                    continue;
+                }
+            };
+
+            let sink_suffix = metric_to_sink_suffix(&threshold.metric);
+
+            // Sinks for each location, including the global sink for all of them
+            let location_sinks = interval.sinks.iter().filter_map(|(sink_name, sink)| {
+                match sink_name.split_once("::") {
+                    Some((location, suffix)) if suffix == sink_suffix => Some((location, sink)),
+                    _ => None,
+                }
+            });
+
+            for (location, sink) in location_sinks {
+                match sink_label_value(sink, &expression.aggregation) {
+                    Some(value) => results.push(ThresholdResult {
+                        passed: expression.evaluate(value),
+                        ..new_result(location, Some(value), None)
+                    }),
+                    None => results.push(new_result(
+                        location,
+                        None,
+                        Some(format!(
+                            "Aggregation {} not found for metric {}",
+                            expression.aggregation, threshold.metric
+                        )),
+                    )),
+                }
+            }
+        }
+
+        // Sinks are unordered, so sort to keep the results in a stable order
+        results.sort_by(|a, b| {
+            (&a.metric, &a.source, &a.location).cmp(&(&b.metric, &b.source, &b.location))
+        });
+
+        results
+    }
+
+    // Records the first breach of a threshold with abort_on_fail set, once its
+    // delay_abort_eval has elapsed. Only the result for all locations combined
+    // is considered so that there is a single decision for the whole test, and
+    // only its result for the given period, so that breaches from before the
+    // delay elapsed are ignored.
+    fn update_abort_decision(&mut self, period: i32, results: &[ThresholdResult]) {
+        if self.abort_decision.is_some() {
+            return;
+        }
+
+        // Without a run start time, periods before the first count as the first
+        let elapsed_seconds = self
+            .period_end_seconds(period)
+            .unwrap_or_else(|| (period.max(0) as i64 + 1) as f64 * self.interval_seconds() as f64);
+
+        for threshold in self.test_info.thresholds.iter() {
+            if threshold.abort_on_fail != Some(true) {
+                continue;
+            }
+
+            if let Some(delay_abort_eval) = &threshold.delay_abort_eval {
+                match parse_duration_seconds(delay_abort_eval) {
+                    Ok(delay_seconds) if elapsed_seconds < delay_seconds => continue,
+                    Ok(_) => {}
+                    // Thresholds that can't be parsed are reported in the results
+                    Err(_) => continue,
+                }
+            }
+
+            let breached = results.iter().find(|x| {
+                x.metric == threshold.metric
+                    && x.source == threshold.source
+                    && x.location == GLOBAL_LOCATION
+                    && x.error.is_none()
+                    && !x.passed
+            });
+
+            if let Some(breached) = breached {
+                self.abort_decision = Some(AbortDecision {
+                    metric: breached.metric.to_string(),
+                    source: breached.source.to_string(),
+                    period: breached.period,
+                    value: breached.value,
+                });
+                self.bump_version(Topic::Abort);
+                return;
+            }
+        }
+    }
+}
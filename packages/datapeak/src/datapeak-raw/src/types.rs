@@ -1,5 +1,5 @@
-// This file is generated by rust-protobuf 3.2.0. Do not edit
-// .proto file is parsed by protoc --rust-out=...
+// This file is generated by rust-protobuf 3.7.2. Do not edit
+// .proto file is parsed by protoc --rs_out=...
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -9,7 +9,6 @@
 #![allow(unused_attributes)]
 #![cfg_attr(rustfmt, rustfmt::skip)]
 
-#![allow(box_pointers)]
 #![allow(dead_code)]
 #![allow(missing_docs)]
 #![allow(non_camel_case_types)]
@@ -25,16 +24,302 @@ use serde::Serialize;
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_2_0;
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_7_2;
 
+///  DDSketch of the samples recorded by a sink. Unlike precomputed percentile
+///  labels, sketches can be merged across intervals and locations.
+// @@protoc_insertion_point(message:Sketch)
 #[derive(PartialEq,Clone,Default,Debug)]
+pub struct Sketch {
+    // message fields
+    // @@protoc_insertion_point(field:Sketch.relative_accuracy)
+    pub relative_accuracy: f64,
+    ///  map<bin index, number of samples>
+    // @@protoc_insertion_point(field:Sketch.positive_bins)
+    pub positive_bins: ::std::collections::HashMap<i32, f64>,
+    // @@protoc_insertion_point(field:Sketch.negative_bins)
+    pub negative_bins: ::std::collections::HashMap<i32, f64>,
+    // @@protoc_insertion_point(field:Sketch.zero_count)
+    pub zero_count: f64,
+    // @@protoc_insertion_point(field:Sketch.count)
+    pub count: f64,
+    // @@protoc_insertion_point(field:Sketch.sum)
+    pub sum: f64,
+    // @@protoc_insertion_point(field:Sketch.min)
+    pub min: f64,
+    // @@protoc_insertion_point(field:Sketch.max)
+    pub max: f64,
+    // special fields
+    // @@protoc_insertion_point(special_field:Sketch.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Sketch {
+    fn default() -> &'a Sketch {
+        <Sketch as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Sketch {
+    pub fn new() -> Sketch {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(8);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "relative_accuracy",
+            |m: &Sketch| { &m.relative_accuracy },
+            |m: &mut Sketch| { &mut m.relative_accuracy },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
+            "positive_bins",
+            |m: &Sketch| { &m.positive_bins },
+            |m: &mut Sketch| { &mut m.positive_bins },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
+            "negative_bins",
+            |m: &Sketch| { &m.negative_bins },
+            |m: &mut Sketch| { &mut m.negative_bins },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "zero_count",
+            |m: &Sketch| { &m.zero_count },
+            |m: &mut Sketch| { &mut m.zero_count },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "count",
+            |m: &Sketch| { &m.count },
+            |m: &mut Sketch| { &mut m.count },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "sum",
+            |m: &Sketch| { &m.sum },
+            |m: &mut Sketch| { &mut m.sum },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "min",
+            |m: &Sketch| { &m.min },
+            |m: &mut Sketch| { &mut m.min },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "max",
+            |m: &Sketch| { &m.max },
+            |m: &mut Sketch| { &mut m.max },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Sketch>(
+            "Sketch",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Sketch {
+    const NAME: &'static str = "Sketch";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                9 => {
+                    self.relative_accuracy = is.read_double()?;
+                },
+                18 => {
+                    let len = is.read_raw_varint32()?;
+                    let old_limit = is.push_limit(len as u64)?;
+                    let mut key = ::std::default::Default::default();
+                    let mut value = ::std::default::Default::default();
+                    while let Some(tag) = is.read_raw_tag_or_eof()? {
+                        match tag {
+                            8 => key = is.read_sint32()?,
+                            17 => value = is.read_double()?,
+                            _ => ::protobuf::rt::skip_field_for_tag(tag, is)?,
+                        };
+                    }
+                    is.pop_limit(old_limit);
+                    self.positive_bins.insert(key, value);
+                },
+                26 => {
+                    let len = is.read_raw_varint32()?;
+                    let old_limit = is.push_limit(len as u64)?;
+                    let mut key = ::std::default::Default::default();
+                    let mut value = ::std::default::Default::default();
+                    while let Some(tag) = is.read_raw_tag_or_eof()? {
+                        match tag {
+                            8 => key = is.read_sint32()?,
+                            17 => value = is.read_double()?,
+                            _ => ::protobuf::rt::skip_field_for_tag(tag, is)?,
+                        };
+                    }
+                    is.pop_limit(old_limit);
+                    self.negative_bins.insert(key, value);
+                },
+                33 => {
+                    self.zero_count = is.read_double()?;
+                },
+                41 => {
+                    self.count = is.read_double()?;
+                },
+                49 => {
+                    self.sum = is.read_double()?;
+                },
+                57 => {
+                    self.min = is.read_double()?;
+                },
+                65 => {
+                    self.max = is.read_double()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.relative_accuracy != 0. {
+            my_size += 1 + 8;
+        }
+        for (k, v) in &self.positive_bins {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::sint32_size(1, *k);
+            entry_size += 1 + 8;
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(entry_size) + entry_size
+        };
+        for (k, v) in &self.negative_bins {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::sint32_size(1, *k);
+            entry_size += 1 + 8;
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(entry_size) + entry_size
+        };
+        if self.zero_count != 0. {
+            my_size += 1 + 8;
+        }
+        if self.count != 0. {
+            my_size += 1 + 8;
+        }
+        if self.sum != 0. {
+            my_size += 1 + 8;
+        }
+        if self.min != 0. {
+            my_size += 1 + 8;
+        }
+        if self.max != 0. {
+            my_size += 1 + 8;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.relative_accuracy != 0. {
+            os.write_double(1, self.relative_accuracy)?;
+        }
+        for (k, v) in &self.positive_bins {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::sint32_size(1, *k);
+            entry_size += 1 + 8;
+            os.write_raw_varint32(18)?; // Tag.
+            os.write_raw_varint32(entry_size as u32)?;
+            os.write_sint32(1, *k)?;
+            os.write_double(2, *v)?;
+        };
+        for (k, v) in &self.negative_bins {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::sint32_size(1, *k);
+            entry_size += 1 + 8;
+            os.write_raw_varint32(26)?; // Tag.
+            os.write_raw_varint32(entry_size as u32)?;
+            os.write_sint32(1, *k)?;
+            os.write_double(2, *v)?;
+        };
+        if self.zero_count != 0. {
+            os.write_double(4, self.zero_count)?;
+        }
+        if self.count != 0. {
+            os.write_double(5, self.count)?;
+        }
+        if self.sum != 0. {
+            os.write_double(6, self.sum)?;
+        }
+        if self.min != 0. {
+            os.write_double(7, self.min)?;
+        }
+        if self.max != 0. {
+            os.write_double(8, self.max)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Sketch {
+        Sketch::new()
+    }
+
+    fn clear(&mut self) {
+        self.relative_accuracy = 0.;
+        self.positive_bins.clear();
+        self.negative_bins.clear();
+        self.zero_count = 0.;
+        self.count = 0.;
+        self.sum = 0.;
+        self.min = 0.;
+        self.max = 0.;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Sketch {
+        static instance: ::protobuf::rt::Lazy<Sketch> = ::protobuf::rt::Lazy::new();
+        instance.get(Sketch::new)
+    }
+}
+
+impl ::protobuf::MessageFull for Sketch {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Sketch").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Sketch {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Sketch {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
 // @@protoc_insertion_point(message:Sink)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct Sink {
     // message fields
     // @@protoc_insertion_point(field:Sink.type)
     pub type_: ::protobuf::EnumOrUnknown<SinkType>,
     // @@protoc_insertion_point(field:Sink.labels)
     pub labels: ::std::collections::HashMap<::std::string::String, f64>,
+    ///  Only set for Trend sinks by senders that support it
+    // @@protoc_insertion_point(field:Sink.sketch)
+    pub sketch: ::protobuf::MessageField<Sketch>,
     // special fields
     // @@protoc_insertion_point(special_field:Sink.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -52,18 +337,23 @@ impl Sink {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut fields = ::std::vec::Vec::with_capacity(3);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
             "type",
             |m: &Sink| { &m.type_ },
             |m: &mut Sink| { &mut m.type_ },
         ));
-        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor::<_, _, _>(
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
             "labels",
             |m: &Sink| { &m.labels },
             |m: &mut Sink| { &mut m.labels },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Sketch>(
+            "sketch",
+            |m: &Sink| { &m.sketch },
+            |m: &mut Sink| { &mut m.sketch },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Sink>(
             "Sink",
             fields,
@@ -100,6 +390,9 @@ impl ::protobuf::Message for Sink {
                     is.pop_limit(old_limit);
                     self.labels.insert(key, value);
                 },
+                26 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.sketch)?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -121,6 +414,10 @@ impl ::protobuf::Message for Sink {
             entry_size += 1 + 8;
             my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(entry_size) + entry_size
         };
+        if let Some(v) = self.sketch.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -139,6 +436,9 @@ impl ::protobuf::Message for Sink {
             os.write_string(1, &k)?;
             os.write_double(2, *v)?;
         };
+        if let Some(v) = self.sketch.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -158,6 +458,7 @@ impl ::protobuf::Message for Sink {
     fn clear(&mut self) {
         self.type_ = ::protobuf::EnumOrUnknown::new(SinkType::Counter);
         self.labels.clear();
+        self.sketch.clear();
         self.special_fields.clear();
     }
 
@@ -184,8 +485,8 @@ impl ::protobuf::reflect::ProtobufValue for Sink {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:Interval)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct Interval {
     // message fields
     // @@protoc_insertion_point(field:Interval.period)
@@ -217,7 +518,7 @@ impl Interval {
             |m: &Interval| { &m.period },
             |m: &mut Interval| { &mut m.period },
         ));
-        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor::<_, _, _>(
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
             "sinks",
             |m: &Interval| { &m.sinks },
             |m: &mut Interval| { &mut m.sinks },
@@ -344,8 +645,8 @@ impl ::protobuf::reflect::ProtobufValue for Interval {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:ConsoleMessage)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct ConsoleMessage {
     // message fields
     // @@protoc_insertion_point(field:ConsoleMessage.message)
@@ -398,7 +699,7 @@ impl ConsoleMessage {
             |m: &ConsoleMessage| { &m.last_occurred },
             |m: &mut ConsoleMessage| { &mut m.last_occurred },
         ));
-        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor::<_, _, _>(
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
             "count",
             |m: &ConsoleMessage| { &m.count },
             |m: &mut ConsoleMessage| { &mut m.count },
@@ -555,9 +856,9 @@ impl ::protobuf::reflect::ProtobufValue for ConsoleMessage {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+// @@protoc_insertion_point(message:Threshold)
 #[derive(PartialEq,Clone,Default,Debug,Serialize)]
 #[serde(rename_all = "camelCase")]
-// @@protoc_insertion_point(message:Threshold)
 pub struct Threshold {
     // message fields
     // @@protoc_insertion_point(field:Threshold.source)
@@ -568,9 +869,9 @@ pub struct Threshold {
     pub abort_on_fail: ::std::option::Option<bool>,
     // @@protoc_insertion_point(field:Threshold.delay_abort_eval)
     pub delay_abort_eval: ::std::option::Option<::std::string::String>,
-    #[serde(skip_serializing)]
     // special fields
     // @@protoc_insertion_point(special_field:Threshold.special_fields)
+    #[serde(skip_serializing)]
     pub special_fields: ::protobuf::SpecialFields,
 }
 
@@ -734,8 +1035,8 @@ impl ::protobuf::reflect::ProtobufValue for Threshold {
 }
 
 ///  TestInfo is the root data structure for the data stored in the database.
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:TestInfo)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct TestInfo {
     // message fields
     // @@protoc_insertion_point(field:TestInfo.intervals)
@@ -749,6 +1050,15 @@ pub struct TestInfo {
     ///  Things such as limit alerts, etc. that are not console messages
     // @@protoc_insertion_point(field:TestInfo.messages)
     pub messages: ::std::vec::Vec<::std::string::String>,
+    // @@protoc_insertion_point(field:TestInfo.locations)
+    pub locations: ::std::vec::Vec<::std::string::String>,
+    ///  When period 0 started, unset if the sender didn't record it
+    // @@protoc_insertion_point(field:TestInfo.start_time)
+    pub start_time: ::protobuf::MessageField<::protobuf::well_known_types::timestamp::Timestamp>,
+    ///  Width of each period, 0 for runs recorded before it was sent which used
+    ///  6 seconds
+    // @@protoc_insertion_point(field:TestInfo.interval_seconds)
+    pub interval_seconds: u32,
     // special fields
     // @@protoc_insertion_point(special_field:TestInfo.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -766,7 +1076,7 @@ impl TestInfo {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(5);
+        let mut fields = ::std::vec::Vec::with_capacity(8);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
             "intervals",
@@ -793,6 +1103,21 @@ impl TestInfo {
             |m: &TestInfo| { &m.messages },
             |m: &mut TestInfo| { &mut m.messages },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "locations",
+            |m: &TestInfo| { &m.locations },
+            |m: &mut TestInfo| { &mut m.locations },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, ::protobuf::well_known_types::timestamp::Timestamp>(
+            "start_time",
+            |m: &TestInfo| { &m.start_time },
+            |m: &mut TestInfo| { &mut m.start_time },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "interval_seconds",
+            |m: &TestInfo| { &m.interval_seconds },
+            |m: &mut TestInfo| { &mut m.interval_seconds },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<TestInfo>(
             "TestInfo",
             fields,
@@ -826,6 +1151,15 @@ impl ::protobuf::Message for TestInfo {
                 42 => {
                     self.messages.push(is.read_string()?);
                 },
+                50 => {
+                    self.locations.push(is.read_string()?);
+                },
+                58 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.start_time)?;
+                },
+                64 => {
+                    self.interval_seconds = is.read_uint32()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -857,6 +1191,16 @@ impl ::protobuf::Message for TestInfo {
         for value in &self.messages {
             my_size += ::protobuf::rt::string_size(5, &value);
         };
+        for value in &self.locations {
+            my_size += ::protobuf::rt::string_size(6, &value);
+        };
+        if let Some(v) = self.start_time.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if self.interval_seconds != 0 {
+            my_size += ::protobuf::rt::uint32_size(8, self.interval_seconds);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -878,6 +1222,15 @@ impl ::protobuf::Message for TestInfo {
         for v in &self.messages {
             os.write_string(5, &v)?;
         };
+        for v in &self.locations {
+            os.write_string(6, &v)?;
+        };
+        if let Some(v) = self.start_time.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(7, v, os)?;
+        }
+        if self.interval_seconds != 0 {
+            os.write_uint32(8, self.interval_seconds)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -900,6 +1253,9 @@ impl ::protobuf::Message for TestInfo {
         self.console_messages.clear();
         self.thresholds.clear();
         self.messages.clear();
+        self.locations.clear();
+        self.start_time.clear();
+        self.interval_seconds = 0;
         self.special_fields.clear();
     }
 
@@ -910,6 +1266,9 @@ impl ::protobuf::Message for TestInfo {
             console_messages: ::std::vec::Vec::new(),
             thresholds: ::std::vec::Vec::new(),
             messages: ::std::vec::Vec::new(),
+            locations: ::std::vec::Vec::new(),
+            start_time: ::protobuf::MessageField::none(),
+            interval_seconds: 0,
             special_fields: ::protobuf::SpecialFields::new(),
         };
         &instance
@@ -933,8 +1292,150 @@ impl ::protobuf::reflect::ProtobufValue for TestInfo {
     type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
 }
 
+///  Sent at the start of a run so that periods can be given real timestamps
+// @@protoc_insertion_point(message:RunInfo)
 #[derive(PartialEq,Clone,Default,Debug)]
+pub struct RunInfo {
+    // message fields
+    // @@protoc_insertion_point(field:RunInfo.start_time)
+    pub start_time: ::protobuf::MessageField<::protobuf::well_known_types::timestamp::Timestamp>,
+    // @@protoc_insertion_point(field:RunInfo.interval_seconds)
+    pub interval_seconds: u32,
+    // special fields
+    // @@protoc_insertion_point(special_field:RunInfo.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a RunInfo {
+    fn default() -> &'a RunInfo {
+        <RunInfo as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl RunInfo {
+    pub fn new() -> RunInfo {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, ::protobuf::well_known_types::timestamp::Timestamp>(
+            "start_time",
+            |m: &RunInfo| { &m.start_time },
+            |m: &mut RunInfo| { &mut m.start_time },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "interval_seconds",
+            |m: &RunInfo| { &m.interval_seconds },
+            |m: &mut RunInfo| { &mut m.interval_seconds },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<RunInfo>(
+            "RunInfo",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for RunInfo {
+    const NAME: &'static str = "RunInfo";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.start_time)?;
+                },
+                16 => {
+                    self.interval_seconds = is.read_uint32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.start_time.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if self.interval_seconds != 0 {
+            my_size += ::protobuf::rt::uint32_size(2, self.interval_seconds);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.start_time.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        if self.interval_seconds != 0 {
+            os.write_uint32(2, self.interval_seconds)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> RunInfo {
+        RunInfo::new()
+    }
+
+    fn clear(&mut self) {
+        self.start_time.clear();
+        self.interval_seconds = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static RunInfo {
+        static instance: RunInfo = RunInfo {
+            start_time: ::protobuf::MessageField::none(),
+            interval_seconds: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for RunInfo {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("RunInfo").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for RunInfo {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RunInfo {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
 // @@protoc_insertion_point(message:DataPoint)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct DataPoint {
     // message oneof groups
     pub data: ::std::option::Option<data_point::Data>,
@@ -1101,8 +1602,57 @@ impl DataPoint {
         }
     }
 
+    // .RunInfo run_info = 4;
+
+    pub fn run_info(&self) -> &RunInfo {
+        match self.data {
+            ::std::option::Option::Some(data_point::Data::RunInfo(ref v)) => v,
+            _ => <RunInfo as ::protobuf::Message>::default_instance(),
+        }
+    }
+
+    pub fn clear_run_info(&mut self) {
+        self.data = ::std::option::Option::None;
+    }
+
+    pub fn has_run_info(&self) -> bool {
+        match self.data {
+            ::std::option::Option::Some(data_point::Data::RunInfo(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_run_info(&mut self, v: RunInfo) {
+        self.data = ::std::option::Option::Some(data_point::Data::RunInfo(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_run_info(&mut self) -> &mut RunInfo {
+        if let ::std::option::Option::Some(data_point::Data::RunInfo(_)) = self.data {
+        } else {
+            self.data = ::std::option::Option::Some(data_point::Data::RunInfo(RunInfo::new()));
+        }
+        match self.data {
+            ::std::option::Option::Some(data_point::Data::RunInfo(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_run_info(&mut self) -> RunInfo {
+        if self.has_run_info() {
+            match self.data.take() {
+                ::std::option::Option::Some(data_point::Data::RunInfo(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            RunInfo::new()
+        }
+    }
+
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut fields = ::std::vec::Vec::with_capacity(4);
         let mut oneofs = ::std::vec::Vec::with_capacity(1);
         fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, Interval>(
             "interval",
@@ -1125,6 +1675,13 @@ impl DataPoint {
             DataPoint::mut_threshold,
             DataPoint::set_threshold,
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, RunInfo>(
+            "run_info",
+            DataPoint::has_run_info,
+            DataPoint::run_info,
+            DataPoint::mut_run_info,
+            DataPoint::set_run_info,
+        ));
         oneofs.push(data_point::Data::generated_oneof_descriptor_data());
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<DataPoint>(
             "DataPoint",
@@ -1153,6 +1710,9 @@ impl ::protobuf::Message for DataPoint {
                 26 => {
                     self.data = ::std::option::Option::Some(data_point::Data::Threshold(is.read_message()?));
                 },
+                34 => {
+                    self.data = ::std::option::Option::Some(data_point::Data::RunInfo(is.read_message()?));
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -1179,6 +1739,10 @@ impl ::protobuf::Message for DataPoint {
                     let len = v.compute_size();
                     my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
                 },
+                &data_point::Data::RunInfo(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                },
             };
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
@@ -1198,6 +1762,9 @@ impl ::protobuf::Message for DataPoint {
                 &data_point::Data::Threshold(ref v) => {
                     ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
                 },
+                &data_point::Data::RunInfo(ref v) => {
+                    ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+                },
             };
         }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
@@ -1220,6 +1787,7 @@ impl ::protobuf::Message for DataPoint {
         self.data = ::std::option::Option::None;
         self.data = ::std::option::Option::None;
         self.data = ::std::option::Option::None;
+        self.data = ::std::option::Option::None;
         self.special_fields.clear();
     }
 
@@ -1262,6 +1830,8 @@ pub mod data_point {
         ConsoleMessage(super::ConsoleMessage),
         // @@protoc_insertion_point(oneof_field:DataPoint.threshold)
         Threshold(super::Threshold),
+        // @@protoc_insertion_point(oneof_field:DataPoint.run_info)
+        RunInfo(super::RunInfo),
     }
 
     impl ::protobuf::Oneof for Data {
@@ -1281,8 +1851,8 @@ pub mod data_point {
     }
 }
 
-#[derive(PartialEq,Clone,Default,Debug)]
 // @@protoc_insertion_point(message:StreamedData)
+#[derive(PartialEq,Clone,Default,Debug)]
 pub struct StreamedData {
     // message fields
     // @@protoc_insertion_point(field:StreamedData.data_points)
@@ -1434,6 +2004,16 @@ impl ::protobuf::Enum for SinkType {
         }
     }
 
+    fn from_str(str: &str) -> ::std::option::Option<SinkType> {
+        match str {
+            "Counter" => ::std::option::Option::Some(SinkType::Counter),
+            "Gauge" => ::std::option::Option::Some(SinkType::Gauge),
+            "Trend" => ::std::option::Option::Some(SinkType::Trend),
+            "Rate" => ::std::option::Option::Some(SinkType::Rate),
+            _ => ::std::option::Option::None
+        }
+    }
+
     const VALUES: &'static [SinkType] = &[
         SinkType::Counter,
         SinkType::Gauge,
@@ -1467,129 +2047,199 @@ impl SinkType {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x0btypes.proto\x1a\x1fgoogle/protobuf/timestamp.proto\"\x8b\x01\n\x04\
+    \n\x0btypes.proto\x1a\x1fgoogle/protobuf/timestamp.proto\"\xa2\x03\n\x06\
+    Sketch\x12+\n\x11relative_accuracy\x18\x01\x20\x01(\x01R\x10relativeAccu\
+    racy\x12>\n\rpositive_bins\x18\x02\x20\x03(\x0b2\x19.Sketch.PositiveBins\
+    EntryR\x0cpositiveBins\x12>\n\rnegative_bins\x18\x03\x20\x03(\x0b2\x19.S\
+    ketch.NegativeBinsEntryR\x0cnegativeBins\x12\x1d\n\nzero_count\x18\x04\
+    \x20\x01(\x01R\tzeroCount\x12\x14\n\x05count\x18\x05\x20\x01(\x01R\x05co\
+    unt\x12\x10\n\x03sum\x18\x06\x20\x01(\x01R\x03sum\x12\x10\n\x03min\x18\
+    \x07\x20\x01(\x01R\x03min\x12\x10\n\x03max\x18\x08\x20\x01(\x01R\x03max\
+    \x1a?\n\x11PositiveBinsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\x11R\x03k\
+    ey\x12\x14\n\x05value\x18\x02\x20\x01(\x01R\x05value:\x028\x01\x1a?\n\
+    \x11NegativeBinsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\x11R\x03key\x12\
+    \x14\n\x05value\x18\x02\x20\x01(\x01R\x05value:\x028\x01\"\xac\x01\n\x04\
     Sink\x12\x1d\n\x04type\x18\x01\x20\x01(\x0e2\t.SinkTypeR\x04type\x12)\n\
-    \x06labels\x18\x02\x20\x03(\x0b2\x11.Sink.LabelsEntryR\x06labels\x1a9\n\
-    \x0bLabelsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\
-    \x05value\x18\x02\x20\x01(\x01R\x05value:\x028\x01\"\x8f\x01\n\x08Interv\
-    al\x12\x16\n\x06period\x18\x01\x20\x01(\x05R\x06period\x12*\n\x05sinks\
-    \x18\x02\x20\x03(\x0b2\x14.Interval.SinksEntryR\x05sinks\x1a?\n\nSinksEn\
-    try\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x1b\n\x05value\x18\
-    \x02\x20\x01(\x0b2\x05.SinkR\x05value:\x028\x01\"\xb0\x02\n\x0eConsoleMe\
-    ssage\x12\x18\n\x07message\x18\x01\x20\x01(\tR\x07message\x12\x14\n\x05l\
-    evel\x18\x02\x20\x01(\tR\x05level\x12A\n\x0efirst_occurred\x18\x03\x20\
-    \x01(\x0b2\x1a.google.protobuf.TimestampR\rfirstOccurred\x12?\n\rlast_oc\
-    curred\x18\x04\x20\x01(\x0b2\x1a.google.protobuf.TimestampR\x0clastOccur\
-    red\x120\n\x05count\x18\x05\x20\x03(\x0b2\x1a.ConsoleMessage.CountEntryR\
+    \x06labels\x18\x02\x20\x03(\x0b2\x11.Sink.LabelsEntryR\x06labels\x12\x1f\
+    \n\x06sketch\x18\x03\x20\x01(\x0b2\x07.SketchR\x06sketch\x1a9\n\x0bLabel\
+    sEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\
+    \x18\x02\x20\x01(\x01R\x05value:\x028\x01\"\x8f\x01\n\x08Interval\x12\
+    \x16\n\x06period\x18\x01\x20\x01(\x05R\x06period\x12*\n\x05sinks\x18\x02\
+    \x20\x03(\x0b2\x14.Interval.SinksEntryR\x05sinks\x1a?\n\nSinksEntry\x12\
+    \x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x1b\n\x05value\x18\x02\x20\
+    \x01(\x0b2\x05.SinkR\x05value:\x028\x01\"\xb0\x02\n\x0eConsoleMessage\
+    \x12\x18\n\x07message\x18\x01\x20\x01(\tR\x07message\x12\x14\n\x05level\
+    \x18\x02\x20\x01(\tR\x05level\x12A\n\x0efirst_occurred\x18\x03\x20\x01(\
+    \x0b2\x1a.google.protobuf.TimestampR\rfirstOccurred\x12?\n\rlast_occurre\
+    d\x18\x04\x20\x01(\x0b2\x1a.google.protobuf.TimestampR\x0clastOccurred\
+    \x120\n\x05count\x18\x05\x20\x03(\x0b2\x1a.ConsoleMessage.CountEntryR\
     \x05count\x1a8\n\nCountEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\
     \x12\x14\n\x05value\x18\x02\x20\x01(\x05R\x05value:\x028\x01\"\xba\x01\n\
     \tThreshold\x12\x16\n\x06source\x18\x01\x20\x01(\tR\x06source\x12\x16\n\
     \x06metric\x18\x02\x20\x01(\tR\x06metric\x12'\n\rabort_on_fail\x18\x03\
     \x20\x01(\x08H\0R\x0babortOnFail\x88\x01\x01\x12-\n\x10delay_abort_eval\
     \x18\x04\x20\x01(\tH\x01R\x0edelayAbortEval\x88\x01\x01B\x10\n\x0e_abort\
-    _on_failB\x13\n\x11_delay_abort_eval\"\xdc\x01\n\x08TestInfo\x12'\n\tint\
+    _on_failB\x13\n\x11_delay_abort_eval\"\xe0\x02\n\x08TestInfo\x12'\n\tint\
     ervals\x18\x01\x20\x03(\x0b2\t.IntervalR\tintervals\x12#\n\x07summary\
     \x18\x02\x20\x01(\x0b2\t.IntervalR\x07summary\x12:\n\x10console_messages\
     \x18\x03\x20\x03(\x0b2\x0f.ConsoleMessageR\x0fconsoleMessages\x12*\n\nth\
     resholds\x18\x04\x20\x03(\x0b2\n.ThresholdR\nthresholds\x12\x1a\n\x08mes\
-    sages\x18\x05\x20\x03(\tR\x08messages\"\xa4\x01\n\tDataPoint\x12'\n\x08i\
-    nterval\x18\x01\x20\x01(\x0b2\t.IntervalH\0R\x08interval\x12:\n\x0fconso\
-    le_message\x18\x02\x20\x01(\x0b2\x0f.ConsoleMessageH\0R\x0econsoleMessag\
-    e\x12*\n\tthreshold\x18\x03\x20\x01(\x0b2\n.ThresholdH\0R\tthresholdB\
-    \x06\n\x04data\";\n\x0cStreamedData\x12+\n\x0bdata_points\x18\x01\x20\
-    \x03(\x0b2\n.DataPointR\ndataPoints*7\n\x08SinkType\x12\x0b\n\x07Counter\
-    \x10\0\x12\t\n\x05Gauge\x10\x01\x12\t\n\x05Trend\x10\x02\x12\x08\n\x04Ra\
-    te\x10\x03J\xca\x10\n\x06\x12\x04\x03\0>\x01\n\xae\x01\n\x01\x0c\x12\x03\
-    \x03\0\x122\xa3\x01\x20To\x20generate\x20rust\x20code\x20from\x20proto\
-    \x20file\x20using\x20protoc,\x20run\x20the\x20following\x20command:\n\
-    \x20protoc\x20--rust_out=.\x20--experimental_allow_proto3_optional\x20ty\
-    pes.proto\x20&&\x20rm\x20mod.rs\n\n\t\n\x02\x03\0\x12\x03\x05\0)\n\n\n\
-    \x02\x05\0\x12\x04\x07\0\x0c\x01\n\n\n\x03\x05\0\x01\x12\x03\x07\x05\r\n\
-    \x0b\n\x04\x05\0\x02\0\x12\x03\x08\x04\x10\n\x0c\n\x05\x05\0\x02\0\x01\
-    \x12\x03\x08\x04\x0b\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x08\x0e\x0f\n\
-    \x0b\n\x04\x05\0\x02\x01\x12\x03\t\x04\x0e\n\x0c\n\x05\x05\0\x02\x01\x01\
-    \x12\x03\t\x04\t\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\t\x0c\r\n\x0b\n\
-    \x04\x05\0\x02\x02\x12\x03\n\x04\x0e\n\x0c\n\x05\x05\0\x02\x02\x01\x12\
-    \x03\n\x04\t\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\n\x0c\r\n\x0b\n\x04\
-    \x05\0\x02\x03\x12\x03\x0b\x04\r\n\x0c\n\x05\x05\0\x02\x03\x01\x12\x03\
-    \x0b\x04\x08\n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03\x0b\x0b\x0c\n\n\n\x02\
-    \x04\0\x12\x04\x0e\0\x11\x01\n\n\n\x03\x04\0\x01\x12\x03\x0e\x08\x0c\n\
-    \x0b\n\x04\x04\0\x02\0\x12\x03\x0f\x04\x16\n\x0c\n\x05\x04\0\x02\0\x06\
-    \x12\x03\x0f\x04\x0c\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x0f\r\x11\n\x0c\
-    \n\x05\x04\0\x02\0\x03\x12\x03\x0f\x14\x15\n\x0b\n\x04\x04\0\x02\x01\x12\
-    \x03\x10\x04#\n\x0c\n\x05\x04\0\x02\x01\x06\x12\x03\x10\x04\x17\n\x0c\n\
-    \x05\x04\0\x02\x01\x01\x12\x03\x10\x18\x1e\n\x0c\n\x05\x04\0\x02\x01\x03\
-    \x12\x03\x10!\"\n\n\n\x02\x04\x01\x12\x04\x13\0\x18\x01\n\n\n\x03\x04\
-    \x01\x01\x12\x03\x13\x08\x10\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x14\x04\
-    \x15\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x14\x04\t\n\x0c\n\x05\x04\x01\
-    \x02\0\x01\x12\x03\x14\n\x10\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x14\
-    \x13\x14\nE\n\x04\x04\x01\x02\x01\x12\x03\x17\x04\x20\x1a8\x20map<metric\
-    \x20name,\x20value>\x20location\x20name\x20is\x20included\x20here\n\n\
-    \x0c\n\x05\x04\x01\x02\x01\x06\x12\x03\x17\x04\x15\n\x0c\n\x05\x04\x01\
-    \x02\x01\x01\x12\x03\x17\x16\x1b\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\
-    \x17\x1e\x1f\n\n\n\x02\x04\x02\x12\x04\x1a\0!\x01\n\n\n\x03\x04\x02\x01\
-    \x12\x03\x1a\x08\x16\n\x0b\n\x04\x04\x02\x02\0\x12\x03\x1b\x04\x17\n\x0c\
-    \n\x05\x04\x02\x02\0\x05\x12\x03\x1b\x04\n\n\x0c\n\x05\x04\x02\x02\0\x01\
-    \x12\x03\x1b\x0b\x12\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x1b\x15\x16\n\
-    \x0b\n\x04\x04\x02\x02\x01\x12\x03\x1c\x04\x15\n\x0c\n\x05\x04\x02\x02\
-    \x01\x05\x12\x03\x1c\x04\n\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x1c\
-    \x0b\x10\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\x1c\x13\x14\n\x0b\n\x04\
-    \x04\x02\x02\x02\x12\x03\x1d\x041\n\x0c\n\x05\x04\x02\x02\x02\x06\x12\
-    \x03\x1d\x04\x1d\n\x0c\n\x05\x04\x02\x02\x02\x01\x12\x03\x1d\x1e,\n\x0c\
-    \n\x05\x04\x02\x02\x02\x03\x12\x03\x1d/0\n\x0b\n\x04\x04\x02\x02\x03\x12\
-    \x03\x1e\x040\n\x0c\n\x05\x04\x02\x02\x03\x06\x12\x03\x1e\x04\x1d\n\x0c\
-    \n\x05\x04\x02\x02\x03\x01\x12\x03\x1e\x1e+\n\x0c\n\x05\x04\x02\x02\x03\
-    \x03\x12\x03\x1e./\n,\n\x04\x04\x02\x02\x04\x12\x03\x20\x04!\x1a\x1f\x20\
-    map<location,\x20times\x20occurred>\n\n\x0c\n\x05\x04\x02\x02\x04\x06\
-    \x12\x03\x20\x04\x16\n\x0c\n\x05\x04\x02\x02\x04\x01\x12\x03\x20\x17\x1c\
-    \n\x0c\n\x05\x04\x02\x02\x04\x03\x12\x03\x20\x1f\x20\n\n\n\x02\x04\x03\
-    \x12\x04#\0(\x01\n\n\n\x03\x04\x03\x01\x12\x03#\x08\x11\n\x0b\n\x04\x04\
-    \x03\x02\0\x12\x03$\x04\x16\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03$\x04\n\
-    \n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03$\x0b\x11\n\x0c\n\x05\x04\x03\x02\
-    \0\x03\x12\x03$\x14\x15\n\x0b\n\x04\x04\x03\x02\x01\x12\x03%\x04\x16\n\
-    \x0c\n\x05\x04\x03\x02\x01\x05\x12\x03%\x04\n\n\x0c\n\x05\x04\x03\x02\
-    \x01\x01\x12\x03%\x0b\x11\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x03%\x14\
-    \x15\n\x0b\n\x04\x04\x03\x02\x02\x12\x03&\x04$\n\x0c\n\x05\x04\x03\x02\
-    \x02\x04\x12\x03&\x04\x0c\n\x0c\n\x05\x04\x03\x02\x02\x05\x12\x03&\r\x11\
-    \n\x0c\n\x05\x04\x03\x02\x02\x01\x12\x03&\x12\x1f\n\x0c\n\x05\x04\x03\
-    \x02\x02\x03\x12\x03&\"#\n\x0b\n\x04\x04\x03\x02\x03\x12\x03'\x04)\n\x0c\
-    \n\x05\x04\x03\x02\x03\x04\x12\x03'\x04\x0c\n\x0c\n\x05\x04\x03\x02\x03\
-    \x05\x12\x03'\r\x13\n\x0c\n\x05\x04\x03\x02\x03\x01\x12\x03'\x14$\n\x0c\
-    \n\x05\x04\x03\x02\x03\x03\x12\x03''(\nV\n\x02\x04\x04\x12\x04+\02\x01\
-    \x1aJ\x20TestInfo\x20is\x20the\x20root\x20data\x20structure\x20for\x20th\
-    e\x20data\x20stored\x20in\x20the\x20database.\n\n\n\n\x03\x04\x04\x01\
-    \x12\x03+\x08\x10\n\x0b\n\x04\x04\x04\x02\0\x12\x03,\x04$\n\x0c\n\x05\
-    \x04\x04\x02\0\x04\x12\x03,\x04\x0c\n\x0c\n\x05\x04\x04\x02\0\x06\x12\
-    \x03,\r\x15\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03,\x16\x1f\n\x0c\n\x05\
-    \x04\x04\x02\0\x03\x12\x03,\"#\n\x0b\n\x04\x04\x04\x02\x01\x12\x03-\x04\
-    \x19\n\x0c\n\x05\x04\x04\x02\x01\x06\x12\x03-\x04\x0c\n\x0c\n\x05\x04\
-    \x04\x02\x01\x01\x12\x03-\r\x14\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03-\
-    \x17\x18\n\x0b\n\x04\x04\x04\x02\x02\x12\x03.\x041\n\x0c\n\x05\x04\x04\
-    \x02\x02\x04\x12\x03.\x04\x0c\n\x0c\n\x05\x04\x04\x02\x02\x06\x12\x03.\r\
-    \x1b\n\x0c\n\x05\x04\x04\x02\x02\x01\x12\x03.\x1c,\n\x0c\n\x05\x04\x04\
-    \x02\x02\x03\x12\x03./0\n\x0b\n\x04\x04\x04\x02\x03\x12\x03/\x04&\n\x0c\
-    \n\x05\x04\x04\x02\x03\x04\x12\x03/\x04\x0c\n\x0c\n\x05\x04\x04\x02\x03\
-    \x06\x12\x03/\r\x16\n\x0c\n\x05\x04\x04\x02\x03\x01\x12\x03/\x17!\n\x0c\
-    \n\x05\x04\x04\x02\x03\x03\x12\x03/$%\nN\n\x04\x04\x04\x02\x04\x12\x031\
-    \x04!\x1aA\x20Things\x20such\x20as\x20limit\x20alerts,\x20etc.\x20that\
-    \x20are\x20not\x20console\x20messages\n\n\x0c\n\x05\x04\x04\x02\x04\x04\
-    \x12\x031\x04\x0c\n\x0c\n\x05\x04\x04\x02\x04\x05\x12\x031\r\x13\n\x0c\n\
-    \x05\x04\x04\x02\x04\x01\x12\x031\x14\x1c\n\x0c\n\x05\x04\x04\x02\x04\
-    \x03\x12\x031\x1f\x20\n\n\n\x02\x04\x05\x12\x044\0:\x01\n\n\n\x03\x04\
-    \x05\x01\x12\x034\x08\x11\n\x0c\n\x04\x04\x05\x08\0\x12\x045\x049\x05\n\
-    \x0c\n\x05\x04\x05\x08\0\x01\x12\x035\n\x0e\n\x0b\n\x04\x04\x05\x02\0\
-    \x12\x036\x08\x1e\n\x0c\n\x05\x04\x05\x02\0\x06\x12\x036\x08\x10\n\x0c\n\
-    \x05\x04\x05\x02\0\x01\x12\x036\x11\x19\n\x0c\n\x05\x04\x05\x02\0\x03\
-    \x12\x036\x1c\x1d\n\x0b\n\x04\x04\x05\x02\x01\x12\x037\x08+\n\x0c\n\x05\
-    \x04\x05\x02\x01\x06\x12\x037\x08\x16\n\x0c\n\x05\x04\x05\x02\x01\x01\
-    \x12\x037\x17&\n\x0c\n\x05\x04\x05\x02\x01\x03\x12\x037)*\n\x0b\n\x04\
-    \x04\x05\x02\x02\x12\x038\x08\x20\n\x0c\n\x05\x04\x05\x02\x02\x06\x12\
-    \x038\x08\x11\n\x0c\n\x05\x04\x05\x02\x02\x01\x12\x038\x12\x1b\n\x0c\n\
-    \x05\x04\x05\x02\x02\x03\x12\x038\x1e\x1f\n\n\n\x02\x04\x06\x12\x04<\0>\
-    \x01\n\n\n\x03\x04\x06\x01\x12\x03<\x08\x14\n\x0b\n\x04\x04\x06\x02\0\
-    \x12\x03=\x04'\n\x0c\n\x05\x04\x06\x02\0\x04\x12\x03=\x04\x0c\n\x0c\n\
-    \x05\x04\x06\x02\0\x06\x12\x03=\r\x16\n\x0c\n\x05\x04\x06\x02\0\x01\x12\
-    \x03=\x17\"\n\x0c\n\x05\x04\x06\x02\0\x03\x12\x03=%&b\x06proto3\
+    sages\x18\x05\x20\x03(\tR\x08messages\x12\x1c\n\tlocations\x18\x06\x20\
+    \x03(\tR\tlocations\x129\n\nstart_time\x18\x07\x20\x01(\x0b2\x1a.google.\
+    protobuf.TimestampR\tstartTime\x12)\n\x10interval_seconds\x18\x08\x20\
+    \x01(\rR\x0fintervalSeconds\"o\n\x07RunInfo\x129\n\nstart_time\x18\x01\
+    \x20\x01(\x0b2\x1a.google.protobuf.TimestampR\tstartTime\x12)\n\x10inter\
+    val_seconds\x18\x02\x20\x01(\rR\x0fintervalSeconds\"\xcb\x01\n\tDataPoin\
+    t\x12'\n\x08interval\x18\x01\x20\x01(\x0b2\t.IntervalH\0R\x08interval\
+    \x12:\n\x0fconsole_message\x18\x02\x20\x01(\x0b2\x0f.ConsoleMessageH\0R\
+    \x0econsoleMessage\x12*\n\tthreshold\x18\x03\x20\x01(\x0b2\n.ThresholdH\
+    \0R\tthreshold\x12%\n\x08run_info\x18\x04\x20\x01(\x0b2\x08.RunInfoH\0R\
+    \x07runInfoB\x06\n\x04data\";\n\x0cStreamedData\x12+\n\x0bdata_points\
+    \x18\x01\x20\x03(\x0b2\n.DataPointR\ndataPoints*7\n\x08SinkType\x12\x0b\
+    \n\x07Counter\x10\0\x12\t\n\x05Gauge\x10\x01\x12\t\n\x05Trend\x10\x02\
+    \x12\x08\n\x04Rate\x10\x03J\x8c\x1b\n\x06\x12\x04\x03\0[\x01\n\xae\x01\n\
+    \x01\x0c\x12\x03\x03\0\x122\xa3\x01\x20To\x20generate\x20rust\x20code\
+    \x20from\x20proto\x20file\x20using\x20protoc,\x20run\x20the\x20following\
+    \x20command:\n\x20protoc\x20--rust_out=.\x20--experimental_allow_proto3_\
+    optional\x20types.proto\x20&&\x20rm\x20mod.rs\n\n\t\n\x02\x03\0\x12\x03\
+    \x05\0)\n\n\n\x02\x05\0\x12\x04\x07\0\x0c\x01\n\n\n\x03\x05\0\x01\x12\
+    \x03\x07\x05\r\n\x0b\n\x04\x05\0\x02\0\x12\x03\x08\x04\x10\n\x0c\n\x05\
+    \x05\0\x02\0\x01\x12\x03\x08\x04\x0b\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\
+    \x08\x0e\x0f\n\x0b\n\x04\x05\0\x02\x01\x12\x03\t\x04\x0e\n\x0c\n\x05\x05\
+    \0\x02\x01\x01\x12\x03\t\x04\t\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\t\
+    \x0c\r\n\x0b\n\x04\x05\0\x02\x02\x12\x03\n\x04\x0e\n\x0c\n\x05\x05\0\x02\
+    \x02\x01\x12\x03\n\x04\t\n\x0c\n\x05\x05\0\x02\x02\x02\x12\x03\n\x0c\r\n\
+    \x0b\n\x04\x05\0\x02\x03\x12\x03\x0b\x04\r\n\x0c\n\x05\x05\0\x02\x03\x01\
+    \x12\x03\x0b\x04\x08\n\x0c\n\x05\x05\0\x02\x03\x02\x12\x03\x0b\x0b\x0c\n\
+    \x98\x01\n\x02\x04\0\x12\x04\x10\0\x1a\x01\x1a\x8b\x01\x20DDSketch\x20of\
+    \x20the\x20samples\x20recorded\x20by\x20a\x20sink.\x20Unlike\x20precompu\
+    ted\x20percentile\n\x20labels,\x20sketches\x20can\x20be\x20merged\x20acr\
+    oss\x20intervals\x20and\x20locations.\n\n\n\n\x03\x04\0\x01\x12\x03\x10\
+    \x08\x0e\n\x0b\n\x04\x04\0\x02\0\x12\x03\x11\x04!\n\x0c\n\x05\x04\0\x02\
+    \0\x05\x12\x03\x11\x04\n\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x11\x0b\x1c\
+    \n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x11\x1f\x20\n0\n\x04\x04\0\x02\x01\
+    \x12\x03\x13\x04*\x1a#\x20map<bin\x20index,\x20number\x20of\x20samples>\
+    \n\n\x0c\n\x05\x04\0\x02\x01\x06\x12\x03\x13\x04\x17\n\x0c\n\x05\x04\0\
+    \x02\x01\x01\x12\x03\x13\x18%\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x13(\
+    )\n\x0b\n\x04\x04\0\x02\x02\x12\x03\x14\x04*\n\x0c\n\x05\x04\0\x02\x02\
+    \x06\x12\x03\x14\x04\x17\n\x0c\n\x05\x04\0\x02\x02\x01\x12\x03\x14\x18%\
+    \n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x14()\n\x0b\n\x04\x04\0\x02\x03\
+    \x12\x03\x15\x04\x1a\n\x0c\n\x05\x04\0\x02\x03\x05\x12\x03\x15\x04\n\n\
+    \x0c\n\x05\x04\0\x02\x03\x01\x12\x03\x15\x0b\x15\n\x0c\n\x05\x04\0\x02\
+    \x03\x03\x12\x03\x15\x18\x19\n\x0b\n\x04\x04\0\x02\x04\x12\x03\x16\x04\
+    \x15\n\x0c\n\x05\x04\0\x02\x04\x05\x12\x03\x16\x04\n\n\x0c\n\x05\x04\0\
+    \x02\x04\x01\x12\x03\x16\x0b\x10\n\x0c\n\x05\x04\0\x02\x04\x03\x12\x03\
+    \x16\x13\x14\n\x0b\n\x04\x04\0\x02\x05\x12\x03\x17\x04\x13\n\x0c\n\x05\
+    \x04\0\x02\x05\x05\x12\x03\x17\x04\n\n\x0c\n\x05\x04\0\x02\x05\x01\x12\
+    \x03\x17\x0b\x0e\n\x0c\n\x05\x04\0\x02\x05\x03\x12\x03\x17\x11\x12\n\x0b\
+    \n\x04\x04\0\x02\x06\x12\x03\x18\x04\x13\n\x0c\n\x05\x04\0\x02\x06\x05\
+    \x12\x03\x18\x04\n\n\x0c\n\x05\x04\0\x02\x06\x01\x12\x03\x18\x0b\x0e\n\
+    \x0c\n\x05\x04\0\x02\x06\x03\x12\x03\x18\x11\x12\n\x0b\n\x04\x04\0\x02\
+    \x07\x12\x03\x19\x04\x13\n\x0c\n\x05\x04\0\x02\x07\x05\x12\x03\x19\x04\n\
+    \n\x0c\n\x05\x04\0\x02\x07\x01\x12\x03\x19\x0b\x0e\n\x0c\n\x05\x04\0\x02\
+    \x07\x03\x12\x03\x19\x11\x12\n\n\n\x02\x04\x01\x12\x04\x1c\0!\x01\n\n\n\
+    \x03\x04\x01\x01\x12\x03\x1c\x08\x0c\n\x0b\n\x04\x04\x01\x02\0\x12\x03\
+    \x1d\x04\x16\n\x0c\n\x05\x04\x01\x02\0\x06\x12\x03\x1d\x04\x0c\n\x0c\n\
+    \x05\x04\x01\x02\0\x01\x12\x03\x1d\r\x11\n\x0c\n\x05\x04\x01\x02\0\x03\
+    \x12\x03\x1d\x14\x15\n\x0b\n\x04\x04\x01\x02\x01\x12\x03\x1e\x04#\n\x0c\
+    \n\x05\x04\x01\x02\x01\x06\x12\x03\x1e\x04\x17\n\x0c\n\x05\x04\x01\x02\
+    \x01\x01\x12\x03\x1e\x18\x1e\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x1e\
+    !\"\nB\n\x04\x04\x01\x02\x02\x12\x03\x20\x04\x16\x1a5\x20Only\x20set\x20\
+    for\x20Trend\x20sinks\x20by\x20senders\x20that\x20support\x20it\n\n\x0c\
+    \n\x05\x04\x01\x02\x02\x06\x12\x03\x20\x04\n\n\x0c\n\x05\x04\x01\x02\x02\
+    \x01\x12\x03\x20\x0b\x11\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03\x20\x14\
+    \x15\n\n\n\x02\x04\x02\x12\x04#\0(\x01\n\n\n\x03\x04\x02\x01\x12\x03#\
+    \x08\x10\n\x0b\n\x04\x04\x02\x02\0\x12\x03$\x04\x15\n\x0c\n\x05\x04\x02\
+    \x02\0\x05\x12\x03$\x04\t\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03$\n\x10\n\
+    \x0c\n\x05\x04\x02\x02\0\x03\x12\x03$\x13\x14\nE\n\x04\x04\x02\x02\x01\
+    \x12\x03'\x04\x20\x1a8\x20map<metric\x20name,\x20value>\x20location\x20n\
+    ame\x20is\x20included\x20here\n\n\x0c\n\x05\x04\x02\x02\x01\x06\x12\x03'\
+    \x04\x15\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03'\x16\x1b\n\x0c\n\x05\
+    \x04\x02\x02\x01\x03\x12\x03'\x1e\x1f\n\n\n\x02\x04\x03\x12\x04*\01\x01\
+    \n\n\n\x03\x04\x03\x01\x12\x03*\x08\x16\n\x0b\n\x04\x04\x03\x02\0\x12\
+    \x03+\x04\x17\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03+\x04\n\n\x0c\n\x05\
+    \x04\x03\x02\0\x01\x12\x03+\x0b\x12\n\x0c\n\x05\x04\x03\x02\0\x03\x12\
+    \x03+\x15\x16\n\x0b\n\x04\x04\x03\x02\x01\x12\x03,\x04\x15\n\x0c\n\x05\
+    \x04\x03\x02\x01\x05\x12\x03,\x04\n\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\
+    \x03,\x0b\x10\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x03,\x13\x14\n\x0b\n\
+    \x04\x04\x03\x02\x02\x12\x03-\x041\n\x0c\n\x05\x04\x03\x02\x02\x06\x12\
+    \x03-\x04\x1d\n\x0c\n\x05\x04\x03\x02\x02\x01\x12\x03-\x1e,\n\x0c\n\x05\
+    \x04\x03\x02\x02\x03\x12\x03-/0\n\x0b\n\x04\x04\x03\x02\x03\x12\x03.\x04\
+    0\n\x0c\n\x05\x04\x03\x02\x03\x06\x12\x03.\x04\x1d\n\x0c\n\x05\x04\x03\
+    \x02\x03\x01\x12\x03.\x1e+\n\x0c\n\x05\x04\x03\x02\x03\x03\x12\x03../\n,\
+    \n\x04\x04\x03\x02\x04\x12\x030\x04!\x1a\x1f\x20map<location,\x20times\
+    \x20occurred>\n\n\x0c\n\x05\x04\x03\x02\x04\x06\x12\x030\x04\x16\n\x0c\n\
+    \x05\x04\x03\x02\x04\x01\x12\x030\x17\x1c\n\x0c\n\x05\x04\x03\x02\x04\
+    \x03\x12\x030\x1f\x20\n\n\n\x02\x04\x04\x12\x043\08\x01\n\n\n\x03\x04\
+    \x04\x01\x12\x033\x08\x11\n\x0b\n\x04\x04\x04\x02\0\x12\x034\x04\x16\n\
+    \x0c\n\x05\x04\x04\x02\0\x05\x12\x034\x04\n\n\x0c\n\x05\x04\x04\x02\0\
+    \x01\x12\x034\x0b\x11\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x034\x14\x15\n\
+    \x0b\n\x04\x04\x04\x02\x01\x12\x035\x04\x16\n\x0c\n\x05\x04\x04\x02\x01\
+    \x05\x12\x035\x04\n\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x035\x0b\x11\n\
+    \x0c\n\x05\x04\x04\x02\x01\x03\x12\x035\x14\x15\n\x0b\n\x04\x04\x04\x02\
+    \x02\x12\x036\x04$\n\x0c\n\x05\x04\x04\x02\x02\x04\x12\x036\x04\x0c\n\
+    \x0c\n\x05\x04\x04\x02\x02\x05\x12\x036\r\x11\n\x0c\n\x05\x04\x04\x02\
+    \x02\x01\x12\x036\x12\x1f\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\x036\"#\n\
+    \x0b\n\x04\x04\x04\x02\x03\x12\x037\x04)\n\x0c\n\x05\x04\x04\x02\x03\x04\
+    \x12\x037\x04\x0c\n\x0c\n\x05\x04\x04\x02\x03\x05\x12\x037\r\x13\n\x0c\n\
+    \x05\x04\x04\x02\x03\x01\x12\x037\x14$\n\x0c\n\x05\x04\x04\x02\x03\x03\
+    \x12\x037'(\nV\n\x02\x04\x05\x12\x04;\0H\x01\x1aJ\x20TestInfo\x20is\x20t\
+    he\x20root\x20data\x20structure\x20for\x20the\x20data\x20stored\x20in\
+    \x20the\x20database.\n\n\n\n\x03\x04\x05\x01\x12\x03;\x08\x10\n\x0b\n\
+    \x04\x04\x05\x02\0\x12\x03<\x04$\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x03<\
+    \x04\x0c\n\x0c\n\x05\x04\x05\x02\0\x06\x12\x03<\r\x15\n\x0c\n\x05\x04\
+    \x05\x02\0\x01\x12\x03<\x16\x1f\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03<\"\
+    #\n\x0b\n\x04\x04\x05\x02\x01\x12\x03=\x04\x19\n\x0c\n\x05\x04\x05\x02\
+    \x01\x06\x12\x03=\x04\x0c\n\x0c\n\x05\x04\x05\x02\x01\x01\x12\x03=\r\x14\
+    \n\x0c\n\x05\x04\x05\x02\x01\x03\x12\x03=\x17\x18\n\x0b\n\x04\x04\x05\
+    \x02\x02\x12\x03>\x041\n\x0c\n\x05\x04\x05\x02\x02\x04\x12\x03>\x04\x0c\
+    \n\x0c\n\x05\x04\x05\x02\x02\x06\x12\x03>\r\x1b\n\x0c\n\x05\x04\x05\x02\
+    \x02\x01\x12\x03>\x1c,\n\x0c\n\x05\x04\x05\x02\x02\x03\x12\x03>/0\n\x0b\
+    \n\x04\x04\x05\x02\x03\x12\x03?\x04&\n\x0c\n\x05\x04\x05\x02\x03\x04\x12\
+    \x03?\x04\x0c\n\x0c\n\x05\x04\x05\x02\x03\x06\x12\x03?\r\x16\n\x0c\n\x05\
+    \x04\x05\x02\x03\x01\x12\x03?\x17!\n\x0c\n\x05\x04\x05\x02\x03\x03\x12\
+    \x03?$%\nN\n\x04\x04\x05\x02\x04\x12\x03A\x04!\x1aA\x20Things\x20such\
+    \x20as\x20limit\x20alerts,\x20etc.\x20that\x20are\x20not\x20console\x20m\
+    essages\n\n\x0c\n\x05\x04\x05\x02\x04\x04\x12\x03A\x04\x0c\n\x0c\n\x05\
+    \x04\x05\x02\x04\x05\x12\x03A\r\x13\n\x0c\n\x05\x04\x05\x02\x04\x01\x12\
+    \x03A\x14\x1c\n\x0c\n\x05\x04\x05\x02\x04\x03\x12\x03A\x1f\x20\n\x0b\n\
+    \x04\x04\x05\x02\x05\x12\x03B\x04\"\n\x0c\n\x05\x04\x05\x02\x05\x04\x12\
+    \x03B\x04\x0c\n\x0c\n\x05\x04\x05\x02\x05\x05\x12\x03B\r\x13\n\x0c\n\x05\
+    \x04\x05\x02\x05\x01\x12\x03B\x14\x1d\n\x0c\n\x05\x04\x05\x02\x05\x03\
+    \x12\x03B\x20!\nJ\n\x04\x04\x05\x02\x06\x12\x03D\x04-\x1a=\x20When\x20pe\
+    riod\x200\x20started,\x20unset\x20if\x20the\x20sender\x20didn't\x20recor\
+    d\x20it\n\n\x0c\n\x05\x04\x05\x02\x06\x06\x12\x03D\x04\x1d\n\x0c\n\x05\
+    \x04\x05\x02\x06\x01\x12\x03D\x1e(\n\x0c\n\x05\x04\x05\x02\x06\x03\x12\
+    \x03D+,\na\n\x04\x04\x05\x02\x07\x12\x03G\x04\x20\x1aT\x20Width\x20of\
+    \x20each\x20period,\x200\x20for\x20runs\x20recorded\x20before\x20it\x20w\
+    as\x20sent\x20which\x20used\n\x206\x20seconds\n\n\x0c\n\x05\x04\x05\x02\
+    \x07\x05\x12\x03G\x04\n\n\x0c\n\x05\x04\x05\x02\x07\x01\x12\x03G\x0b\x1b\
+    \n\x0c\n\x05\x04\x05\x02\x07\x03\x12\x03G\x1e\x1f\nU\n\x02\x04\x06\x12\
+    \x04K\0N\x01\x1aI\x20Sent\x20at\x20the\x20start\x20of\x20a\x20run\x20so\
+    \x20that\x20periods\x20can\x20be\x20given\x20real\x20timestamps\n\n\n\n\
+    \x03\x04\x06\x01\x12\x03K\x08\x0f\n\x0b\n\x04\x04\x06\x02\0\x12\x03L\x04\
+    -\n\x0c\n\x05\x04\x06\x02\0\x06\x12\x03L\x04\x1d\n\x0c\n\x05\x04\x06\x02\
+    \0\x01\x12\x03L\x1e(\n\x0c\n\x05\x04\x06\x02\0\x03\x12\x03L+,\n\x0b\n\
+    \x04\x04\x06\x02\x01\x12\x03M\x04\x20\n\x0c\n\x05\x04\x06\x02\x01\x05\
+    \x12\x03M\x04\n\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\x03M\x0b\x1b\n\x0c\n\
+    \x05\x04\x06\x02\x01\x03\x12\x03M\x1e\x1f\n\n\n\x02\x04\x07\x12\x04P\0W\
+    \x01\n\n\n\x03\x04\x07\x01\x12\x03P\x08\x11\n\x0c\n\x04\x04\x07\x08\0\
+    \x12\x04Q\x04V\x05\n\x0c\n\x05\x04\x07\x08\0\x01\x12\x03Q\n\x0e\n\x0b\n\
+    \x04\x04\x07\x02\0\x12\x03R\x08\x1e\n\x0c\n\x05\x04\x07\x02\0\x06\x12\
+    \x03R\x08\x10\n\x0c\n\x05\x04\x07\x02\0\x01\x12\x03R\x11\x19\n\x0c\n\x05\
+    \x04\x07\x02\0\x03\x12\x03R\x1c\x1d\n\x0b\n\x04\x04\x07\x02\x01\x12\x03S\
+    \x08+\n\x0c\n\x05\x04\x07\x02\x01\x06\x12\x03S\x08\x16\n\x0c\n\x05\x04\
+    \x07\x02\x01\x01\x12\x03S\x17&\n\x0c\n\x05\x04\x07\x02\x01\x03\x12\x03S)\
+    *\n\x0b\n\x04\x04\x07\x02\x02\x12\x03T\x08\x20\n\x0c\n\x05\x04\x07\x02\
+    \x02\x06\x12\x03T\x08\x11\n\x0c\n\x05\x04\x07\x02\x02\x01\x12\x03T\x12\
+    \x1b\n\x0c\n\x05\x04\x07\x02\x02\x03\x12\x03T\x1e\x1f\n\x0b\n\x04\x04\
+    \x07\x02\x03\x12\x03U\x08\x1d\n\x0c\n\x05\x04\x07\x02\x03\x06\x12\x03U\
+    \x08\x0f\n\x0c\n\x05\x04\x07\x02\x03\x01\x12\x03U\x10\x18\n\x0c\n\x05\
+    \x04\x07\x02\x03\x03\x12\x03U\x1b\x1c\n\n\n\x02\x04\x08\x12\x04Y\0[\x01\
+    \n\n\n\x03\x04\x08\x01\x12\x03Y\x08\x14\n\x0b\n\x04\x04\x08\x02\0\x12\
+    \x03Z\x04'\n\x0c\n\x05\x04\x08\x02\0\x04\x12\x03Z\x04\x0c\n\x0c\n\x05\
+    \x04\x08\x02\0\x06\x12\x03Z\r\x16\n\x0c\n\x05\x04\x08\x02\0\x01\x12\x03Z\
+    \x17\"\n\x0c\n\x05\x04\x08\x02\0\x03\x12\x03Z%&b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -1608,12 +2258,14 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
             let mut deps = ::std::vec::Vec::with_capacity(1);
             deps.push(::protobuf::well_known_types::timestamp::file_descriptor().clone());
-            let mut messages = ::std::vec::Vec::with_capacity(7);
+            let mut messages = ::std::vec::Vec::with_capacity(9);
+            messages.push(Sketch::generated_message_descriptor_data());
             messages.push(Sink::generated_message_descriptor_data());
             messages.push(Interval::generated_message_descriptor_data());
             messages.push(ConsoleMessage::generated_message_descriptor_data());
             messages.push(Threshold::generated_message_descriptor_data());
             messages.push(TestInfo::generated_message_descriptor_data());
+            messages.push(RunInfo::generated_message_descriptor_data());
             messages.push(DataPoint::generated_message_descriptor_data());
             messages.push(StreamedData::generated_message_descriptor_data());
             let mut enums = ::std::vec::Vec::with_capacity(1);
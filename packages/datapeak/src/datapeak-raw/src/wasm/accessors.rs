@@ -0,0 +1,66 @@
+use super::with_manager;
+use crate::types;
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen (js_name = rawGetThresholds)]
+pub fn get_thresholds(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        let thresholds = js_sys::Array::new();
+
+        for tr in manager.thresholds().iter() {
+            thresholds.push(&Result::from(tr)?);
+        }
+
+        Ok(JsValue::from(thresholds))
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetMessages)]
+pub fn get_messages(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        let messages = js_sys::Array::new();
+
+        for message in manager.messages().iter() {
+            messages.push(&JsValue::from_str(message.as_str()));
+        }
+
+        Ok(JsValue::from(messages))
+    })
+}
+
+impl From<&types::Threshold> for Result<JsValue, JsValue> {
+    fn from(tr: &types::Threshold) -> Self {
+        let obj = js_sys::Object::new();
+
+        Reflect::set(
+            &obj,
+            &JsValue::from_str("metric"),
+            &JsValue::from_str(tr.metric.as_str()),
+        )?;
+
+        Reflect::set(
+            &obj,
+            &JsValue::from_str("source"),
+            &JsValue::from_str(tr.source.as_str()),
+        )?;
+
+        if let Some(abort_on_fail) = &tr.abort_on_fail {
+            Reflect::set(
+                &obj,
+                &JsValue::from_str("abortOnFail"),
+                &JsValue::from_bool(*abort_on_fail),
+            )?;
+        }
+
+        if let Some(delay_abort_eval) = &tr.delay_abort_eval {
+            Reflect::set(
+                &obj,
+                &JsValue::from_str("delayAbortEval"),
+                &JsValue::from_str(delay_abort_eval),
+            )?;
+        }
+
+        Ok(obj.into())
+    }
+}
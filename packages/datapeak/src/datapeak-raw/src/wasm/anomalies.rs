@@ -0,0 +1,62 @@
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use super::{subscriptions, with_manager};
+use crate::{anomalies::AnomalyOptions, states::Topic};
+
+#[wasm_bindgen (js_name = rawGetAnomalies)]
+pub fn get_anomalies(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        serde_wasm_bindgen::to_value(manager.anomalies())
+            .map_err(|e| JsValue::from_str(e.to_string().as_str()))
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetAnomaliesState)]
+pub fn get_anomalies_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Anomalies) as f64)
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AnomalyDetectionOptions {
+    window_size: Option<usize>,
+    min_window_size: Option<usize>,
+    z_score_threshold: Option<f64>,
+}
+
+// Options not given keep their defaults
+#[wasm_bindgen (js_name = rawSetAnomalyOptions)]
+pub fn set_anomaly_options(test_info_id: &str, options: JsValue) -> Result<(), JsValue> {
+    let options: Option<AnomalyDetectionOptions> = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+
+    let mut anomaly_options = AnomalyOptions::default();
+
+    if let Some(options) = options {
+        if let Some(window_size) = options.window_size {
+            anomaly_options.window_size = window_size;
+        }
+        if let Some(min_window_size) = options.min_window_size {
+            anomaly_options.min_window_size = min_window_size;
+        }
+        if let Some(z_score_threshold) = options.z_score_threshold {
+            anomaly_options.z_score_threshold = z_score_threshold;
+        }
+    }
+
+    let changed = with_manager(test_info_id, |manager| {
+        let previous_version = manager.topic_version(Topic::Anomalies);
+        manager.set_anomaly_options(anomaly_options);
+
+        Ok(manager.topic_version(Topic::Anomalies) != previous_version)
+    })?;
+
+    if changed {
+        subscriptions::notify_subscribers(test_info_id, &[Topic::Anomalies])?;
+    }
+
+    Ok(())
+}
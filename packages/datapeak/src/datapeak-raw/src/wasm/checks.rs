@@ -0,0 +1,38 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsValue;
+
+use super::{parse_window, with_manager};
+use crate::{intervals::checks::CheckCollection, states::Topic};
+
+impl From<&CheckCollection> for JsValue {
+    fn from(cs: &CheckCollection) -> Self {
+        serde_wasm_bindgen::to_value(cs).unwrap()
+    }
+}
+
+#[wasm_bindgen (js_name = rawGetChecks)]
+pub fn get_checks(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(JsValue::from(manager.checks()))
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetWindowChecks)]
+pub fn get_window_checks(test_info_id: &str, window: JsValue) -> Result<JsValue, JsValue> {
+    let window = parse_window(window)?;
+
+    with_manager(test_info_id, |manager| {
+        let checks = manager
+            .window_checks(&window)
+            .map_err(|e| JsValue::from_str(e.as_str()))?;
+
+        Ok(JsValue::from(&checks))
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetChecksState)]
+pub fn get_checks_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Checks) as f64)
+    })
+}
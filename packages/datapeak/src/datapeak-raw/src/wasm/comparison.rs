@@ -0,0 +1,81 @@
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use super::with_manager_pair;
+use crate::comparison::{compare_bytes, Comparison, ComparisonOptions};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompareOptions {
+    p95_tolerance: Option<f64>,
+    error_rate_tolerance: Option<f64>,
+    significance_level: Option<f64>,
+}
+
+fn parse_options(options: JsValue) -> Result<ComparisonOptions, JsValue> {
+    let options: Option<CompareOptions> = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+
+    let mut comparison_options = ComparisonOptions::default();
+
+    if let Some(options) = options {
+        if let Some(p95_tolerance) = options.p95_tolerance {
+            comparison_options.p95_tolerance = p95_tolerance;
+        }
+        if let Some(error_rate_tolerance) = options.error_rate_tolerance {
+            comparison_options.error_rate_tolerance = error_rate_tolerance;
+        }
+        if let Some(significance_level) = options.significance_level {
+            comparison_options.significance_level = significance_level;
+        }
+    }
+
+    Ok(comparison_options)
+}
+
+fn to_js(comparison: &Comparison) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(comparison).map_err(|e| JsValue::from_str(e.to_string().as_str()))
+}
+
+#[wasm_bindgen (js_name = rawCompareTestData)]
+pub fn compare_test_data(
+    baseline_id: &str,
+    candidate_id: &str,
+    options: JsValue,
+) -> Result<JsValue, JsValue> {
+    let options = parse_options(options)?;
+
+    with_manager_pair(baseline_id, candidate_id, |baseline, candidate| {
+        to_js(&baseline.compare(candidate, &options))
+    })
+}
+
+#[wasm_bindgen (js_name = rawCompareTestDataBytes)]
+pub fn compare_test_data_bytes(
+    baseline: Vec<u8>,
+    candidate: Vec<u8>,
+    options: JsValue,
+) -> Result<JsValue, JsValue> {
+    let options = parse_options(options)?;
+
+    let comparison = compare_bytes(&baseline, &candidate, &options)
+        .map_err(|e| JsValue::from_str(e.as_str()))?;
+
+    to_js(&comparison)
+}
+
+#[wasm_bindgen (js_name = rawAlignedTimeSeries)]
+pub fn aligned_time_series(
+    baseline_id: &str,
+    candidate_id: &str,
+    sink_path: &str,
+) -> Result<JsValue, JsValue> {
+    with_manager_pair(baseline_id, candidate_id, |baseline, candidate| {
+        let time_series = baseline
+            .aligned_time_series(candidate, sink_path)
+            .map_err(|e| JsValue::from_str(e.as_str()))?;
+
+        serde_wasm_bindgen::to_value(&time_series)
+            .map_err(|e| JsValue::from_str(e.to_string().as_str()))
+    })
+}
@@ -0,0 +1,94 @@
+use super::with_manager;
+use crate::{states::Topic, types};
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen (js_name = rawGetConsoleMessages)]
+pub fn get_console_messages(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        let arr = js_sys::Array::new();
+        for cm in manager.console_messages().iter() {
+            arr.push(&Result::from(cm)?);
+        }
+
+        Ok(arr.into())
+    })
+}
+
+impl From<&types::ConsoleMessage> for Result<JsValue, JsValue> {
+    fn from(cm: &types::ConsoleMessage) -> Self {
+        let obj = js_sys::Object::new();
+
+        Reflect::set(
+            &obj,
+            &JsValue::from_str("message"),
+            &JsValue::from_str(cm.message.as_str()),
+        )?;
+        Reflect::set(
+            &obj,
+            &JsValue::from_str("level"),
+            &JsValue::from_str(cm.level.as_str()),
+        )?;
+
+        // Convert to timestamp
+        let first_occurred = chrono::DateTime::from_timestamp(
+            cm.first_occurred.seconds,
+            cm.first_occurred.nanos as u32,
+        )
+        .map(|x| x.naive_utc())
+        .ok_or_else(|| {
+            JsValue::from_str(
+                format!(
+                    "Failed to convert first_occurred to timestamp: {}",
+                    cm.first_occurred.seconds
+                )
+                .as_str(),
+            )
+        })?;
+        Reflect::set(
+            &obj,
+            &JsValue::from_str("firstOccurred"),
+            &JsValue::from_str(&first_occurred.to_string()),
+        )?;
+
+        let last_occurred = chrono::DateTime::from_timestamp(
+            cm.last_occurred.seconds,
+            cm.last_occurred.nanos as u32,
+        )
+        .map(|x| x.naive_utc())
+        .ok_or_else(|| {
+            JsValue::from_str(
+                format!(
+                    "Failed to convert last_occurred to timestamp: {}",
+                    cm.last_occurred.seconds
+                )
+                .as_str(),
+            )
+        })?;
+        Reflect::set(
+            &obj,
+            &JsValue::from_str("lastOccurred"),
+            &JsValue::from_str(&last_occurred.to_string()),
+        )?;
+
+        let count = js_sys::Object::new();
+        for (key, value) in cm.count.iter() {
+            Reflect::set(
+                &count,
+                &JsValue::from_str(key.as_str()),
+                &JsValue::from_f64(*value as f64),
+            )?;
+        }
+
+        Reflect::set(&obj, &JsValue::from_str("count"), &count)?;
+
+        Ok(obj.into())
+    }
+}
+
+#[wasm_bindgen (js_name = rawGetConsoleMessagesState)]
+pub fn get_console_messages_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::ConsoleMessages) as f64)
+    })
+}
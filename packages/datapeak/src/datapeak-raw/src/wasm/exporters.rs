@@ -0,0 +1,49 @@
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use super::{parse_time, with_manager};
+use crate::exporters::csv::CsvOptions;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CsvExportOptions {
+    columns: Option<Vec<String>>,
+    // Milliseconds since the unix epoch, as given by Date.getTime()
+    start_time: Option<f64>,
+}
+
+#[wasm_bindgen (js_name = rawExportCsv)]
+pub fn export_csv(test_info_id: &str, options: JsValue) -> Result<String, JsValue> {
+    let options: Option<CsvExportOptions> = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+
+    let options = match options {
+        Some(options) => CsvOptions {
+            columns: options.columns.unwrap_or_default(),
+            start_time: parse_time(options.start_time)?,
+            interval_seconds: None,
+        },
+        None => CsvOptions::default(),
+    };
+
+    with_manager(test_info_id, |manager| Ok(manager.export_csv(&options)))
+}
+
+#[wasm_bindgen (js_name = rawExportOpenMetrics)]
+pub fn export_openmetrics(test_info_id: &str) -> Result<String, JsValue> {
+    with_manager(test_info_id, |manager| Ok(manager.export_openmetrics()))
+}
+
+#[wasm_bindgen (js_name = rawExportInfluxLineProtocol)]
+pub fn export_influx_line_protocol(
+    test_info_id: &str,
+    start_time: Option<f64>,
+) -> Result<String, JsValue> {
+    let start_time = parse_time(start_time)?;
+
+    with_manager(test_info_id, |manager| {
+        manager
+            .export_influx(start_time)
+            .map_err(|e| JsValue::from_str(e.as_str()))
+    })
+}
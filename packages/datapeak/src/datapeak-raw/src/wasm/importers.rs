@@ -0,0 +1,49 @@
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use super::insert_manager;
+use crate::importers::{
+    jmeter::{import_jmeter_csv, JMeterImportOptions},
+    k6::{import_k6_json, K6ImportOptions},
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportOptions {
+    location: Option<String>,
+}
+
+fn parse_location(options: JsValue) -> Result<Option<String>, JsValue> {
+    let options: Option<ImportOptions> = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+
+    Ok(options.and_then(|x| x.location))
+}
+
+// Imports k6 `--out json` output as new test data, returning its id
+#[wasm_bindgen (js_name = rawImportK6Json)]
+pub fn import_k6_json_test_data(ndjson: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let mut import_options = K6ImportOptions::default();
+    if let Some(location) = parse_location(options)? {
+        import_options.location = location;
+    }
+
+    let manager = import_k6_json(ndjson.as_bytes(), &import_options)
+        .map_err(|e| JsValue::from_str(e.as_str()))?;
+
+    Ok(JsValue::from_str(insert_manager(manager).as_str()))
+}
+
+// Imports a JMeter .jtl CSV results file as new test data, returning its id
+#[wasm_bindgen (js_name = rawImportJMeterCsv)]
+pub fn import_jmeter_csv_test_data(csv: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let mut import_options = JMeterImportOptions::default();
+    if let Some(location) = parse_location(options)? {
+        import_options.location = location;
+    }
+
+    let manager = import_jmeter_csv(csv.as_bytes(), &import_options)
+        .map_err(|e| JsValue::from_str(e.as_str()))?;
+
+    Ok(JsValue::from_str(insert_manager(manager).as_str()))
+}
@@ -0,0 +1,44 @@
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+
+use super::with_manager;
+use crate::{states::Topic, types};
+
+#[wasm_bindgen (js_name = rawGetIntervalsState)]
+pub fn get_intervals_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Intervals) as f64)
+    })
+}
+
+impl From<types::Interval> for Result<JsValue, JsValue> {
+    fn from(interval: types::Interval) -> Self {
+        let obj = js_sys::Object::new();
+
+        Reflect::set(
+            &obj,
+            &JsValue::from_str("period"),
+            &JsValue::from_f64(interval.period as f64),
+        )?;
+
+        let sinks = js_sys::Object::new();
+        for (sink_key, sink_value) in interval.sinks.iter() {
+            let sink = js_sys::Object::new();
+
+            let labels = js_sys::Object::new();
+            for (label, label_value) in sink_value.labels.iter() {
+                Reflect::set(
+                    &labels,
+                    &JsValue::from_str(label.as_str()),
+                    &JsValue::from_f64(*label_value),
+                )?;
+            }
+            Reflect::set(&sink, &JsValue::from_str("labels"), &labels)?;
+
+            Reflect::set(&sinks, &JsValue::from_str(sink_key.as_str()), &sink)?;
+        }
+        Reflect::set(&obj, &JsValue::from_str("sinks"), &sinks)?;
+
+        Ok(JsValue::from(obj))
+    }
+}
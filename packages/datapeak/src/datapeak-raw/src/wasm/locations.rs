@@ -0,0 +1,37 @@
+use super::{subscriptions::notify_subscribers, with_manager};
+use crate::states::Topic;
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen (js_name = rawGetLocations)]
+pub fn get_locations(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        let locations = js_sys::Array::new();
+
+        for location in manager.locations().iter() {
+            locations.push(&JsValue::from_str(location.as_str()));
+        }
+
+        Ok(JsValue::from(locations))
+    })
+}
+
+#[wasm_bindgen (js_name = rawSetLocations)]
+pub fn set_locations(test_info_id: &str, locations: Array) -> Result<(), JsValue> {
+    // Extract vector of locations from JsValue
+    let locations: Vec<String> = locations
+        .iter()
+        .map(|x| {
+            x.as_string()
+                .ok_or_else(|| JsValue::from_str(format!("Invalid location: {:?}", x).as_str()))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let changed = with_manager(test_info_id, |manager| Ok(manager.set_locations(locations)))?;
+
+    if !changed {
+        return Ok(());
+    }
+
+    notify_subscribers(test_info_id, &[Topic::Locations])
+}
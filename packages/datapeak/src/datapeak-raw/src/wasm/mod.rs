@@ -0,0 +1,184 @@
+// Thin wasm-bindgen adapter over TestInfoManager. Managers are kept in a
+// global map keyed by a generated id, as JS can't hold onto Rust references.
+
+pub mod accessors;
+pub mod anomalies;
+pub mod checks;
+pub mod comparison;
+pub mod console_messages;
+pub mod exporters;
+pub mod importers;
+pub mod intervals;
+pub mod locations;
+pub mod run_info;
+pub mod states;
+pub mod subscriptions;
+pub mod summary;
+pub mod thresholds;
+pub mod time_series;
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+use wasm_bindgen::prelude::*;
+
+use crate::{intervals::window::Window, manager::TestInfoManager, states::Topic};
+
+lazy_static! {
+    static ref MANAGERS: Mutex<HashMap<String, TestInfoManager>> = Mutex::new(HashMap::new());
+}
+
+// Gets the manager for the id if it exists, otherwise returns an error
+fn get_manager<'a>(
+    managers: &'a mut HashMap<String, TestInfoManager>,
+    test_info_id: &str,
+) -> Result<&'a mut TestInfoManager, JsValue> {
+    managers.get_mut(test_info_id).ok_or_else(|| {
+        JsValue::from_str(format!("No test data found for test_info_id: {}", test_info_id).as_str())
+    })
+}
+
+// Runs a function against the manager for the id while holding the lock
+fn with_manager<T>(
+    test_info_id: &str,
+    f: impl FnOnce(&mut TestInfoManager) -> Result<T, JsValue>,
+) -> Result<T, JsValue> {
+    let mut managers = MANAGERS.lock().unwrap();
+
+    f(get_manager(&mut managers, test_info_id)?)
+}
+
+// Runs a function against two managers while holding the lock, eg. to compare
+// a baseline run against a candidate
+fn with_manager_pair<T>(
+    baseline_id: &str,
+    candidate_id: &str,
+    f: impl FnOnce(&TestInfoManager, &TestInfoManager) -> Result<T, JsValue>,
+) -> Result<T, JsValue> {
+    let mut managers = MANAGERS.lock().unwrap();
+
+    // Checked for existence first as only one mutable borrow can be held
+    get_manager(&mut managers, baseline_id)?;
+    get_manager(&mut managers, candidate_id)?;
+
+    f(&managers[baseline_id], &managers[candidate_id])
+}
+
+// Stores the manager under a new id, which is returned
+fn insert_manager(manager: TestInfoManager) -> String {
+    let test_info_id = Uuid::new_v4().to_string();
+
+    MANAGERS
+        .lock()
+        .unwrap()
+        .insert(test_info_id.to_owned(), manager);
+
+    test_info_id
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowOptions {
+    start_period: Option<i32>,
+    end_period: Option<i32>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+}
+
+// Parses an optional window, where undefined or null covers the whole run
+fn parse_window(window: JsValue) -> Result<Window, JsValue> {
+    let window: Option<WindowOptions> = serde_wasm_bindgen::from_value(window)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+
+    Ok(match window {
+        Some(window) => Window {
+            start_period: window.start_period,
+            end_period: window.end_period,
+            start_time: parse_time(window.start_time)?,
+            end_time: parse_time(window.end_time)?,
+        },
+        None => Window::default(),
+    })
+}
+
+// Parses milliseconds since the unix epoch, as given by Date.getTime()
+fn parse_time(time: Option<f64>) -> Result<Option<DateTime<Utc>>, JsValue> {
+    match time {
+        Some(time) => DateTime::from_timestamp_millis(time as i64)
+            .map(Some)
+            .ok_or_else(|| JsValue::from_str(format!("Invalid time: {}", time).as_str())),
+        None => Ok(None),
+    }
+}
+
+#[wasm_bindgen (js_name = rawInitTestData)]
+pub fn init_test_data(test_info: Option<Vec<u8>>) -> Result<JsValue, JsValue> {
+    // If test data is provided, parse it, otherwise create a new test info
+    let manager = match test_info {
+        Some(bytes) => {
+            TestInfoManager::from_bytes(&bytes).map_err(|e| JsValue::from_str(e.as_str()))?
+        }
+        None => TestInfoManager::new(None),
+    };
+
+    Ok(JsValue::from_str(insert_manager(manager).as_str()))
+}
+
+#[wasm_bindgen (js_name = rawExportTestData)]
+pub fn export_test_data(test_info_id: &str) -> Result<Vec<u8>, JsValue> {
+    with_manager(test_info_id, |manager| {
+        manager.to_bytes().map_err(|e| JsValue::from_str(e.as_str()))
+    })
+}
+
+#[wasm_bindgen (js_name = rawDeleteTestData)]
+pub fn delete_test_data(test_info_id: &str) -> Result<(), JsValue> {
+    let mut managers = MANAGERS.lock().unwrap();
+
+    // Get test data if it exists, otherwise return
+    match managers.remove(test_info_id) {
+        Some(_) => {
+            subscriptions::remove_subscriptions(test_info_id);
+            Ok(())
+        }
+        None => Err(JsValue::from_str(
+            format!("No test data found for test_info_id: {}", test_info_id).as_str(),
+        )),
+    }
+}
+
+#[wasm_bindgen (js_name = rawAddStreamedData)]
+pub fn add_streamed_data(test_info_id: &str, bytes: Vec<u8>) -> Result<(), JsValue> {
+    let (result, changed_topics) = with_manager(test_info_id, |manager| {
+        let previous_versions = manager.topic_versions();
+
+        let result = manager.add_streamed_data(bytes);
+
+        Ok((result, manager.changed_topics(&previous_versions)))
+    })?;
+
+    // The lock is released so subscribers can read the new data
+    subscriptions::notify_subscribers(test_info_id, &changed_topics)?;
+
+    result.map_err(|e| JsValue::from_str(e.as_str()))
+}
+
+#[wasm_bindgen (js_name = rawAddMessage)]
+pub fn add_message(test_info_id: &str, message: &str) -> Result<(), JsValue> {
+    with_manager(test_info_id, |manager| {
+        manager.add_message(message);
+        Ok(())
+    })?;
+
+    subscriptions::notify_subscribers(test_info_id, &[Topic::Messages])
+}
+
+#[wasm_bindgen (js_name = rawTestInfoIdExists)]
+pub fn test_info_id_exists(test_info_id: &str) -> bool {
+    let managers = MANAGERS.lock().unwrap();
+
+    managers.contains_key(test_info_id)
+}
@@ -0,0 +1,50 @@
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+
+use super::{parse_time, subscriptions::notify_subscribers, with_manager};
+
+#[wasm_bindgen (js_name = rawGetRunInfo)]
+pub fn get_run_info(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        let run_info = js_sys::Object::new();
+
+        // Milliseconds since the unix epoch, as taken by new Date()
+        Reflect::set(
+            &run_info,
+            &JsValue::from_str("startTime"),
+            &match manager.start_time() {
+                Some(start_time) => JsValue::from_f64(start_time.timestamp_millis() as f64),
+                None => JsValue::NULL,
+            },
+        )?;
+
+        Reflect::set(
+            &run_info,
+            &JsValue::from_str("intervalSeconds"),
+            &JsValue::from_f64(manager.interval_seconds() as f64),
+        )?;
+
+        Ok(JsValue::from(run_info))
+    })
+}
+
+#[wasm_bindgen (js_name = rawSetRunInfo)]
+pub fn set_run_info(
+    test_info_id: &str,
+    start_time: Option<f64>,
+    interval_seconds: Option<u32>,
+) -> Result<(), JsValue> {
+    let start_time = parse_time(start_time)?;
+
+    let (result, changed_topics) = with_manager(test_info_id, |manager| {
+        let previous_versions = manager.topic_versions();
+
+        let result = manager.set_run_info(start_time, interval_seconds);
+
+        Ok((result, manager.changed_topics(&previous_versions)))
+    })?;
+
+    notify_subscribers(test_info_id, &changed_topics)?;
+
+    result.map_err(|e| JsValue::from_str(e.as_str()))
+}
@@ -0,0 +1,66 @@
+use wasm_bindgen::prelude::*;
+
+use super::with_manager;
+use crate::states::Topic;
+
+#[wasm_bindgen (js_name = rawGetLocationsState)]
+pub fn get_location_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Locations) as f64)
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetThresholdsState)]
+pub fn get_thresholds_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Thresholds) as f64)
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetMessagesState)]
+pub fn get_messages_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Messages) as f64)
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetChangesSince)]
+pub fn get_changes_since(test_info_id: &str, version: f64) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        let changes = manager.changes_since(version as u64);
+
+        let obj = js_sys::Object::new();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("version"),
+            &JsValue::from_f64(changes.version as f64),
+        )?;
+
+        let intervals = js_sys::Array::new();
+        for interval in changes.intervals.into_iter() {
+            intervals.push(&Result::from(interval)?);
+        }
+        js_sys::Reflect::set(&obj, &JsValue::from_str("intervals"), &intervals)?;
+
+        let console_messages = js_sys::Array::new();
+        for console_message in changes.console_messages.iter() {
+            console_messages.push(&Result::from(console_message)?);
+        }
+        js_sys::Reflect::set(&obj, &JsValue::from_str("consoleMessages"), &console_messages)?;
+
+        let thresholds = js_sys::Array::new();
+        for threshold in changes.thresholds.iter() {
+            thresholds.push(&Result::from(threshold)?);
+        }
+        js_sys::Reflect::set(&obj, &JsValue::from_str("thresholds"), &thresholds)?;
+
+        let messages = js_sys::Array::new();
+        for message in changes.messages.iter() {
+            messages.push(&JsValue::from_str(message));
+        }
+        js_sys::Reflect::set(&obj, &JsValue::from_str("messages"), &messages)?;
+
+        Ok(JsValue::from(obj))
+    })
+}
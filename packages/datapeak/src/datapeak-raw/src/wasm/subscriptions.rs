@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use super::MANAGERS;
+use crate::states::Topic;
+
+struct Subscription {
+    topic: Topic,
+    callback: js_sys::Function,
+}
+
+thread_local! {
+    // Callbacks aren't Send, so they are kept apart from the managers
+    static SUBSCRIPTIONS: RefCell<HashMap<String, Vec<Subscription>>> = RefCell::new(HashMap::new());
+}
+
+// Invokes each subscribed callback once with the changed topics it is
+// subscribed to. Must be called without holding the managers lock as callbacks
+// are likely to read the changed data.
+pub fn notify_subscribers(test_info_id: &str, changed_topics: &[Topic]) -> Result<(), JsValue> {
+    if changed_topics.is_empty() {
+        return Ok(());
+    }
+
+    // Group topics by callback so that each callback is only invoked once
+    let notifications = SUBSCRIPTIONS.with(|subscriptions| {
+        let subscriptions = subscriptions.borrow();
+
+        let mut notifications: Vec<(js_sys::Function, js_sys::Array)> = Vec::new();
+
+        for subscription in subscriptions.get(test_info_id).into_iter().flatten() {
+            if !changed_topics.contains(&subscription.topic) {
+                continue;
+            }
+
+            let topic = JsValue::from_str(subscription.topic.as_str());
+
+            match notifications
+                .iter()
+                .find(|(callback, _)| *callback == subscription.callback)
+            {
+                Some((_, topics)) => {
+                    topics.push(&topic);
+                }
+                None => {
+                    notifications.push((subscription.callback.clone(), js_sys::Array::of1(&topic)));
+                }
+            }
+        }
+
+        notifications
+    });
+
+    for (callback, topics) in notifications.iter() {
+        callback.call1(&JsValue::NULL, topics)?;
+    }
+
+    Ok(())
+}
+
+pub fn remove_subscriptions(test_info_id: &str) {
+    SUBSCRIPTIONS.with(|subscriptions| {
+        subscriptions.borrow_mut().remove(test_info_id);
+    });
+}
+
+#[wasm_bindgen (js_name = rawSubscribe)]
+pub fn subscribe(
+    test_info_id: &str,
+    topic: JsValue,
+    callback: js_sys::Function,
+) -> Result<(), JsValue> {
+    if !MANAGERS.lock().unwrap().contains_key(test_info_id) {
+        return Err(JsValue::from_str(
+            format!("No test data found for test_info_id: {}", test_info_id).as_str(),
+        ));
+    }
+
+    let topic: Topic = serde_wasm_bindgen::from_value(topic)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+
+    SUBSCRIPTIONS.with(|subscriptions| {
+        let mut subscriptions = subscriptions.borrow_mut();
+        let test_info_subscriptions = subscriptions.entry(test_info_id.to_string()).or_default();
+
+        if !test_info_subscriptions
+            .iter()
+            .any(|x| x.topic == topic && x.callback == callback)
+        {
+            test_info_subscriptions.push(Subscription { topic, callback });
+        }
+    });
+
+    Ok(())
+}
+
+#[wasm_bindgen (js_name = rawUnsubscribe)]
+pub fn unsubscribe(
+    test_info_id: &str,
+    topic: JsValue,
+    callback: js_sys::Function,
+) -> Result<(), JsValue> {
+    let topic: Topic = serde_wasm_bindgen::from_value(topic)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+
+    SUBSCRIPTIONS.with(|subscriptions| {
+        if let Some(test_info_subscriptions) = subscriptions.borrow_mut().get_mut(test_info_id) {
+            test_info_subscriptions.retain(|x| x.topic != topic || x.callback != callback);
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use wasm_bindgen::prelude::*;
+
+use super::{parse_window, with_manager};
+use crate::states::Topic;
+
+#[wasm_bindgen (js_name = rawGetSummary)]
+pub fn get_summary(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        match manager.summary() {
+            Some(summary) => Result::from(summary.clone()),
+            None => Ok(JsValue::null()),
+        }
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetWindowSummary)]
+pub fn get_window_summary(test_info_id: &str, window: JsValue) -> Result<JsValue, JsValue> {
+    let window = parse_window(window)?;
+
+    with_manager(test_info_id, |manager| {
+        match manager
+            .window_summary(&window)
+            .map_err(|e| JsValue::from_str(e.as_str()))?
+        {
+            Some(summary) => Result::from(summary),
+            None => Ok(JsValue::null()),
+        }
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetSummaryState)]
+pub fn get_summary_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Summary) as f64)
+    })
+}
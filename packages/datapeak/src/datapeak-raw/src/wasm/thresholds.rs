@@ -0,0 +1,30 @@
+use wasm_bindgen::prelude::*;
+
+use super::with_manager;
+use crate::states::Topic;
+
+#[wasm_bindgen (js_name = rawGetThresholdResults)]
+pub fn get_threshold_results(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        serde_wasm_bindgen::to_value(manager.threshold_results())
+            .map_err(|e| JsValue::from_str(e.to_string().as_str()))
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetAbortDecision)]
+pub fn get_abort_decision(test_info_id: &str) -> Result<JsValue, JsValue> {
+    with_manager(test_info_id, |manager| {
+        match manager.abort_decision() {
+            Some(abort_decision) => serde_wasm_bindgen::to_value(abort_decision)
+                .map_err(|e| JsValue::from_str(e.to_string().as_str())),
+            None => Ok(JsValue::null()),
+        }
+    })
+}
+
+#[wasm_bindgen (js_name = rawGetAbortState)]
+pub fn get_abort_state(test_info_id: &str) -> Result<f64, JsValue> {
+    with_manager(test_info_id, |manager| {
+        Ok(manager.topic_version(Topic::Abort) as f64)
+    })
+}
@@ -0,0 +1,112 @@
+use std::iter::FromIterator;
+
+use js_sys::{Array, Reflect};
+use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
+
+use super::{parse_window, with_manager};
+use crate::intervals::time_series::{DownsizingMethod, TimeSeries};
+
+#[wasm_bindgen (js_name = rawIntervalTimeSeries)]
+pub fn interval_time_series(
+    test_info_id: &str,
+    sink_path: &str,
+    downsizing_method: JsValue,
+    window: JsValue,
+) -> Result<JsValue, JsValue> {
+    let downsizing_method: DownsizingMethod = serde_wasm_bindgen::from_value(downsizing_method)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+    let window = parse_window(window)?;
+
+    let time_series = with_manager(test_info_id, |manager| {
+        manager
+            .window_time_series(sink_path, downsizing_method, &window)
+            .map_err(|e| JsValue::from_str(e.as_str()))
+    })?;
+
+    time_series_to_js(&time_series)
+}
+
+#[wasm_bindgen (js_name = rawQueryTimeSeries)]
+pub fn query_time_series(
+    test_info_id: &str,
+    pattern: &str,
+    downsizing_method: JsValue,
+    window: JsValue,
+) -> Result<JsValue, JsValue> {
+    let downsizing_method: DownsizingMethod = serde_wasm_bindgen::from_value(downsizing_method)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+    let window = parse_window(window)?;
+
+    let time_series = with_manager(test_info_id, |manager| {
+        manager
+            .query_time_series(pattern, downsizing_method, &window)
+            .map_err(|e| JsValue::from_str(e.as_str()))
+    })?;
+
+    let series_array = Array::new();
+
+    for time_series in time_series.iter() {
+        series_array.push(&time_series_to_js(time_series)?);
+    }
+
+    Ok(JsValue::from(series_array))
+}
+
+#[wasm_bindgen (js_name = rawExpressionTimeSeries)]
+pub fn expression_time_series(
+    test_info_id: &str,
+    expression: &str,
+    downsizing_method: JsValue,
+    window: JsValue,
+) -> Result<JsValue, JsValue> {
+    let downsizing_method: DownsizingMethod = serde_wasm_bindgen::from_value(downsizing_method)
+        .map_err(|e| JsValue::from_str(e.to_string().as_str()))?;
+    let window = parse_window(window)?;
+
+    let time_series = with_manager(test_info_id, |manager| {
+        manager
+            .expression_time_series(expression, downsizing_method, &window)
+            .map_err(|e| JsValue::from_str(e.as_str()))
+    })?;
+
+    time_series_to_js(&time_series)
+}
+
+fn time_series_to_js(time_series: &TimeSeries) -> Result<JsValue, JsValue> {
+    let series_object = js_sys::Object::new();
+
+    Reflect::set(
+        &series_object,
+        &JsValue::from_str("name"),
+        &JsValue::from_str(time_series.name.as_str()),
+    )?;
+
+    // Gaps are null so that charts break the line there
+    Reflect::set(
+        &series_object,
+        &JsValue::from_str("data"),
+        &Array::from_iter(time_series.data.iter().map(|(x, y)| {
+            Array::of2(
+                &JsValue::from_f64(*x),
+                &if y.is_nan() {
+                    JsValue::NULL
+                } else {
+                    JsValue::from_f64(*y)
+                },
+            )
+        })),
+    )?;
+
+    Reflect::set(
+        &series_object,
+        &JsValue::from_str("timestamps"),
+        &match &time_series.timestamps {
+            Some(timestamps) => JsValue::from(Array::from_iter(
+                timestamps.iter().map(|x| JsValue::from_f64(*x)),
+            )),
+            None => JsValue::NULL,
+        },
+    )?;
+
+    Ok(JsValue::from(series_object))
+}
@@ -0,0 +1,147 @@
+//! Tests for anomaly detection on incoming intervals.
+
+mod common;
+
+use common::sink;
+use datapeak::{
+    anomalies::{AnomalyKind, AnomalyOptions},
+    manager::TestInfoManager,
+    states::Topic,
+    types,
+};
+
+// An interval with a p(95), a cumulative request count and a failure rate
+fn interval(period: i32, p95: f64, count: f64, failed: f64) -> types::Interval {
+    common::interval(
+        period,
+        vec![
+            (
+                "us-east1::http_req_duration",
+                sink(types::SinkType::Trend, &[("p(95)", p95)]),
+            ),
+            (
+                "us-east1::http_reqs",
+                sink(types::SinkType::Counter, &[("count", count)]),
+            ),
+            (
+                "us-east1::http_req_failed",
+                sink(types::SinkType::Rate, &[("pass", failed), ("total", 100.0)]),
+            ),
+        ],
+    )
+}
+
+// Steady intervals with a little noise, at 10 requests per second
+fn steady_manager(periods: i32) -> TestInfoManager {
+    let mut manager = TestInfoManager::new(None);
+
+    for period in 0..periods {
+        let noise = if period % 2 == 0 { 5.0 } else { -5.0 };
+        manager
+            .process_interval(&interval(
+                period,
+                200.0 + noise,
+                60.0 * (period + 1) as f64,
+                1.0,
+            ))
+            .unwrap();
+    }
+
+    manager
+}
+
+#[test]
+fn flags_latency_spikes() {
+    let mut manager = steady_manager(8);
+    assert!(manager.anomalies().is_empty());
+
+    manager
+        .process_interval(&interval(8, 400.0, 540.0, 1.0))
+        .unwrap();
+
+    let spikes: Vec<_> = manager
+        .anomalies()
+        .iter()
+        .filter(|x| x.kind == AnomalyKind::LatencySpike)
+        .collect();
+
+    assert_eq!(spikes.len(), 2);
+    assert_eq!(spikes[0].sink_name, "global::http_req_duration");
+    assert_eq!(spikes[1].location, "us-east1");
+    assert_eq!(spikes[1].period, 8);
+    assert_eq!(spikes[1].value, 400.0);
+    assert!((spikes[1].expected - 200.0).abs() < 5.0);
+    assert!(spikes[1].z_score > 3.0);
+}
+
+#[test]
+fn needs_a_full_window_before_flagging() {
+    let mut manager = steady_manager(3);
+
+    manager
+        .process_interval(&interval(3, 400.0, 240.0, 1.0))
+        .unwrap();
+
+    assert!(manager.anomalies().is_empty());
+}
+
+#[test]
+fn flags_error_rate_spikes_and_throughput_drops() {
+    let mut manager = steady_manager(8);
+
+    // Only 6 more requests, of which half failed
+    manager
+        .process_interval(&interval(8, 200.0, 486.0, 50.0))
+        .unwrap();
+
+    let kinds: Vec<(AnomalyKind, &str)> = manager
+        .anomalies()
+        .iter()
+        .filter(|x| x.location == "us-east1")
+        .map(|x| (x.kind, x.label.as_str()))
+        .collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            (AnomalyKind::ErrorRateSpike, "rate"),
+            (AnomalyKind::ThroughputDrop, "rate")
+        ]
+    );
+}
+
+#[test]
+fn anomalies_have_their_own_state() {
+    let mut manager = steady_manager(8);
+    let version = manager.topic_version(Topic::Anomalies);
+
+    // An interval without anomalies leaves the state alone
+    manager
+        .process_interval(&interval(8, 200.0, 540.0, 1.0))
+        .unwrap();
+    assert_eq!(manager.topic_version(Topic::Anomalies), version);
+
+    manager
+        .process_interval(&interval(9, 400.0, 600.0, 1.0))
+        .unwrap();
+    assert!(manager.topic_version(Topic::Anomalies) > version);
+
+    // Raising the threshold clears them
+    manager.set_anomaly_options(AnomalyOptions {
+        z_score_threshold: 1000.0,
+        ..AnomalyOptions::default()
+    });
+    assert!(manager.anomalies().is_empty());
+}
+
+#[test]
+fn loaded_runs_are_checked_for_anomalies() {
+    let mut manager = steady_manager(8);
+    manager
+        .process_interval(&interval(8, 400.0, 540.0, 1.0))
+        .unwrap();
+
+    let loaded = TestInfoManager::from_bytes(&manager.to_bytes().unwrap()).unwrap();
+
+    assert_eq!(loaded.anomalies(), manager.anomalies());
+}
@@ -0,0 +1,146 @@
+//! Tests for the native Rust API, which doesn't depend on wasm-bindgen.
+
+mod common;
+
+use common::sink;
+use datapeak::{
+    intervals::time_series::DownsizingMethod, manager::TestInfoManager, states::Topic, types,
+};
+use protobuf::Message;
+
+fn interval(period: i32, duration: f64) -> types::Interval {
+    common::interval(
+        period,
+        vec![
+            (
+                "eu-west1::http_reqs",
+                sink(
+                    types::SinkType::Counter,
+                    &[("count", 6.0 * (period + 1) as f64)],
+                ),
+            ),
+            (
+                "eu-west1::http_req_duration",
+                sink(types::SinkType::Trend, &[("avg", duration)]),
+            ),
+        ],
+    )
+}
+
+fn test_info() -> types::TestInfo {
+    let mut threshold = types::Threshold::new();
+    threshold.metric = "http_req_duration".to_string();
+    threshold.source = "avg<150".to_string();
+
+    let mut test_info = types::TestInfo::new();
+    test_info.intervals = vec![interval(0, 100.0), interval(1, 200.0)];
+    test_info.thresholds = vec![threshold];
+    test_info.messages = vec!["started".to_string()];
+    test_info
+}
+
+#[test]
+fn loads_test_info_from_bytes() {
+    let bytes = test_info().write_to_bytes().unwrap();
+    let manager = TestInfoManager::from_bytes(&bytes).unwrap();
+
+    assert_eq!(manager.intervals().len(), 2);
+    assert_eq!(manager.thresholds().len(), 1);
+    assert_eq!(manager.messages(), ["started".to_string()]);
+    assert_eq!(manager.locations(), ["eu-west1".to_string()]);
+    assert_eq!(manager.latest_period, 1);
+
+    let summary = manager.summary().unwrap();
+    assert!(summary.sinks.contains_key("global::http_reqs"));
+}
+
+#[test]
+fn rejects_invalid_bytes() {
+    assert!(TestInfoManager::from_bytes(&[0xff, 0xff, 0xff]).is_err());
+}
+
+#[test]
+fn evaluates_thresholds_of_loaded_data() {
+    let manager = TestInfoManager::new(Some(&test_info()));
+
+    let results = manager.threshold_results();
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|x| !x.passed && x.period == 1));
+    assert!(manager.abort_decision().is_none());
+}
+
+#[test]
+fn gets_time_series_for_label() {
+    let manager = TestInfoManager::new(Some(&test_info()));
+
+    let time_series = manager
+        .interval_time_series("global::http_req_duration::avg", DownsizingMethod::None)
+        .unwrap();
+
+    assert_eq!(time_series.name, "global::http_req_duration");
+    assert_eq!(time_series.data, vec![(0.0, 100.0), (1.0, 200.0)]);
+
+    assert!(manager
+        .interval_time_series("http_req_duration", DownsizingMethod::None)
+        .is_err());
+    assert!(manager
+        .interval_time_series("global::missing::avg", DownsizingMethod::None)
+        .is_err());
+}
+
+#[test]
+fn set_locations_reports_changes() {
+    let mut manager = TestInfoManager::new(None);
+
+    assert!(manager.set_locations(vec!["eu-west1".to_string()]));
+    let version = manager.topic_version(Topic::Locations);
+
+    assert!(!manager.set_locations(vec!["eu-west1".to_string()]));
+    assert_eq!(manager.topic_version(Topic::Locations), version);
+    assert_eq!(manager.locations(), ["eu-west1".to_string()]);
+}
+
+#[test]
+fn records_console_messages() {
+    let mut console_message = types::ConsoleMessage::new();
+    console_message.message = "request failed".to_string();
+    console_message.level = "error".to_string();
+    console_message.count.insert("eu-west1".to_string(), 3);
+
+    let mut data_point = types::DataPoint::new();
+    data_point.set_console_message(console_message);
+
+    let mut streamed_data = types::StreamedData::new();
+    streamed_data.data_points.push(data_point);
+
+    let mut manager = TestInfoManager::new(None);
+    manager
+        .add_streamed_data(streamed_data.write_to_bytes().unwrap())
+        .unwrap();
+
+    assert_eq!(manager.console_messages().len(), 1);
+    assert_eq!(manager.console_messages()[0].count["eu-west1"], 3);
+}
+
+#[test]
+fn exported_bytes_load_the_same_data() {
+    let mut manager = TestInfoManager::new(Some(&test_info()));
+    manager.set_locations(vec!["eu-west1".to_string(), "us-east1".to_string()]);
+    manager.add_message("finished");
+
+    let bytes = manager.to_bytes().unwrap();
+    let exported = types::TestInfo::parse_from_bytes(&bytes).unwrap();
+
+    assert_eq!(exported.intervals.len(), 2);
+    assert_eq!(exported.thresholds.len(), 1);
+    assert_eq!(exported.messages, vec!["started".to_string(), "finished".to_string()]);
+    assert_eq!(exported.summary.as_ref(), manager.summary());
+    assert_eq!(exported.locations, vec!["eu-west1".to_string(), "us-east1".to_string()]);
+
+    let loaded = TestInfoManager::from_bytes(&bytes).unwrap();
+
+    assert_eq!(loaded.intervals(), manager.intervals());
+    assert_eq!(loaded.summary(), manager.summary());
+    assert_eq!(loaded.locations(), manager.locations());
+    assert_eq!(loaded.threshold_results(), manager.threshold_results());
+}
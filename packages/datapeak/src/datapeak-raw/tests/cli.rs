@@ -0,0 +1,165 @@
+//! Tests for the datapeak command line tool.
+
+mod common;
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use common::sink;
+use datapeak::types;
+use protobuf::Message;
+
+fn interval(period: i32, duration: f64) -> types::Interval {
+    common::interval(
+        period,
+        vec![
+            (
+                "us-east1::http_req_duration",
+                sink(types::SinkType::Trend, &[("avg", duration)]),
+            ),
+            (
+                "us-east1::checks::check::status is 200",
+                sink(
+                    types::SinkType::Rate,
+                    &[("pass", 9.0), ("total", 10.0), ("rate", 0.9)],
+                ),
+            ),
+        ],
+    )
+}
+
+fn threshold(source: &str) -> types::Threshold {
+    let mut threshold = types::Threshold::new();
+    threshold.metric = "http_req_duration".to_string();
+    threshold.source = source.to_string();
+    threshold
+}
+
+fn console_message(message: &str, count: i32) -> types::ConsoleMessage {
+    let mut console_message = types::ConsoleMessage::new();
+    console_message.message = message.to_string();
+    console_message.level = "warn".to_string();
+    console_message.count.insert("us-east1".to_string(), count);
+    console_message
+}
+
+fn write_file(name: &str, bytes: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("datapeak-cli-{}-{}", std::process::id(), name));
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_datapeak"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn run_file(args: &[&str], path: PathBuf) -> Output {
+    let mut args = args.to_vec();
+    args.push(path.to_str().unwrap());
+
+    let output = run(&args);
+    std::fs::remove_file(path).unwrap();
+    output
+}
+
+#[test]
+fn prints_report_for_test_info() {
+    let mut test_info = types::TestInfo::new();
+    test_info.intervals = vec![interval(0, 100.0), interval(1, 120.0)];
+    test_info.thresholds = vec![threshold("avg<500")];
+    test_info.console_messages = vec![
+        console_message("rarely", 1),
+        console_message("often", 12),
+    ];
+
+    let path = write_file("passing", &test_info.write_to_bytes().unwrap());
+    let output = run_file(&[], path);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("global::http_req_duration"));
+    assert!(stdout.contains("avg<500"));
+    assert!(stdout.contains("status is 200"));
+    assert!(stdout.contains("90.00%"));
+    assert!(stdout.find("often").unwrap() < stdout.find("rarely").unwrap());
+}
+
+#[test]
+fn exits_with_failure_when_thresholds_fail() {
+    let mut test_info = types::TestInfo::new();
+    test_info.intervals = vec![interval(0, 100.0)];
+    test_info.thresholds = vec![threshold("avg<50")];
+
+    let path = write_file("failing", &test_info.write_to_bytes().unwrap());
+    let output = run_file(&[], path);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("FAIL"));
+}
+
+#[test]
+fn exits_with_failure_when_the_run_fails_despite_recovering() {
+    let mut test_info = types::TestInfo::new();
+    test_info.intervals = vec![interval(0, 900.0), interval(1, 100.0)];
+    test_info.thresholds = vec![threshold("avg<400")];
+
+    let path = write_file("recovered", &test_info.write_to_bytes().unwrap());
+    let output = run_file(&[], path);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    // The last interval passes, but the average over the run is 500
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("FAIL"));
+    assert!(stdout.contains("500"));
+}
+
+#[test]
+fn replays_streamed_data_frames() {
+    let mut bytes: Vec<u8> = Vec::new();
+
+    for period in 0..3 {
+        let mut data_point = types::DataPoint::new();
+        data_point.set_interval(interval(period, 100.0));
+
+        let mut streamed_data = types::StreamedData::new();
+        streamed_data.data_points.push(data_point);
+
+        bytes.extend(streamed_data.write_length_delimited_to_bytes().unwrap());
+    }
+
+    let path = write_file("streamed", &bytes);
+    let output = run_file(&["--streamed", "--top", "1"], path);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("us-east1::http_req_duration"));
+}
+
+#[test]
+fn imports_k6_json_output() {
+    let ndjson = r#"{"type":"Metric","data":{"type":"trend","thresholds":["avg<50"]},"metric":"http_req_duration"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45Z","value":100,"tags":{"load_zone":"us-east1"}},"metric":"http_req_duration"}
+"#;
+
+    let path = write_file("k6", ndjson.as_bytes());
+    let output = run_file(&["--k6"], path);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("us-east1::http_req_duration"));
+}
+
+#[test]
+fn reports_usage_errors() {
+    assert_eq!(run(&[]).status.code(), Some(2));
+    assert_eq!(run(&["--unknown", "file"]).status.code(), Some(2));
+    assert_eq!(run(&["--streamed", "--k6", "file"]).status.code(), Some(2));
+    assert_eq!(run(&["--k6", "--jmeter", "file"]).status.code(), Some(2));
+    assert_eq!(run(&["/nonexistent/datapeak.pb"]).status.code(), Some(2));
+    assert_eq!(run(&["--help"]).status.code(), Some(0));
+}
@@ -0,0 +1,23 @@
+//! Fixtures shared by the integration tests.
+
+use datapeak::types;
+
+pub fn sink(sink_type: types::SinkType, labels: &[(&str, f64)]) -> types::Sink {
+    let mut sink = types::Sink::new();
+    sink.type_ = sink_type.into();
+    for (label, value) in labels.iter() {
+        sink.labels.insert(label.to_string(), *value);
+    }
+    sink
+}
+
+// Not every test binary builds intervals
+#[allow(dead_code)]
+pub fn interval(period: i32, sinks: Vec<(&str, types::Sink)>) -> types::Interval {
+    let mut interval = types::Interval::new();
+    interval.period = period;
+    for (sink_name, sink) in sinks.into_iter() {
+        interval.sinks.insert(sink_name.to_string(), sink);
+    }
+    interval
+}
@@ -0,0 +1,211 @@
+//! Tests for comparing two runs.
+
+mod common;
+
+use common::sink;
+use datapeak::{
+    comparison::{
+        compare_bytes,
+        significance::{mann_whitney_u, two_proportion_z_test, SignificanceTest},
+        ComparisonOptions, LabelDelta,
+    },
+    manager::TestInfoManager,
+    types,
+};
+
+// A run with a fixed p(95) and request failure rate in every interval
+fn run(periods: i32, p95: f64, failed: f64) -> TestInfoManager {
+    let mut manager = TestInfoManager::new(None);
+
+    for period in 0..periods {
+        let mut interval = types::Interval::new();
+        interval.period = period;
+        interval.sinks.insert(
+            "us-east1::http_req_duration".to_string(),
+            sink(
+                types::SinkType::Trend,
+                &[("avg", p95 / 2.0), ("p(95)", p95)],
+            ),
+        );
+        interval.sinks.insert(
+            "us-east1::http_req_failed".to_string(),
+            sink(
+                types::SinkType::Rate,
+                &[("pass", failed), ("total", 100.0), ("rate", failed / 100.0)],
+            ),
+        );
+
+        manager.process_interval(&interval).unwrap();
+    }
+
+    manager
+}
+
+fn delta<'a>(deltas: &'a [LabelDelta], sink_name: &str, label: &str) -> &'a LabelDelta {
+    deltas
+        .iter()
+        .find(|x| x.sink_name == sink_name && x.label == label)
+        .unwrap()
+}
+
+#[test]
+fn gives_absolute_and_relative_deltas() {
+    let comparison = run(2, 200.0, 1.0).compare(&run(2, 210.0, 1.0), &ComparisonOptions::default());
+
+    let p95 = delta(&comparison.deltas, "global::http_req_duration", "p(95)");
+    assert_eq!(p95.absolute_delta, 10.0);
+    assert_eq!(p95.relative_delta, Some(0.05));
+    assert!(!p95.regression);
+
+    assert!(!comparison.regressed);
+    assert!(comparison.baseline_only.is_empty());
+}
+
+#[test]
+fn flags_slower_p95_as_regression() {
+    let comparison = run(2, 200.0, 1.0).compare(&run(2, 250.0, 1.0), &ComparisonOptions::default());
+
+    assert!(comparison.regressed);
+    assert!(delta(&comparison.deltas, "us-east1::http_req_duration", "p(95)").regression);
+    // Only percentiles are judged
+    assert!(!delta(&comparison.deltas, "us-east1::http_req_duration", "avg").regression);
+
+    let options = ComparisonOptions {
+        p95_tolerance: 0.5,
+        ..ComparisonOptions::default()
+    };
+    assert!(
+        !run(2, 200.0, 1.0)
+            .compare(&run(2, 250.0, 1.0), &options)
+            .regressed
+    );
+}
+
+#[test]
+fn flags_higher_error_rate_as_regression() {
+    let comparison = run(2, 200.0, 1.0).compare(&run(2, 200.0, 5.0), &ComparisonOptions::default());
+
+    let rate = delta(&comparison.deltas, "global::http_req_failed", "rate");
+    assert!((rate.absolute_delta - 0.04).abs() < 1e-9);
+    assert!(rate.regression);
+
+    // Fewer failures is an improvement
+    let comparison = run(2, 200.0, 5.0).compare(&run(2, 200.0, 1.0), &ComparisonOptions::default());
+    assert!(!comparison.regressed);
+}
+
+#[test]
+fn lists_sinks_missing_from_either_run() {
+    let baseline = run(1, 200.0, 1.0);
+
+    let mut candidate = TestInfoManager::new(None);
+    let mut interval = types::Interval::new();
+    interval.sinks.insert(
+        "us-east1::vus".to_string(),
+        sink(types::SinkType::Gauge, &[("value", 10.0)]),
+    );
+    candidate.process_interval(&interval).unwrap();
+
+    let comparison = baseline.compare(&candidate, &ComparisonOptions::default());
+
+    assert!(comparison.deltas.is_empty());
+    assert!(comparison
+        .baseline_only
+        .contains(&"us-east1::http_req_duration".to_string()));
+    assert_eq!(
+        comparison.candidate_only,
+        vec!["global::vus".to_string(), "us-east1::vus".to_string()]
+    );
+}
+
+#[test]
+fn compares_exported_test_info() {
+    let baseline = run(2, 200.0, 1.0).to_bytes().unwrap();
+    let candidate = run(2, 300.0, 1.0).to_bytes().unwrap();
+
+    let comparison = compare_bytes(&baseline, &candidate, &ComparisonOptions::default()).unwrap();
+    assert!(comparison.regressed);
+
+    assert!(compare_bytes(&[0xff], &candidate, &ComparisonOptions::default()).is_err());
+}
+
+#[test]
+fn aligns_time_series_by_period() {
+    let baseline = run(3, 200.0, 1.0);
+    let candidate = run(2, 300.0, 1.0);
+
+    let time_series = baseline
+        .aligned_time_series(&candidate, "global::http_req_duration::p(95)")
+        .unwrap();
+
+    assert_eq!(time_series.name, "global::http_req_duration");
+    assert_eq!(time_series.data.len(), 3);
+    assert_eq!(time_series.data[1].baseline, Some(200.0));
+    assert_eq!(time_series.data[1].candidate, Some(300.0));
+    assert_eq!(time_series.data[2].candidate, None);
+
+    assert!(baseline
+        .aligned_time_series(&candidate, "global::vus::value")
+        .is_err());
+    assert!(baseline.aligned_time_series(&candidate, "vus").is_err());
+}
+
+#[test]
+fn mann_whitney_u_matches_reference_values() {
+    let outcome = mann_whitney_u(&[1.0, 2.0, 3.0, 4.0, 5.0], &[6.0, 7.0, 8.0, 9.0, 10.0]).unwrap();
+
+    assert_eq!(outcome.statistic, 25.0);
+    assert_eq!(outcome.effect, 1.0);
+    assert!((outcome.p_value - 0.0122).abs() < 1e-3);
+
+    let outcome = mann_whitney_u(&[3.0, 3.0, 3.0], &[3.0, 3.0, 3.0]).unwrap();
+    assert_eq!(outcome.p_value, 1.0);
+
+    assert!(mann_whitney_u(&[], &[1.0]).is_none());
+}
+
+#[test]
+fn two_proportion_z_test_matches_reference_values() {
+    let outcome = two_proportion_z_test((10.0, 100.0), (20.0, 100.0)).unwrap();
+
+    assert!((outcome.statistic - 1.98).abs() < 1e-2);
+    assert!((outcome.p_value - 0.0477).abs() < 1e-3);
+    assert!((outcome.effect - 0.1).abs() < 1e-9);
+
+    assert!(two_proportion_z_test((0.0, 0.0), (1.0, 10.0)).is_none());
+}
+
+#[test]
+fn comparison_reports_significance() {
+    let options = ComparisonOptions::default();
+
+    // Identical runs show no significant differences
+    let comparison = run(5, 200.0, 1.0).compare(&run(5, 200.0, 1.0), &options);
+    assert!(!comparison.significance.is_empty());
+    assert!(comparison.significance.iter().all(|x| !x.significant));
+
+    let comparison = run(5, 200.0, 1.0).compare(&run(5, 300.0, 10.0), &options);
+
+    let p95 = comparison
+        .significance
+        .iter()
+        .find(|x| x.sink_name == "global::http_req_duration" && x.label == "p(95)")
+        .unwrap();
+    assert_eq!(p95.test, SignificanceTest::MannWhitneyU);
+    assert!(p95.significant);
+
+    let failed = comparison
+        .significance
+        .iter()
+        .find(|x| x.sink_name == "global::http_req_failed")
+        .unwrap();
+    assert_eq!(failed.test, SignificanceTest::TwoProportionZ);
+    assert!(failed.significant);
+
+    // Too few intervals to test trends
+    let comparison = run(2, 200.0, 1.0).compare(&run(2, 300.0, 1.0), &options);
+    assert!(comparison
+        .significance
+        .iter()
+        .all(|x| x.test == SignificanceTest::TwoProportionZ));
+}
@@ -0,0 +1,278 @@
+//! Tests for exporting test data to other formats.
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::sink;
+use datapeak::{
+    exporters::{
+        csv::{intervals_to_csv, CsvOptions},
+        influx::intervals_to_influx,
+        openmetrics::render_openmetrics,
+        parse_sink_path, SinkPath,
+    },
+    glob::glob_match,
+    manager::TestInfoManager,
+    types,
+};
+
+fn manager() -> TestInfoManager {
+    let mut manager = TestInfoManager::new(None);
+
+    for period in 0..2 {
+        let mut interval = types::Interval::new();
+        interval.period = period;
+        interval.sinks.insert(
+            "us-east1::http_reqs".to_string(),
+            sink(types::SinkType::Counter, &[("count", 12.0 * (period + 1) as f64)]),
+        );
+        interval.sinks.insert(
+            "eu-west1::http_reqs".to_string(),
+            sink(types::SinkType::Counter, &[("count", 6.0 * (period + 1) as f64)]),
+        );
+        interval.sinks.insert(
+            "us-east1::http_req_duration".to_string(),
+            sink(types::SinkType::Trend, &[("avg", 100.0), ("p(95)", 150.5)]),
+        );
+
+        manager.process_interval(&interval).unwrap();
+    }
+
+    manager
+}
+
+#[test]
+fn glob_matches_sink_paths() {
+    assert!(glob_match("*", "us-east1::http_reqs"));
+    assert!(glob_match("*::http_reqs", "us-east1::http_reqs"));
+    assert!(glob_match("us-*::http_reqs", "us-east1::http_reqs"));
+    assert!(glob_match("*::p(95)", "http_req_duration::p(95)"));
+    assert!(glob_match("http_req?", "http_reqs"));
+    assert!(glob_match("*reqs*", "http_reqs::rate"));
+    assert!(!glob_match("eu-*::http_reqs", "us-east1::http_reqs"));
+    assert!(!glob_match("http_req?", "http_req"));
+    assert!(!glob_match("*::rate", "http_reqs::count"));
+}
+
+#[test]
+fn csv_has_a_row_per_period_and_location() {
+    let csv = manager().export_csv(&CsvOptions {
+        columns: vec!["http_reqs::*".to_string()],
+        ..CsvOptions::default()
+    });
+
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "period,location,http_reqs::count,http_reqs::mean,http_reqs::rate");
+    assert_eq!(lines[1], "0,global,18,3,3");
+    assert_eq!(lines[2], "0,eu-west1,6,1,1");
+    assert_eq!(lines[3], "0,us-east1,12,2,2");
+    assert_eq!(lines[4], "1,global,36,3,3");
+    assert_eq!(lines.len(), 7);
+}
+
+#[test]
+fn csv_leaves_missing_values_empty() {
+    let csv = manager().export_csv(&CsvOptions {
+        columns: vec!["*::p(95)".to_string(), "http_reqs::count".to_string()],
+        ..CsvOptions::default()
+    });
+
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "period,location,http_req_duration::p(95),http_reqs::count");
+    assert_eq!(lines[2], "0,eu-west1,,6");
+    assert_eq!(lines[3], "0,us-east1,150.5,12");
+}
+
+#[test]
+fn csv_includes_timestamps_when_start_time_is_known() {
+    let csv = manager().export_csv(&CsvOptions {
+        columns: vec!["http_reqs::count".to_string()],
+        start_time: Some(Utc.with_ymd_and_hms(2023, 5, 1, 12, 0, 0).unwrap()),
+        ..CsvOptions::default()
+    });
+
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "period,timestamp,location,http_reqs::count");
+    assert_eq!(lines[1], "0,2023-05-01T12:00:00.000Z,global,18");
+    assert_eq!(lines[4], "1,2023-05-01T12:00:06.000Z,global,36");
+}
+
+#[test]
+fn csv_quotes_cells_with_separators() {
+    let mut interval = types::Interval::new();
+    interval.sinks.insert(
+        "us-east1::checks::check::status is 200, body ok".to_string(),
+        sink(types::SinkType::Rate, &[("rate", 1.0)]),
+    );
+
+    let csv = intervals_to_csv(&[interval], &CsvOptions::default());
+
+    assert_eq!(
+        csv.lines().next().unwrap(),
+        "period,location,\"checks::check::status is 200, body ok::rate\""
+    );
+}
+
+#[test]
+fn sink_paths_split_into_location_metric_and_tags() {
+    assert_eq!(
+        parse_sink_path("us-east1::checks::check::status is 200"),
+        Some(SinkPath {
+            location: "us-east1",
+            metric: "checks",
+            tags: vec![("check", "status is 200")],
+        })
+    );
+    assert_eq!(
+        parse_sink_path("global::http_reqs::extra"),
+        Some(SinkPath {
+            location: "global",
+            metric: "http_reqs",
+            tags: vec![("tag", "extra")],
+        })
+    );
+    assert_eq!(parse_sink_path("http_reqs"), None);
+}
+
+#[test]
+fn openmetrics_maps_sink_types_to_metric_families() {
+    let mut interval = types::Interval::new();
+    interval.period = 3;
+    interval.sinks.insert(
+        "us-east1::http_reqs".to_string(),
+        sink(types::SinkType::Counter, &[("count", 120.0), ("rate", 5.0)]),
+    );
+    interval.sinks.insert(
+        "us-east1::vus".to_string(),
+        sink(types::SinkType::Gauge, &[("value", 10.0)]),
+    );
+    interval.sinks.insert(
+        "us-east1::checks::check::status is \"ok\"".to_string(),
+        sink(types::SinkType::Rate, &[("pass", 9.0), ("total", 10.0)]),
+    );
+    interval.sinks.insert(
+        "us-east1::http_req_duration".to_string(),
+        sink(
+            types::SinkType::Trend,
+            &[("avg", 100.0), ("count", 4.0), ("med", 90.0), ("p(95)", 180.0), ("max", 200.0)],
+        ),
+    );
+
+    let text = render_openmetrics(None, Some(&interval));
+    let lines: Vec<&str> = text.lines().collect();
+
+    let expected = [
+        "# TYPE k6_checks_interval_fail gauge",
+        "k6_checks_interval_fail{scope=\"interval\",location=\"us-east1\",check=\"status is \\\"ok\\\"\"} 1",
+        "# TYPE k6_checks_interval_pass gauge",
+        "k6_checks_interval_pass{scope=\"interval\",location=\"us-east1\",check=\"status is \\\"ok\\\"\"} 9",
+        "# TYPE k6_http_req_duration summary",
+        "k6_http_req_duration{scope=\"interval\",location=\"us-east1\",quantile=\"0.5\"} 90",
+        "k6_http_req_duration{scope=\"interval\",location=\"us-east1\",quantile=\"0.95\"} 180",
+        "k6_http_req_duration{scope=\"interval\",location=\"us-east1\",quantile=\"1\"} 200",
+        "k6_http_req_duration_count{scope=\"interval\",location=\"us-east1\"} 4",
+        "k6_http_req_duration_sum{scope=\"interval\",location=\"us-east1\"} 400",
+        "# TYPE k6_http_reqs counter",
+        "k6_http_reqs_total{scope=\"interval\",location=\"us-east1\"} 120",
+        "# TYPE k6_period gauge",
+        "k6_period 3",
+        "# TYPE k6_vus gauge",
+        "k6_vus{scope=\"interval\",location=\"us-east1\"} 10",
+        "# EOF",
+    ];
+
+    assert_eq!(lines, expected);
+}
+
+#[test]
+fn openmetrics_keeps_the_samples_of_each_label_set_together() {
+    let mut summary = types::Interval::new();
+    for (location, failures, p95) in [("us-east1", 1.0, 200.0), ("eu-west1", 2.0, 300.0)] {
+        summary.sinks.insert(
+            format!("{}::checks", location),
+            sink(types::SinkType::Rate, &[("pass", 10.0 - failures), ("fail", failures)]),
+        );
+        summary.sinks.insert(
+            format!("{}::http_req_duration", location),
+            sink(
+                types::SinkType::Trend,
+                &[("avg", 100.0), ("count", 2.0), ("p(95)", p95), ("med", 90.0)],
+            ),
+        );
+    }
+
+    let text = render_openmetrics(Some(&summary), None);
+    let lines: Vec<&str> = text.lines().collect();
+
+    let expected = [
+        "# TYPE k6_checks_fail counter",
+        "k6_checks_fail_total{scope=\"summary\",location=\"eu-west1\"} 2",
+        "k6_checks_fail_total{scope=\"summary\",location=\"us-east1\"} 1",
+        "# TYPE k6_checks_pass counter",
+        "k6_checks_pass_total{scope=\"summary\",location=\"eu-west1\"} 8",
+        "k6_checks_pass_total{scope=\"summary\",location=\"us-east1\"} 9",
+        "# TYPE k6_http_req_duration summary",
+        "k6_http_req_duration{scope=\"summary\",location=\"eu-west1\",quantile=\"0.5\"} 90",
+        "k6_http_req_duration{scope=\"summary\",location=\"eu-west1\",quantile=\"0.95\"} 300",
+        "k6_http_req_duration_count{scope=\"summary\",location=\"eu-west1\"} 2",
+        "k6_http_req_duration_sum{scope=\"summary\",location=\"eu-west1\"} 200",
+        "k6_http_req_duration{scope=\"summary\",location=\"us-east1\",quantile=\"0.5\"} 90",
+        "k6_http_req_duration{scope=\"summary\",location=\"us-east1\",quantile=\"0.95\"} 200",
+        "k6_http_req_duration_count{scope=\"summary\",location=\"us-east1\"} 2",
+        "k6_http_req_duration_sum{scope=\"summary\",location=\"us-east1\"} 200",
+        "# EOF",
+    ];
+
+    assert_eq!(lines, expected);
+}
+
+#[test]
+fn openmetrics_includes_summary_and_latest_interval() {
+    let text = manager().export_openmetrics();
+
+    assert!(text.contains("k6_http_reqs_total{scope=\"summary\",location=\"global\"} 36"));
+    assert!(text.contains("k6_http_reqs_total{scope=\"interval\",location=\"eu-west1\"} 12"));
+    assert!(text.contains("k6_period 1"));
+    assert!(text.ends_with("# EOF\n"));
+}
+
+#[test]
+fn influx_writes_a_line_per_sink_and_interval() {
+    let start_time = Utc.with_ymd_and_hms(2023, 5, 1, 12, 0, 0).unwrap();
+    let text = manager().export_influx(Some(start_time)).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines.len(), 10);
+    assert_eq!(
+        lines[0],
+        "http_reqs,location=eu-west1 count=6,mean=1,rate=1 1682942400000000000"
+    );
+    assert_eq!(
+        lines[6],
+        "http_req_duration,location=global avg=100,p(95)=150.5 1682942406000000000"
+    );
+}
+
+#[test]
+fn influx_tags_include_extra_path_segments() {
+    let mut interval = types::Interval::new();
+    interval.period = 2;
+    interval.sinks.insert(
+        "us-east1::checks::check::status is 200, ok".to_string(),
+        sink(types::SinkType::Rate, &[("pass", 9.0), ("rate", f64::NAN)]),
+    );
+    interval.sinks.insert(
+        "us-east1::empty".to_string(),
+        sink(types::SinkType::Gauge, &[]),
+    );
+
+    let text = intervals_to_influx(&[interval], Utc.timestamp_opt(0, 0).unwrap(), 6).unwrap();
+
+    assert_eq!(
+        text,
+        "checks,check=status\\ is\\ 200\\,\\ ok,location=us-east1 pass=9 12000000000\n"
+    );
+}
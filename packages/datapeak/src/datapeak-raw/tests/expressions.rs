@@ -0,0 +1,211 @@
+//! Tests for evaluating series expressions.
+
+mod common;
+
+use common::sink;
+use datapeak::{
+    intervals::{
+        expressions::evaluate_expression, time_series::DownsizingMethod, window::Window,
+        INTERVAL_PERIOD_SECONDS,
+    },
+    manager::TestInfoManager,
+    types,
+};
+
+// Cumulative request and failure counts in two zones, with eu-west1 failing
+// twice as often
+fn intervals(periods: &[i32]) -> Vec<types::Interval> {
+    periods
+        .iter()
+        .map(|period| {
+            let mut interval = types::Interval::new();
+            interval.period = *period;
+
+            let count = 60.0 * (period + 1) as f64;
+
+            for (location, failures) in [("global", 3.0), ("us-east1", 1.0), ("eu-west1", 2.0)] {
+                interval.sinks.insert(
+                    format!("{}::http_reqs", location),
+                    sink(types::SinkType::Counter, &[("count", count)]),
+                );
+                interval.sinks.insert(
+                    format!("{}::http_req_failed", location),
+                    sink(
+                        types::SinkType::Rate,
+                        &[("pass", failures * (period + 1) as f64), ("total", count)],
+                    ),
+                );
+                interval.sinks.insert(
+                    format!("{}::http_req_duration", location),
+                    sink(types::SinkType::Trend, &[("p(95)", 100.0 * failures)]),
+                );
+            }
+
+            interval
+        })
+        .collect()
+}
+
+fn values(intervals: &[types::Interval], expression: &str) -> Vec<f64> {
+    evaluate_expression(intervals, expression, INTERVAL_PERIOD_SECONDS)
+        .unwrap()
+        .into_iter()
+        .map(|x| x.1)
+        .collect()
+}
+
+#[test]
+fn bare_paths_are_global() {
+    let intervals = intervals(&[0, 1, 2]);
+
+    assert_eq!(
+        values(&intervals, "http_req_failed::pass / http_reqs::count"),
+        vec![0.05, 0.05, 0.05]
+    );
+    assert_eq!(
+        values(&intervals, "eu-west1::http_req_failed::pass"),
+        vec![2.0, 4.0, 6.0]
+    );
+}
+
+#[test]
+fn evaluates_arithmetic_with_precedence() {
+    let intervals = intervals(&[0, 1]);
+
+    assert_eq!(
+        values(
+            &intervals,
+            "us-east1::http_req_duration::p(95) - eu-west1::http_req_duration::p(95)"
+        ),
+        vec![-100.0, -100.0]
+    );
+    assert_eq!(
+        values(&intervals, "abs(-(1 + 2) * http_req_duration::p(95) / 100)"),
+        vec![9.0, 9.0]
+    );
+}
+
+#[test]
+fn rate_and_delta_use_the_previous_interval() {
+    // Period 2 is missing, so its change is spread over two periods
+    let intervals = intervals(&[0, 1, 3]);
+
+    let rate = values(&intervals, "rate(http_reqs::count)");
+    assert!(rate[0].is_nan());
+    assert_eq!(rate[1..], [10.0, 10.0]);
+
+    let delta = values(&intervals, "delta(http_reqs::count)");
+    assert!(delta[0].is_nan());
+    assert_eq!(delta[1..], [60.0, 120.0]);
+}
+
+#[test]
+fn division_by_zero_and_missing_values_are_nan() {
+    let mut intervals = intervals(&[0, 1]);
+    intervals[1].sinks.remove("us-east1::http_reqs");
+
+    let data = values(&intervals, "us-east1::http_reqs::count / 0");
+    assert!(data.iter().all(|x| x.is_nan()));
+
+    let data = values(&intervals, "us-east1::http_reqs::count");
+    assert_eq!(data[0], 60.0);
+    assert!(data[1].is_nan());
+}
+
+#[test]
+fn invalid_expressions_are_errors() {
+    let intervals = intervals(&[0, 1]);
+
+    for expression in [
+        "",
+        "http_reqs",
+        "iterations::count",
+        "median(http_reqs::count)",
+        "(http_reqs::count",
+        "http_reqs::count http_reqs::count",
+        "http_reqs::count * / 2",
+        "http_reqs::count % 2",
+    ] {
+        assert!(
+            evaluate_expression(&intervals, expression, INTERVAL_PERIOD_SECONDS).is_err(),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn expression_series_are_named_and_downsized() {
+    let mut manager = TestInfoManager::new(None);
+    for interval in intervals(&[0, 1, 2, 3, 4]).iter() {
+        manager.process_interval(interval).unwrap();
+    }
+
+    let time_series = manager
+        .expression_time_series(
+            " http_req_failed::pass / http_reqs::count ",
+            DownsizingMethod::FixedIntervals {
+                max_data_points: Some(3),
+            },
+            &Window::default(),
+        )
+        .unwrap();
+
+    assert_eq!(time_series.name, "http_req_failed::pass / http_reqs::count");
+    assert_eq!(time_series.data.len(), 3);
+}
+
+#[test]
+fn expression_series_use_every_downsizing_method() {
+    let mut manager = TestInfoManager::new(None);
+    for interval in intervals(&[0, 1, 2, 3, 4]).iter() {
+        manager.process_interval(interval).unwrap();
+    }
+
+    let data = |downsizing_method: DownsizingMethod| {
+        manager
+            .expression_time_series(
+                "http_req_failed::pass / http_reqs::count",
+                downsizing_method,
+                &Window::default(),
+            )
+            .unwrap()
+            .data
+    };
+
+    assert_eq!(
+        data(DownsizingMethod::MovingMean {
+            window_size: Some(2),
+            max_data_points: None,
+        }),
+        vec![(1.0, 0.025), (2.0, 0.025), (3.0, 0.025), (4.0, 0.025)]
+    );
+    assert_eq!(
+        data(DownsizingMethod::MovingMean {
+            window_size: None,
+            max_data_points: None,
+        })
+        .len(),
+        5
+    );
+
+    for max_data_points in [0, 1] {
+        for downsizing_method in [
+            DownsizingMethod::FixedIntervals {
+                max_data_points: Some(max_data_points),
+            },
+            DownsizingMethod::MovingMean {
+                window_size: None,
+                max_data_points: Some(max_data_points),
+            },
+            DownsizingMethod::Lttb {
+                max_data_points: Some(max_data_points),
+            },
+            DownsizingMethod::MinMax {
+                max_data_points: Some(max_data_points),
+            },
+        ] {
+            assert_eq!(data(downsizing_method).len(), max_data_points);
+        }
+    }
+}
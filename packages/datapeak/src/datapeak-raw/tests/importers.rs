@@ -0,0 +1,260 @@
+//! Tests for importing results written by other tools.
+
+use datapeak::{
+    importers::{
+        jmeter::{import_jmeter_csv, JMeterImportOptions},
+        k6::{import_k6_json, K6ImportOptions},
+    },
+    manager::TestInfoManager,
+};
+
+const K6_JSON: &str = r#"{"type":"Metric","data":{"name":"http_reqs","type":"counter","contains":"default","thresholds":[],"submetrics":null},"metric":"http_reqs"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45.000Z","value":1,"tags":{"status":"200"}},"metric":"http_reqs"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:46.000Z","value":1,"tags":{"status":"200"}},"metric":"http_reqs"}
+{"type":"Metric","data":{"name":"http_req_duration","type":"trend","contains":"time","thresholds":["p(95)<500"],"submetrics":[{"name":"http_req_duration{status:200}","suffix":"status:200","tags":{"status":"200"}}]},"metric":"http_req_duration"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45.000Z","value":100,"tags":{"status":"200"}},"metric":"http_req_duration"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:46.000Z","value":300,"tags":{"status":"500"}},"metric":"http_req_duration"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:58.000Z","value":200,"tags":{"status":"200"}},"metric":"http_req_duration"}
+
+{"type":"Metric","data":{"name":"vus","type":"gauge","contains":"default","thresholds":[],"submetrics":null},"metric":"vus"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45.000Z","value":5,"tags":null},"metric":"vus"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:47.000Z","value":3,"tags":null},"metric":"vus"}
+{"type":"Metric","data":{"name":"checks","type":"rate","contains":"default","thresholds":[],"submetrics":null},"metric":"checks"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45.000Z","value":1,"tags":{"check":"status is 200","group":"::login","scenario":"default"}},"metric":"checks"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:46.000Z","value":0,"tags":{"check":"status is 200","group":"::login","scenario":"default"}},"metric":"checks"}
+"#;
+
+fn import() -> TestInfoManager {
+    import_k6_json(K6_JSON.as_bytes(), &K6ImportOptions::default()).unwrap()
+}
+
+fn label(manager: &TestInfoManager, period: usize, sink_name: &str, label: &str) -> f64 {
+    manager.intervals()[period].sinks[sink_name].labels[label]
+}
+
+#[test]
+fn k6_points_are_bucketed_into_intervals() {
+    let manager = import();
+
+    // The last point is 13 seconds after the first
+    let periods: Vec<i32> = manager.intervals().iter().map(|x| x.period).collect();
+    assert_eq!(periods, vec![0, 1, 2]);
+
+    assert_eq!(manager.locations(), &["local".to_string()]);
+
+    // Periods are counted from the first point
+    assert_eq!(
+        manager.start_time().unwrap().to_rfc3339(),
+        "2023-05-09T14:34:45+00:00"
+    );
+    assert_eq!(manager.interval_seconds(), 6);
+}
+
+#[test]
+fn k6_sinks_have_streamed_labels() {
+    let manager = import();
+
+    assert_eq!(label(&manager, 0, "local::http_reqs", "count"), 2.0);
+    // Counters are carried through periods without points
+    assert_eq!(label(&manager, 2, "local::http_reqs", "count"), 2.0);
+
+    assert_eq!(label(&manager, 0, "local::vus", "value"), 3.0);
+    assert_eq!(label(&manager, 0, "local::vus", "max"), 5.0);
+
+    let duration = &manager.intervals()[0].sinks["local::http_req_duration"];
+    assert!(duration.sketch.is_some());
+    assert!((duration.labels["avg"] - 200.0).abs() < 1e-9);
+
+    assert_eq!(label(&manager, 0, "local::checks", "pass"), 1.0);
+    assert_eq!(label(&manager, 0, "local::checks", "total"), 2.0);
+}
+
+#[test]
+fn k6_checks_and_submetrics_get_their_own_sinks() {
+    let manager = import();
+    let sinks = &manager.intervals()[0].sinks;
+
+    assert!(sinks.contains_key("local::checks::check::status is 200"));
+    assert!(sinks.contains_key("local::checks::group::login"));
+    assert!(sinks.contains_key("local::checks::scenario::default"));
+    assert_eq!(
+        sinks["local::http_req_duration::status::200"].labels["count"],
+        1.0
+    );
+
+    let checks = manager.checks();
+    assert_eq!(checks.named.len(), 1);
+    assert_eq!(checks.named[0].rates["local"].fail, 1);
+}
+
+#[test]
+fn k6_thresholds_are_evaluated() {
+    let manager = import();
+
+    assert_eq!(manager.thresholds().len(), 1);
+    assert_eq!(manager.thresholds()[0].source, "p(95)<500");
+    assert!(!manager.threshold_results().is_empty());
+    assert!(manager.threshold_results().iter().all(|x| x.passed));
+}
+
+#[test]
+fn k6_load_zone_sets_location() {
+    let ndjson = r#"{"type":"Metric","data":{"type":"counter"},"metric":"iterations"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45Z","value":1,"tags":{"load_zone":"eu-west1"}},"metric":"iterations"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45Z","value":1,"tags":{}},"metric":"iterations"}
+"#;
+
+    let options = K6ImportOptions {
+        location: "us-east1".to_string(),
+    };
+    let manager = import_k6_json(ndjson.as_bytes(), &options).unwrap();
+
+    let sinks = &manager.intervals()[0].sinks;
+    assert!(sinks.contains_key("eu-west1::iterations"));
+    assert!(sinks.contains_key("us-east1::iterations"));
+    assert_eq!(sinks["global::iterations"].labels["count"], 2.0);
+}
+
+#[test]
+fn k6_points_out_of_time_order_are_bucketed_by_their_time() {
+    let lines: Vec<&str> = K6_JSON.lines().collect();
+
+    // Metric lines come first, then points out of time order, including the
+    // two vus points in the first interval
+    let reordered: Vec<&str> = vec![0, 3, 8, 11, 4, 6, 10, 9, 5, 2, 1, 13, 12]
+        .into_iter()
+        .map(|index| lines[index])
+        .collect();
+
+    let reordered =
+        import_k6_json(reordered.join("\n").as_bytes(), &K6ImportOptions::default()).unwrap();
+    let manager = import();
+
+    assert_eq!(reordered.start_time(), manager.start_time());
+    assert_eq!(reordered.intervals(), manager.intervals());
+    assert_eq!(label(&reordered, 0, "local::vus", "value"), 3.0);
+}
+
+#[test]
+fn k6_points_before_the_first_read_start_the_run() {
+    let ndjson = r#"{"type":"Metric","data":{"type":"counter"},"metric":"iterations"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:57Z","value":1,"tags":{}},"metric":"iterations"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45Z","value":1,"tags":{}},"metric":"iterations"}
+"#;
+
+    let manager = import_k6_json(ndjson.as_bytes(), &K6ImportOptions::default()).unwrap();
+
+    let periods: Vec<i32> = manager.intervals().iter().map(|x| x.period).collect();
+    assert_eq!(periods, vec![0, 1, 2]);
+    assert_eq!(
+        manager.start_time().unwrap().to_rfc3339(),
+        "2023-05-09T14:34:45+00:00"
+    );
+    assert_eq!(label(&manager, 0, "local::iterations", "count"), 1.0);
+    assert_eq!(label(&manager, 2, "local::iterations", "count"), 2.0);
+}
+
+#[test]
+fn k6_metrics_cannot_change_type() {
+    let ndjson = r#"{"type":"Metric","data":{"type":"counter"},"metric":"iterations"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:45Z","value":1,"tags":{}},"metric":"iterations"}
+{"type":"Metric","data":{"type":"gauge"},"metric":"iterations"}
+{"type":"Point","data":{"time":"2023-05-09T14:34:57Z","value":1,"tags":{}},"metric":"iterations"}
+"#;
+
+    match import_k6_json(ndjson.as_bytes(), &K6ImportOptions::default()) {
+        Err(err) => assert!(err.contains("different types")),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn k6_invalid_lines_are_rejected() {
+    let unknown_metric =
+        r#"{"type":"Point","data":{"time":"2023-05-09T14:34:45Z","value":1},"metric":"vus"}"#;
+    assert!(import_k6_json(unknown_metric.as_bytes(), &K6ImportOptions::default()).is_err());
+
+    match import_k6_json("{}\n".as_bytes(), &K6ImportOptions::default()) {
+        Err(err) => assert!(err.contains("line 1")),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+const JMETER_CSV: &str = "timeStamp,elapsed,label,responseCode,responseMessage,threadName,dataType,success,failureMessage,bytes,sentBytes,grpThreads,allThreads,URL,Latency,IdleTime,Connect
+1683643200000,120,Login,200,OK,Thread Group 1-1,text,true,,512,128,2,2,http://example.com/login,110,0,30
+1683643201000,80,Home,200,OK,Thread Group 1-2,text,true,,1024,96,2,2,http://example.com/,70,0,20
+1683643202500,300,Login,500,\"Internal, Server Error\",Thread Group 1-1,text,false,\"Expected \"\"200\"\"\",256,128,2,2,http://example.com/login,290,0,25
+1683643207000,100,Home,200,OK,Thread Group 1-2,text,true,,1024,96,1,1,http://example.com/,90,0,20
+";
+
+fn import_jmeter() -> TestInfoManager {
+    import_jmeter_csv(JMETER_CSV.as_bytes(), &JMeterImportOptions::default()).unwrap()
+}
+
+#[test]
+fn jmeter_samples_are_bucketed_into_intervals() {
+    let manager = import_jmeter();
+
+    let periods: Vec<i32> = manager.intervals().iter().map(|x| x.period).collect();
+    assert_eq!(periods, vec![0, 1]);
+
+    assert_eq!(label(&manager, 0, "local::http_reqs", "count"), 3.0);
+    assert_eq!(label(&manager, 1, "local::http_reqs", "count"), 4.0);
+    assert_eq!(label(&manager, 0, "local::data_received", "count"), 1792.0);
+    assert_eq!(label(&manager, 1, "local::vus", "value"), 1.0);
+}
+
+#[test]
+fn jmeter_labels_get_trend_and_error_rate_sinks() {
+    let manager = import_jmeter();
+
+    let duration = &manager.intervals()[0].sinks["local::http_req_duration::label::Login"];
+    assert!(duration.sketch.is_some());
+    assert_eq!(duration.labels["count"], 2.0);
+
+    assert_eq!(label(&manager, 0, "local::http_req_failed", "pass"), 1.0);
+    assert_eq!(label(&manager, 0, "local::http_req_failed", "total"), 3.0);
+    assert_eq!(
+        label(&manager, 0, "local::http_req_failed::label::Login", "rate"),
+        0.5
+    );
+    assert_eq!(
+        label(&manager, 0, "global::http_reqs::label::Home", "count"),
+        1.0
+    );
+}
+
+#[test]
+fn jmeter_files_need_a_header() {
+    let headerless = "1683643200000,120,Login,200,OK,Thread Group 1-1,text,true\n";
+    assert!(import_jmeter_csv(headerless.as_bytes(), &JMeterImportOptions::default()).is_err());
+
+    let invalid_elapsed = "timeStamp,elapsed,label,success\n1683643200000,slow,Login,true\n";
+    match import_jmeter_csv(invalid_elapsed.as_bytes(), &JMeterImportOptions::default()) {
+        Err(err) => assert!(err.contains("row 2")),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn jmeter_quoted_cells_can_span_lines() {
+    let csv = "timeStamp,elapsed,label,success,failureMessage\r
+1683643200000,120,Login,false,\"Expected \"\"200\"\"\r
+got 500\"\r
+\r
+1683643201000,slow,Home,true,\r
+";
+    let reader = std::io::BufReader::with_capacity(8, csv.as_bytes());
+
+    // The blank line and the line inside the quoted cell aren't counted as rows
+    match import_jmeter_csv(reader, &JMeterImportOptions::default()) {
+        Err(err) => assert!(err.contains("row 3")),
+        Ok(_) => panic!("Expected an error"),
+    }
+
+    let unterminated = "timeStamp,elapsed,label,success\n1683643200000,120,\"Login\n";
+    match import_jmeter_csv(unterminated.as_bytes(), &JMeterImportOptions::default()) {
+        Err(err) => assert_eq!(err, "Unterminated quoted cell"),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
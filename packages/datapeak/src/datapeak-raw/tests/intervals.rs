@@ -0,0 +1,136 @@
+//! Tests for intervals that arrive late, out of order or more than once.
+
+mod common;
+
+use common::{interval, sink};
+use datapeak::{manager::TestInfoManager, states::Topic, types};
+
+fn counter_interval(period: i32, count: f64, check_pass: f64) -> types::Interval {
+    interval(
+        period,
+        vec![
+            (
+                "us-east1::http_reqs",
+                sink(types::SinkType::Counter, &[("count", count)]),
+            ),
+            (
+                "us-east1::checks::check::status is 200",
+                sink(
+                    types::SinkType::Rate,
+                    &[
+                        ("pass", check_pass),
+                        ("total", 10.0),
+                        ("rate", check_pass / 10.0),
+                    ],
+                ),
+            ),
+        ],
+    )
+}
+
+fn label(manager: &TestInfoManager, period: i32, sink_name: &str, label: &str) -> f64 {
+    manager
+        .test_info
+        .intervals
+        .iter()
+        .find(|x| x.period == period)
+        .and_then(|x| x.sinks.get(sink_name))
+        .and_then(|x| x.labels.get(label))
+        .copied()
+        .unwrap()
+}
+
+#[test]
+fn rate_uses_previous_count() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&counter_interval(0, 60.0, 10.0)).unwrap();
+    manager.process_interval(&counter_interval(1, 90.0, 10.0)).unwrap();
+
+    assert_eq!(label(&manager, 0, "us-east1::http_reqs", "rate"), 10.0);
+    assert_eq!(label(&manager, 1, "us-east1::http_reqs", "rate"), 5.0);
+    assert_eq!(label(&manager, 1, "global::http_reqs", "rate"), 5.0);
+}
+
+#[test]
+fn rate_spans_missing_periods() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&counter_interval(0, 60.0, 10.0)).unwrap();
+    manager.process_interval(&counter_interval(2, 120.0, 10.0)).unwrap();
+
+    assert_eq!(label(&manager, 2, "us-east1::http_reqs", "rate"), 5.0);
+}
+
+#[test]
+fn late_interval_recalculates_following_rate() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&counter_interval(0, 60.0, 10.0)).unwrap();
+    manager.process_interval(&counter_interval(2, 120.0, 10.0)).unwrap();
+    let version = manager.version;
+
+    manager.process_interval(&counter_interval(1, 108.0, 10.0)).unwrap();
+
+    let periods: Vec<i32> = manager.test_info.intervals.iter().map(|x| x.period).collect();
+    assert_eq!(periods, vec![0, 1, 2]);
+    assert_eq!(manager.latest_period, 2);
+
+    assert_eq!(label(&manager, 1, "us-east1::http_reqs", "rate"), 8.0);
+    assert_eq!(label(&manager, 2, "us-east1::http_reqs", "rate"), 2.0);
+    assert_eq!(label(&manager, 2, "global::http_reqs", "rate"), 2.0);
+
+    // Both the late interval and the one following it are reported as changed
+    let changed: Vec<i32> = manager
+        .changes_since(version)
+        .intervals
+        .iter()
+        .map(|x| x.period)
+        .collect();
+    assert_eq!(changed, vec![1, 2]);
+}
+
+#[test]
+fn first_interval_arriving_last_is_reconciled() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&counter_interval(1, 120.0, 10.0)).unwrap();
+    assert_eq!(label(&manager, 1, "us-east1::http_reqs", "rate"), 10.0);
+
+    manager.process_interval(&counter_interval(0, 84.0, 10.0)).unwrap();
+    assert_eq!(label(&manager, 0, "us-east1::http_reqs", "rate"), 14.0);
+    assert_eq!(label(&manager, 1, "us-east1::http_reqs", "rate"), 6.0);
+}
+
+#[test]
+fn replayed_latest_interval_refreshes_checks_and_summary() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&counter_interval(0, 60.0, 10.0)).unwrap();
+    manager.process_interval(&counter_interval(1, 90.0, 10.0)).unwrap();
+    let checks_version = manager.topic_version(Topic::Checks);
+    let summary_version = manager.topic_version(Topic::Summary);
+
+    manager.process_interval(&counter_interval(1, 96.0, 4.0)).unwrap();
+
+    assert!(manager.topic_version(Topic::Checks) > checks_version);
+    assert!(manager.topic_version(Topic::Summary) > summary_version);
+    assert_eq!(manager.checks.named[0].rates["us-east1"].fail, 6);
+
+    let summary = manager.test_info.summary.as_ref().unwrap();
+    assert_eq!(summary.sinks["us-east1::http_reqs"].labels["count"], 96.0);
+}
+
+#[test]
+fn late_interval_leaves_checks_of_latest_interval() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&counter_interval(0, 60.0, 10.0)).unwrap();
+    manager.process_interval(&counter_interval(2, 120.0, 10.0)).unwrap();
+    let checks_version = manager.topic_version(Topic::Checks);
+
+    manager.process_interval(&counter_interval(1, 90.0, 2.0)).unwrap();
+
+    assert_eq!(manager.topic_version(Topic::Checks), checks_version);
+    assert_eq!(manager.checks.named[0].rates["us-east1"].fail, 0);
+}
@@ -0,0 +1,252 @@
+//! Tests for the global sinks that combine every location.
+
+mod common;
+
+use common::{interval, sink};
+use datapeak::{
+    intervals::merge::{add_global_sinks, merge_location_sinks},
+    types,
+};
+
+fn sketched_trend(values: impl Iterator<Item = f64>) -> types::Sink {
+    let mut sketch = types::Sketch::with_relative_accuracy(0.01);
+    for value in values {
+        sketch.add(value);
+    }
+
+    let mut sink = sink(types::SinkType::Trend, &[]);
+    sink.labels = sketch.trend_labels().into_iter().collect();
+    sink.sketch = protobuf::MessageField::some(sketch);
+    sink
+}
+
+fn global_interval(sinks: Vec<(&str, types::Sink)>) -> types::Interval {
+    let mut interval = interval(0, sinks);
+    add_global_sinks(&mut interval).unwrap();
+    interval
+}
+
+fn label(interval: &types::Interval, sink_name: &str, label: &str) -> f64 {
+    interval.sinks[sink_name].labels[label]
+}
+
+#[test]
+fn counters_are_summed_across_locations() {
+    let interval = global_interval(vec![
+        (
+            "us-east1::http_reqs",
+            sink(
+                types::SinkType::Counter,
+                &[("count", 60.0), ("rate", 10.0), ("mean", 5.0)],
+            ),
+        ),
+        (
+            "eu-west1::http_reqs",
+            sink(
+                types::SinkType::Counter,
+                &[("count", 30.0), ("rate", 5.0), ("mean", 2.5)],
+            ),
+        ),
+    ]);
+
+    assert_eq!(label(&interval, "global::http_reqs", "count"), 90.0);
+    assert_eq!(label(&interval, "global::http_reqs", "rate"), 15.0);
+    assert_eq!(label(&interval, "global::http_reqs", "mean"), 7.5);
+}
+
+#[test]
+fn gauges_are_summed_across_locations() {
+    let interval = global_interval(vec![
+        (
+            "us-east1::vus",
+            sink(
+                types::SinkType::Gauge,
+                &[("value", 10.0), ("min", 5.0), ("max", 12.0)],
+            ),
+        ),
+        (
+            "eu-west1::vus",
+            sink(
+                types::SinkType::Gauge,
+                &[("value", 20.0), ("min", 20.0), ("max", 25.0)],
+            ),
+        ),
+    ]);
+
+    // The min and max are summed like the value, so they still bound it
+    assert_eq!(label(&interval, "global::vus", "value"), 30.0);
+    assert_eq!(label(&interval, "global::vus", "min"), 25.0);
+    assert_eq!(label(&interval, "global::vus", "max"), 37.0);
+}
+
+#[test]
+fn rates_sum_pass_and_total_across_locations() {
+    let interval = global_interval(vec![
+        (
+            "us-east1::checks",
+            sink(
+                types::SinkType::Rate,
+                &[("pass", 9.0), ("total", 10.0), ("rate", 0.9)],
+            ),
+        ),
+        (
+            "eu-west1::checks",
+            sink(
+                types::SinkType::Rate,
+                &[("pass", 1.0), ("total", 10.0), ("rate", 0.1)],
+            ),
+        ),
+    ]);
+
+    assert_eq!(label(&interval, "global::checks", "pass"), 10.0);
+    assert_eq!(label(&interval, "global::checks", "fail"), 10.0);
+    assert_eq!(label(&interval, "global::checks", "total"), 20.0);
+    assert_eq!(label(&interval, "global::checks", "rate"), 0.5);
+}
+
+#[test]
+fn trend_averages_are_weighted_by_sample_count() {
+    let interval = global_interval(vec![
+        (
+            "us-east1::http_req_duration",
+            sink(
+                types::SinkType::Trend,
+                &[
+                    ("avg", 100.0),
+                    ("min", 10.0),
+                    ("max", 400.0),
+                    ("count", 1.0),
+                ],
+            ),
+        ),
+        (
+            "eu-west1::http_req_duration",
+            sink(
+                types::SinkType::Trend,
+                &[
+                    ("avg", 200.0),
+                    ("min", 20.0),
+                    ("max", 300.0),
+                    ("count", 3.0),
+                ],
+            ),
+        ),
+    ]);
+
+    assert_eq!(label(&interval, "global::http_req_duration", "avg"), 175.0);
+    assert_eq!(label(&interval, "global::http_req_duration", "min"), 10.0);
+    assert_eq!(label(&interval, "global::http_req_duration", "max"), 400.0);
+    assert_eq!(label(&interval, "global::http_req_duration", "count"), 4.0);
+}
+
+#[test]
+fn trends_without_counts_are_weighted_equally() {
+    let us = sink(types::SinkType::Trend, &[("avg", 100.0), ("p(95)", 300.0)]);
+    let eu = sink(types::SinkType::Trend, &[("avg", 200.0), ("p(95)", 500.0)]);
+
+    let merged = merge_location_sinks(&[&us, &eu]).unwrap();
+
+    assert_eq!(merged.labels["avg"], 150.0);
+    assert_eq!(merged.labels["p(95)"], 400.0);
+    assert!(merged.sketch.is_none());
+}
+
+#[test]
+fn trends_without_counts_are_weighted_by_their_sketches() {
+    let mut us = sketched_trend((1..=30).map(|_| 100.0));
+    us.labels.remove("count");
+    us.labels.insert("p(95)".to_string(), 100.0);
+    let eu = sink(types::SinkType::Trend, &[("avg", 200.0), ("p(95)", 200.0)]);
+
+    // The sketch holds 30 samples, against a weight of one without a sketch
+    let merged = merge_location_sinks(&[&us, &eu]).unwrap();
+
+    assert!(merged.sketch.is_none());
+    assert!((merged.labels["p(95)"] - (3000.0 + 200.0) / 31.0).abs() < 1e-9);
+}
+
+#[test]
+fn trends_are_merged_through_their_sketches() {
+    let us = sketched_trend((1..=50).map(|x| x as f64));
+    let eu = sketched_trend((51..=100).map(|x| x as f64));
+
+    let merged = merge_location_sinks(&[&us, &eu]).unwrap();
+
+    assert_eq!(merged.sketch.as_ref().unwrap().count, 100.0);
+    assert_eq!(merged.labels["min"], 1.0);
+    assert_eq!(merged.labels["max"], 100.0);
+    assert_eq!(merged.labels["avg"], 50.5);
+    assert!((merged.labels["p(95)"] - 95.0).abs() <= 0.95);
+}
+
+#[test]
+fn trends_fall_back_to_labels_unless_every_sink_has_a_sketch() {
+    let us = sketched_trend((1..=50).map(|x| x as f64));
+    let eu = sink(
+        types::SinkType::Trend,
+        &[
+            ("avg", 300.0),
+            ("min", 200.0),
+            ("max", 400.0),
+            ("count", 50.0),
+        ],
+    );
+
+    let merged = merge_location_sinks(&[&us, &eu]).unwrap();
+
+    assert!(merged.sketch.is_none());
+    assert_eq!(merged.labels["avg"], (25.5 + 300.0) / 2.0);
+    assert_eq!(merged.labels["min"], 1.0);
+    assert_eq!(merged.labels["max"], 400.0);
+    assert_eq!(merged.labels["count"], 100.0);
+}
+
+#[test]
+fn existing_global_sinks_are_replaced() {
+    let mut interval = types::Interval::new();
+    interval.sinks.insert(
+        "us-east1::vus".to_string(),
+        sink(types::SinkType::Gauge, &[("value", 10.0)]),
+    );
+    interval.sinks.insert(
+        "global::vus".to_string(),
+        sink(types::SinkType::Gauge, &[("value", 99.0)]),
+    );
+    interval.sinks.insert(
+        "global::stale".to_string(),
+        sink(types::SinkType::Gauge, &[("value", 1.0)]),
+    );
+
+    add_global_sinks(&mut interval).unwrap();
+
+    assert_eq!(label(&interval, "global::vus", "value"), 10.0);
+    assert!(!interval.sinks.contains_key("global::stale"));
+}
+
+#[test]
+fn sinks_of_different_types_are_not_merged() {
+    let interval = global_interval(vec![
+        (
+            "us-east1::http_reqs",
+            sink(types::SinkType::Counter, &[("count", 1.0)]),
+        ),
+        (
+            "eu-west1::http_reqs",
+            sink(types::SinkType::Gauge, &[("value", 1.0)]),
+        ),
+        (
+            "eu-west1::vus",
+            sink(types::SinkType::Gauge, &[("value", 10.0)]),
+        ),
+    ]);
+
+    // Only the metric with mixed types is left without a global sink
+    assert!(!interval.sinks.contains_key("global::http_reqs"));
+    assert_eq!(label(&interval, "global::vus", "value"), 10.0);
+
+    let counter = sink(types::SinkType::Counter, &[("count", 1.0)]);
+    let gauge = sink(types::SinkType::Gauge, &[("value", 1.0)]);
+
+    assert!(merge_location_sinks(&[&counter, &gauge]).is_err());
+    assert!(merge_location_sinks(&[]).is_err());
+}
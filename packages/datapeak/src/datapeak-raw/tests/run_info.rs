@@ -0,0 +1,228 @@
+//! Tests for the run start time and interval width.
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::sink;
+use datapeak::{
+    exporters::csv::CsvOptions,
+    intervals::{time_series::DownsizingMethod, window::Window},
+    manager::TestInfoManager,
+    types,
+};
+use protobuf::Message;
+
+// An interval with 60 more requests than the previous one
+fn interval(period: i32) -> types::Interval {
+    common::interval(
+        period,
+        vec![(
+            "us-east1::http_reqs",
+            sink(
+                types::SinkType::Counter,
+                &[("count", 60.0 * (period + 1) as f64)],
+            ),
+        )],
+    )
+}
+
+// Streams the run info ahead of the intervals, as the worker does
+fn streamed_manager(interval_seconds: u32) -> TestInfoManager {
+    let mut start_time = protobuf::well_known_types::timestamp::Timestamp::new();
+    start_time.seconds = Utc
+        .with_ymd_and_hms(2023, 5, 1, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+
+    let mut run_info = types::RunInfo::new();
+    run_info.start_time = protobuf::MessageField::some(start_time);
+    run_info.interval_seconds = interval_seconds;
+
+    let mut streamed_data = types::StreamedData::new();
+
+    let mut data_point = types::DataPoint::new();
+    data_point.set_run_info(run_info);
+    streamed_data.data_points.push(data_point);
+
+    for period in 0..3 {
+        let mut data_point = types::DataPoint::new();
+        data_point.set_interval(interval(period));
+        streamed_data.data_points.push(data_point);
+    }
+
+    let mut manager = TestInfoManager::new(None);
+    manager
+        .add_streamed_data(streamed_data.write_to_bytes().unwrap())
+        .unwrap();
+    manager
+}
+
+fn rates(manager: &TestInfoManager) -> Vec<f64> {
+    manager
+        .intervals()
+        .iter()
+        .map(|x| x.sinks["global::http_reqs"].labels["rate"])
+        .collect()
+}
+
+#[test]
+fn runs_without_run_info_use_6_second_periods() {
+    let mut manager = TestInfoManager::new(None);
+    manager.process_interval(&interval(0)).unwrap();
+
+    assert_eq!(manager.start_time(), None);
+    assert_eq!(manager.interval_seconds(), 6);
+    assert_eq!(rates(&manager), vec![10.0]);
+}
+
+#[test]
+fn counter_rates_use_the_interval_width() {
+    let manager = streamed_manager(10);
+
+    assert_eq!(manager.interval_seconds(), 10);
+    assert_eq!(rates(&manager), vec![6.0, 6.0, 6.0]);
+    assert_eq!(
+        manager.summary().unwrap().sinks["global::http_reqs"].labels["rate"],
+        6.0
+    );
+}
+
+#[test]
+fn changing_the_width_recalculates_received_intervals() {
+    let mut manager = TestInfoManager::new(None);
+    for period in 0..3 {
+        manager.process_interval(&interval(period)).unwrap();
+    }
+    assert_eq!(rates(&manager), vec![10.0, 10.0, 10.0]);
+
+    manager.set_run_info(None, Some(20)).unwrap();
+    assert_eq!(rates(&manager), vec![3.0, 3.0, 3.0]);
+    assert_eq!(
+        manager.summary().unwrap().sinks["global::http_reqs"].labels["rate"],
+        3.0
+    );
+
+    assert!(manager.set_run_info(None, Some(0)).is_err());
+}
+
+#[test]
+fn intervals_are_kept_if_they_cant_be_recalculated() {
+    let mut test_info = types::TestInfo::new();
+    test_info.intervals = vec![interval(0), interval(1)];
+
+    // Saved without the count that the rate is calculated from
+    let mut invalid_interval = interval(2);
+    invalid_interval
+        .sinks
+        .get_mut("us-east1::http_reqs")
+        .unwrap()
+        .labels
+        .remove("count");
+    test_info.intervals.push(invalid_interval);
+
+    let mut manager = TestInfoManager::new(Some(&test_info));
+    let intervals = manager.intervals().to_vec();
+
+    assert!(manager.set_run_info(None, Some(20)).is_err());
+    assert_eq!(manager.interval_seconds(), 6);
+    assert_eq!(manager.intervals(), intervals.as_slice());
+}
+
+#[test]
+fn time_series_have_timestamps_alongside_periods() {
+    let manager = streamed_manager(10);
+    let start_millis = manager.start_time().unwrap().timestamp_millis() as f64;
+
+    let time_series = manager
+        .interval_time_series("global::http_reqs::count", DownsizingMethod::None)
+        .unwrap();
+
+    assert_eq!(
+        time_series.data,
+        vec![(0.0, 60.0), (1.0, 120.0), (2.0, 180.0)]
+    );
+    assert_eq!(
+        time_series.timestamps,
+        Some(vec![
+            start_millis,
+            start_millis + 10_000.0,
+            start_millis + 20_000.0
+        ])
+    );
+
+    let time_series = manager
+        .expression_time_series(
+            "http_reqs::count",
+            DownsizingMethod::None,
+            &Window::new(Some(1), None),
+        )
+        .unwrap();
+
+    assert_eq!(time_series.data, vec![(1.0, 120.0), (2.0, 180.0)]);
+    assert_eq!(
+        time_series.timestamps,
+        Some(vec![start_millis + 10_000.0, start_millis + 20_000.0])
+    );
+}
+
+#[test]
+fn time_series_have_no_timestamps_without_a_start_time() {
+    let mut manager = TestInfoManager::new(None);
+    manager.process_interval(&interval(0)).unwrap();
+
+    let time_series = manager
+        .interval_time_series("global::http_reqs::count", DownsizingMethod::None)
+        .unwrap();
+
+    assert_eq!(time_series.data, vec![(0.0, 60.0)]);
+    assert_eq!(time_series.timestamps, None);
+}
+
+#[test]
+fn exports_use_the_recorded_start_time() {
+    let manager = streamed_manager(10);
+
+    let csv = manager.export_csv(&CsvOptions {
+        columns: vec!["http_reqs::count".to_string()],
+        ..CsvOptions::default()
+    });
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "period,timestamp,location,http_reqs::count");
+    assert_eq!(lines[3], "1,2023-05-01T12:00:10.000Z,global,120");
+
+    let text = manager.export_influx(None).unwrap();
+    assert!(text.ends_with(" 1682942420000000000\n"));
+
+    assert!(TestInfoManager::new(None).export_influx(None).is_err());
+}
+
+#[test]
+fn windows_can_be_given_as_times() {
+    let manager = streamed_manager(10);
+    let start_time = manager.start_time().unwrap();
+
+    // Only period 1 lies wholly between 5 and 25 seconds
+    let window = Window {
+        start_time: Some(start_time + chrono::Duration::seconds(5)),
+        end_time: Some(start_time + chrono::Duration::seconds(25)),
+        ..Window::default()
+    };
+
+    let summary = manager.window_summary(&window).unwrap().unwrap();
+    assert_eq!(summary.period, 1);
+    assert_eq!(summary.sinks["global::http_reqs"].labels["count"], 60.0);
+
+    assert!(TestInfoManager::new(None).window_summary(&window).is_err());
+}
+
+#[test]
+fn run_info_is_saved_with_the_test_info() {
+    let manager = streamed_manager(10);
+
+    let loaded = TestInfoManager::from_bytes(&manager.to_bytes().unwrap()).unwrap();
+
+    assert_eq!(loaded.start_time(), manager.start_time());
+    assert_eq!(loaded.interval_seconds(), 10);
+    assert_eq!(rates(&loaded), rates(&manager));
+}
@@ -0,0 +1,150 @@
+//! Tests for the DDSketch used to merge trend percentiles.
+
+use datapeak::{manager::TestInfoManager, sketch::sink_label_value, types};
+
+const RELATIVE_ACCURACY: f64 = 0.01;
+
+fn sketch(values: impl Iterator<Item = f64>) -> types::Sketch {
+    let mut sketch = types::Sketch::with_relative_accuracy(RELATIVE_ACCURACY);
+    for value in values {
+        sketch.add(value);
+    }
+    sketch
+}
+
+fn trend_sink(sketch: types::Sketch, p95: f64) -> types::Sink {
+    let mut sink = types::Sink::new();
+    sink.type_ = types::SinkType::Trend.into();
+    sink.labels.insert("p(95)".to_string(), p95);
+    sink.sketch = protobuf::MessageField::some(sketch);
+    sink
+}
+
+fn assert_within_accuracy(estimate: f64, exact: f64) {
+    assert!(
+        (estimate - exact).abs() <= RELATIVE_ACCURACY * exact.abs(),
+        "{} is not within {} of {}",
+        estimate,
+        RELATIVE_ACCURACY,
+        exact
+    );
+}
+
+#[test]
+fn add_tracks_count_sum_and_extremes() {
+    let sketch = sketch(vec![3.0, -2.0, 0.0, 10.0].into_iter());
+
+    assert_eq!(sketch.count, 4.0);
+    assert_eq!(sketch.sum, 11.0);
+    assert_eq!(sketch.min, -2.0);
+    assert_eq!(sketch.max, 10.0);
+    assert_eq!(sketch.zero_count, 1.0);
+    assert_eq!(sketch.positive_bins.values().sum::<f64>(), 2.0);
+    assert_eq!(sketch.negative_bins.values().sum::<f64>(), 1.0);
+}
+
+#[test]
+fn quantiles_are_within_the_relative_accuracy() {
+    let values: Vec<f64> = (1..=1000).map(|x| x as f64 * 1.7).collect();
+    let sketch = sketch(values.iter().copied());
+
+    for quantile in [0.0, 0.25, 0.5, 0.9, 0.95, 0.99, 1.0].iter() {
+        let exact = values[(quantile * (values.len() - 1) as f64) as usize];
+        assert_within_accuracy(sketch.quantile(*quantile).unwrap(), exact);
+    }
+
+    assert_eq!(sketch.quantile(1.5), None);
+    assert_eq!(types::Sketch::new().quantile(0.5), None);
+}
+
+#[test]
+fn quantiles_of_negative_values_are_within_the_relative_accuracy() {
+    let values: Vec<f64> = (-500..=-1).map(|x| x as f64).collect();
+    let sketch = sketch(values.iter().copied());
+
+    for quantile in [0.1, 0.5, 0.9].iter() {
+        let exact = values[(quantile * (values.len() - 1) as f64) as usize];
+        assert_within_accuracy(sketch.quantile(*quantile).unwrap(), exact);
+    }
+}
+
+#[test]
+fn merge_matches_a_sketch_of_every_value() {
+    let mut merged = sketch((1..=500).map(|x| x as f64));
+    merged
+        .merge(&sketch((501..=1000).map(|x| x as f64)))
+        .unwrap();
+
+    let single = sketch((1..=1000).map(|x| x as f64));
+
+    assert_eq!(merged, single);
+    assert_within_accuracy(merged.quantile(0.95).unwrap(), 950.0);
+}
+
+#[test]
+fn merge_into_an_empty_sketch_copies_it() {
+    let other = sketch((1..=10).map(|x| x as f64));
+
+    let mut merged = types::Sketch::new();
+    merged.merge(&other).unwrap();
+
+    assert_eq!(merged, other);
+}
+
+#[test]
+fn merge_rejects_mismatched_accuracies() {
+    let mut merged = sketch((1..=10).map(|x| x as f64));
+
+    let mut other = types::Sketch::with_relative_accuracy(0.05);
+    other.add(5.0);
+
+    assert!(merged.merge(&other).is_err());
+    assert_eq!(merged.count, 10.0);
+}
+
+#[test]
+fn invalid_accuracies_are_rejected() {
+    for relative_accuracy in [0.0, 1.0, -0.5, f64::NAN].iter() {
+        let mut invalid = types::Sketch::with_relative_accuracy(*relative_accuracy);
+        invalid.count = 1.0;
+        invalid.positive_bins.insert(i32::MAX, 1.0);
+
+        assert!(!invalid.is_valid());
+        assert_eq!(invalid.quantile(0.5), None);
+
+        let mut merged = sketch((1..=10).map(|x| x as f64));
+        assert!(merged.merge(&invalid).is_err());
+    }
+}
+
+#[test]
+fn invalid_sketches_fall_back_to_the_labels() {
+    let mut invalid = types::Sketch::with_relative_accuracy(0.0);
+    invalid.add(100.0);
+
+    let sink = trend_sink(invalid, 250.0);
+    assert_eq!(sink_label_value(&sink, "p(95)"), Some(250.0));
+
+    let mut interval = types::Interval::new();
+    interval
+        .sinks
+        .insert("us-east1::http_req_duration".to_string(), sink);
+
+    let mut manager = TestInfoManager::new(None);
+    manager.process_interval(&interval).unwrap();
+
+    let stored = &manager.intervals()[0].sinks["us-east1::http_req_duration"];
+    assert!(stored.sketch.is_none());
+    assert_eq!(stored.labels["p(95)"], 250.0);
+
+    let global = &manager.intervals()[0].sinks["global::http_req_duration"];
+    assert_eq!(global.labels["p(95)"], 250.0);
+}
+
+#[test]
+fn valid_sketches_are_used_for_any_percentile() {
+    let sink = trend_sink(sketch((1..=100).map(|x| x as f64)), 0.0);
+
+    assert_within_accuracy(sink_label_value(&sink, "p(95)").unwrap(), 95.0);
+    assert_within_accuracy(sink_label_value(&sink, "p(99.9)").unwrap(), 99.0);
+}
@@ -0,0 +1,192 @@
+//! Tests that topic versions change exactly when the underlying data changes.
+
+mod common;
+
+use common::sink;
+use datapeak::{manager::TestInfoManager, states::Topic, types};
+use protobuf::Message;
+
+fn interval(period: i32, check_pass: f64) -> types::Interval {
+    common::interval(
+        period,
+        vec![
+            (
+                "us-east1::http_reqs",
+                sink(types::SinkType::Counter, &[("count", 10.0 * (period + 1) as f64)]),
+            ),
+            (
+                "us-east1::http_req_duration",
+                sink(
+                    types::SinkType::Trend,
+                    &[("avg", 100.0), ("min", 50.0), ("max", 150.0), ("p(95)", 140.0)],
+                ),
+            ),
+            (
+                "us-east1::checks::check::status is 200",
+                sink(
+                    types::SinkType::Rate,
+                    &[("pass", check_pass), ("total", 10.0), ("rate", check_pass / 10.0)],
+                ),
+            ),
+        ],
+    )
+}
+
+fn console_message(message: &str) -> types::ConsoleMessage {
+    let mut console_message = types::ConsoleMessage::new();
+    console_message.message = message.to_string();
+    console_message.level = "info".to_string();
+    console_message.count.insert("us-east1".to_string(), 1);
+    console_message
+}
+
+fn threshold(metric: &str, source: &str) -> types::Threshold {
+    let mut threshold = types::Threshold::new();
+    threshold.metric = metric.to_string();
+    threshold.source = source.to_string();
+    threshold
+}
+
+fn streamed_threshold(threshold: types::Threshold) -> Vec<u8> {
+    let mut data_point = types::DataPoint::new();
+    data_point.set_threshold(threshold);
+
+    let mut streamed_data = types::StreamedData::new();
+    streamed_data.data_points.push(data_point);
+    streamed_data.write_to_bytes().unwrap()
+}
+
+#[test]
+fn intervals_version_changes_with_new_intervals_only() {
+    let mut manager = TestInfoManager::new(None);
+    let initial = manager.topic_version(Topic::Intervals);
+
+    manager.process_interval(&interval(0, 10.0)).unwrap();
+    let after_first = manager.topic_version(Topic::Intervals);
+    assert!(after_first > initial);
+
+    // Replaying the same interval changes nothing
+    manager.process_interval(&interval(0, 10.0)).unwrap();
+    assert_eq!(manager.topic_version(Topic::Intervals), after_first);
+
+    manager.process_interval(&interval(1, 10.0)).unwrap();
+    assert!(manager.topic_version(Topic::Intervals) > after_first);
+    assert_eq!(manager.test_info.intervals.len(), 2);
+}
+
+#[test]
+fn checks_version_changes_when_check_rates_change() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&interval(0, 10.0)).unwrap();
+    let after_first = manager.topic_version(Topic::Checks);
+    assert!(after_first > 0);
+    assert_eq!(manager.checks.named.len(), 1);
+    assert_eq!(manager.checks.named[0].name, "status is 200");
+
+    // Same check results in the next interval
+    manager.process_interval(&interval(1, 10.0)).unwrap();
+    assert_eq!(manager.topic_version(Topic::Checks), after_first);
+
+    manager.process_interval(&interval(2, 7.0)).unwrap();
+    assert!(manager.topic_version(Topic::Checks) > after_first);
+    assert_eq!(manager.checks.named.len(), 1);
+    assert_eq!(manager.checks.named[0].rates["us-east1"].fail, 3);
+}
+
+#[test]
+fn summary_and_locations_versions_change_with_their_data() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&interval(0, 10.0)).unwrap();
+    let summary_version = manager.topic_version(Topic::Summary);
+    let locations_version = manager.topic_version(Topic::Locations);
+    assert!(summary_version > 0);
+    assert!(locations_version > 0);
+    assert_eq!(manager.locations, vec!["us-east1".to_string()]);
+    assert!(manager.test_info.summary.is_some());
+
+    manager.process_interval(&interval(1, 10.0)).unwrap();
+    assert!(manager.topic_version(Topic::Summary) > summary_version);
+    assert_eq!(manager.topic_version(Topic::Locations), locations_version);
+}
+
+#[test]
+fn console_messages_version_changes_with_each_message() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_console_message(&console_message("hello"));
+    let after_first = manager.topic_version(Topic::ConsoleMessages);
+    assert!(after_first > 0);
+
+    manager.process_console_message(&console_message("hello"));
+    assert!(manager.topic_version(Topic::ConsoleMessages) > after_first);
+    assert_eq!(manager.test_info.console_messages.len(), 1);
+    assert_eq!(manager.test_info.console_messages[0].count["us-east1"], 2);
+}
+
+#[test]
+fn thresholds_version_ignores_repeated_thresholds() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager
+        .add_streamed_data(streamed_threshold(threshold("http_req_duration", "avg<200")))
+        .unwrap();
+    let after_first = manager.topic_version(Topic::Thresholds);
+    assert!(after_first > 0);
+
+    manager
+        .add_streamed_data(streamed_threshold(threshold("http_req_duration", "avg<200")))
+        .unwrap();
+    assert_eq!(manager.topic_version(Topic::Thresholds), after_first);
+
+    manager.process_interval(&interval(0, 10.0)).unwrap();
+    assert!(manager.topic_version(Topic::Thresholds) > after_first);
+    assert!(manager.threshold_results.iter().all(|x| x.passed));
+}
+
+#[test]
+fn messages_version_changes_with_each_message() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.add_message("limit reached");
+    assert!(manager.topic_version(Topic::Messages) > 0);
+    assert_eq!(manager.test_info.messages, vec!["limit reached".to_string()]);
+}
+
+#[test]
+fn changes_since_only_returns_newer_data() {
+    let mut manager = TestInfoManager::new(None);
+
+    manager.process_interval(&interval(0, 10.0)).unwrap();
+    manager.process_console_message(&console_message("hello"));
+    let version = manager.version;
+
+    manager.process_interval(&interval(1, 10.0)).unwrap();
+    manager.add_message("limit reached");
+
+    let changes = manager.changes_since(version);
+    assert_eq!(changes.version, manager.version);
+    assert_eq!(changes.intervals.len(), 1);
+    assert_eq!(changes.intervals[0].period, 1);
+    assert!(changes.console_messages.is_empty());
+    assert_eq!(changes.messages, vec!["limit reached".to_string()]);
+
+    let everything = manager.changes_since(0);
+    assert_eq!(everything.intervals.len(), 2);
+    assert_eq!(everything.console_messages.len(), 1);
+}
+
+#[test]
+fn changed_topics_lists_topics_with_newer_versions() {
+    let mut manager = TestInfoManager::new(None);
+    let previous_versions = manager.topic_versions();
+
+    manager.add_message("limit reached");
+    manager.process_console_message(&console_message("hello"));
+
+    let changed_topics = manager.changed_topics(&previous_versions);
+    assert_eq!(changed_topics.len(), 2);
+    assert!(changed_topics.contains(&Topic::Messages));
+    assert!(changed_topics.contains(&Topic::ConsoleMessages));
+}
@@ -0,0 +1,179 @@
+//! Tests for the summary interval aggregated over the whole run.
+
+mod common;
+
+use common::{interval, sink};
+use datapeak::{intervals::summary::summarise_intervals, manager::TestInfoManager, types};
+
+fn summary_labels(manager: &TestInfoManager, sink_name: &str) -> Vec<(String, f64)> {
+    let sink = &manager.test_info.summary.as_ref().unwrap().sinks[sink_name];
+    let mut labels: Vec<(String, f64)> = sink
+        .labels
+        .iter()
+        .map(|(label, value)| (label.to_string(), *value))
+        .collect();
+    labels.sort_by(|a, b| a.0.cmp(&b.0));
+    labels
+}
+
+fn label(manager: &TestInfoManager, sink_name: &str, label: &str) -> f64 {
+    manager.test_info.summary.as_ref().unwrap().sinks[sink_name].labels[label]
+}
+
+#[test]
+fn counter_summary_is_the_latest_total() {
+    let mut manager = TestInfoManager::new(None);
+
+    for (period, count) in [(0, 10.0), (1, 25.0), (2, 40.0)] {
+        let counter = sink(types::SinkType::Counter, &[("count", count)]);
+        manager
+            .process_interval(&interval(period, vec![("us-east1::http_reqs", counter)]))
+            .unwrap();
+    }
+
+    assert_eq!(manager.test_info.summary.as_ref().unwrap().period, 2);
+    assert_eq!(label(&manager, "us-east1::http_reqs", "count"), 40.0);
+
+    // The rate is the mean over the whole run, not that of the last interval
+    let mean = label(&manager, "us-east1::http_reqs", "mean");
+    assert_eq!(label(&manager, "us-east1::http_reqs", "rate"), mean);
+    assert!((mean - 40.0 / 18.0).abs() < 1e-9);
+}
+
+#[test]
+fn gauge_summary_keeps_the_latest_value_and_extremes() {
+    let mut manager = TestInfoManager::new(None);
+
+    let gauges = vec![
+        sink(
+            types::SinkType::Gauge,
+            &[("value", 5.0), ("min", 1.0), ("max", 8.0)],
+        ),
+        sink(types::SinkType::Gauge, &[("value", 3.0)]),
+        sink(types::SinkType::Gauge, &[("value", 7.0)]),
+    ];
+
+    for (period, gauge) in gauges.into_iter().enumerate() {
+        manager
+            .process_interval(&interval(period as i32, vec![("us-east1::vus", gauge)]))
+            .unwrap();
+    }
+
+    assert_eq!(
+        summary_labels(&manager, "us-east1::vus"),
+        vec![
+            ("max".to_string(), 8.0),
+            ("min".to_string(), 1.0),
+            ("value".to_string(), 7.0),
+        ]
+    );
+}
+
+#[test]
+fn rate_summary_sums_pass_and_total() {
+    let mut manager = TestInfoManager::new(None);
+
+    for (period, pass) in [(0, 9.0), (1, 5.0)] {
+        let rate = sink(types::SinkType::Rate, &[("pass", pass), ("total", 10.0)]);
+        manager
+            .process_interval(&interval(period, vec![("us-east1::checks", rate)]))
+            .unwrap();
+    }
+
+    assert_eq!(label(&manager, "us-east1::checks", "pass"), 14.0);
+    assert_eq!(label(&manager, "us-east1::checks", "fail"), 6.0);
+    assert_eq!(label(&manager, "us-east1::checks", "total"), 20.0);
+    assert_eq!(label(&manager, "us-east1::checks", "rate"), 0.7);
+}
+
+#[test]
+fn trend_summary_combines_min_max_and_avg() {
+    let mut manager = TestInfoManager::new(None);
+
+    let trends = vec![
+        sink(
+            types::SinkType::Trend,
+            &[("avg", 100.0), ("min", 20.0), ("max", 300.0)],
+        ),
+        sink(
+            types::SinkType::Trend,
+            &[("avg", 200.0), ("min", 50.0), ("max", 900.0)],
+        ),
+    ];
+
+    for (period, trend) in trends.into_iter().enumerate() {
+        manager
+            .process_interval(&interval(
+                period as i32,
+                vec![("us-east1::http_req_duration", trend)],
+            ))
+            .unwrap();
+    }
+
+    assert_eq!(
+        summary_labels(&manager, "us-east1::http_req_duration"),
+        vec![
+            ("avg".to_string(), 150.0),
+            ("max".to_string(), 900.0),
+            ("min".to_string(), 20.0),
+        ]
+    );
+}
+
+#[test]
+fn trend_summary_is_weighted_by_sample_count() {
+    let mut manager = TestInfoManager::new(None);
+
+    for (period, avg, count) in [(0, 100.0, 1.0), (1, 200.0, 3.0)] {
+        let trend = sink(types::SinkType::Trend, &[("avg", avg), ("count", count)]);
+        manager
+            .process_interval(&interval(
+                period,
+                vec![("us-east1::http_req_duration", trend)],
+            ))
+            .unwrap();
+    }
+
+    assert_eq!(label(&manager, "us-east1::http_req_duration", "avg"), 175.0);
+    assert_eq!(label(&manager, "us-east1::http_req_duration", "count"), 4.0);
+}
+
+#[test]
+fn late_and_replayed_intervals_are_summarised_again() {
+    let rate_interval = |period: i32, pass: f64| {
+        interval(
+            period,
+            vec![
+                (
+                    "us-east1::checks",
+                    sink(types::SinkType::Rate, &[("pass", pass), ("total", 10.0)]),
+                ),
+                (
+                    "us-east1::http_reqs",
+                    sink(
+                        types::SinkType::Counter,
+                        &[("count", 10.0 * (period + 1) as f64)],
+                    ),
+                ),
+            ],
+        )
+    };
+
+    let mut manager = TestInfoManager::new(None);
+    manager.process_interval(&rate_interval(0, 10.0)).unwrap();
+    manager.process_interval(&rate_interval(2, 8.0)).unwrap();
+
+    // Arrives late
+    manager.process_interval(&rate_interval(1, 6.0)).unwrap();
+    assert_eq!(label(&manager, "us-east1::checks", "pass"), 24.0);
+
+    // Replaces the newest interval
+    manager.process_interval(&rate_interval(2, 2.0)).unwrap();
+    assert_eq!(label(&manager, "us-east1::checks", "pass"), 18.0);
+    assert_eq!(label(&manager, "us-east1::checks", "total"), 30.0);
+
+    manager.process_interval(&rate_interval(3, 10.0)).unwrap();
+
+    let rebuilt = summarise_intervals(manager.intervals()).unwrap();
+    assert_eq!(manager.summary(), rebuilt.as_ref());
+}
@@ -0,0 +1,377 @@
+//! Tests for parsing threshold sources and evaluating them as intervals arrive.
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::sink;
+use datapeak::{
+    manager::TestInfoManager,
+    thresholds::{
+        metric_to_sink_suffix, parse_duration_seconds, parse_threshold_source, ThresholdOperator,
+    },
+    types,
+};
+
+fn interval(period: i32, p95: f64) -> types::Interval {
+    common::interval(
+        period,
+        vec![(
+            "us-east1::http_req_duration",
+            sink(
+                types::SinkType::Trend,
+                &[
+                    ("avg", p95 / 2.0),
+                    ("min", 10.0),
+                    ("max", p95),
+                    ("p(95)", p95),
+                ],
+            ),
+        )],
+    )
+}
+
+fn threshold(metric: &str, source: &str) -> types::Threshold {
+    let mut threshold = types::Threshold::new();
+    threshold.metric = metric.to_string();
+    threshold.source = source.to_string();
+    threshold
+}
+
+fn abort_threshold(source: &str, delay_abort_eval: Option<&str>) -> types::Threshold {
+    let mut threshold = threshold("http_req_duration", source);
+    threshold.abort_on_fail = Some(true);
+    threshold.delay_abort_eval = delay_abort_eval.map(|x| x.to_string());
+    threshold
+}
+
+fn manager(thresholds: Vec<types::Threshold>) -> TestInfoManager {
+    let mut test_info = types::TestInfo::new();
+    test_info.thresholds = thresholds;
+    TestInfoManager::new(Some(&test_info))
+}
+
+#[test]
+fn parses_threshold_sources() {
+    let expression = parse_threshold_source("p(95)<500").unwrap();
+    assert_eq!(expression.aggregation, "p(95)");
+    assert_eq!(expression.operator, ThresholdOperator::LessThan);
+    assert_eq!(expression.value, 500.0);
+
+    let expression = parse_threshold_source(" rate >= 0.99 ").unwrap();
+    assert_eq!(expression.aggregation, "rate");
+    assert_eq!(expression.operator, ThresholdOperator::GreaterThanOrEqual);
+    assert_eq!(expression.value, 0.99);
+
+    let expression = parse_threshold_source("count===0").unwrap();
+    assert_eq!(expression.operator, ThresholdOperator::Equal);
+
+    let expression = parse_threshold_source("value!=1").unwrap();
+    assert_eq!(expression.operator, ThresholdOperator::NotEqual);
+
+    // Percentiles are normalised so they match the sink labels
+    assert_eq!(
+        parse_threshold_source("p(95.0)<500").unwrap().aggregation,
+        "p(95)"
+    );
+    assert_eq!(
+        parse_threshold_source("med<=200").unwrap().aggregation,
+        "med"
+    );
+}
+
+#[test]
+fn rejects_invalid_threshold_sources() {
+    assert!(parse_threshold_source("p(95)").is_err());
+    assert!(parse_threshold_source("p(95)<").is_err());
+    assert!(parse_threshold_source("p(95)<fast").is_err());
+    assert!(parse_threshold_source("p(101)<500").is_err());
+    assert!(parse_threshold_source("median<500").is_err());
+    assert!(parse_threshold_source("avg=>500").is_err());
+}
+
+#[test]
+fn evaluates_threshold_expressions() {
+    let expression = parse_threshold_source("avg<=200").unwrap();
+
+    assert!(expression.evaluate(150.0));
+    assert!(expression.evaluate(200.0));
+    assert!(!expression.evaluate(250.0));
+}
+
+#[test]
+fn converts_metrics_to_sink_suffixes() {
+    assert_eq!(metric_to_sink_suffix("http_reqs"), "http_reqs");
+    assert_eq!(
+        metric_to_sink_suffix("checks{check:status is 200}"),
+        "checks::check::status is 200"
+    );
+    assert_eq!(
+        metric_to_sink_suffix("http_req_duration{ group: login , scenario: default }"),
+        "http_req_duration::group::login::scenario::default"
+    );
+}
+
+#[test]
+fn evaluates_loaded_thresholds_per_location_and_globally() {
+    let mut test_info = types::TestInfo::new();
+    test_info.thresholds = vec![threshold("http_req_duration", "p(95)<500")];
+
+    let mut manager = TestInfoManager::new(Some(&test_info));
+    manager.process_interval(&interval(0, 900.0)).unwrap();
+
+    let results = &manager.threshold_results;
+    let mut locations: Vec<&str> = results.iter().map(|x| x.location.as_str()).collect();
+    locations.sort_unstable();
+    assert_eq!(locations, vec!["global", "us-east1"]);
+
+    for result in results.iter() {
+        assert!(!result.passed);
+        assert_eq!(result.period, 0);
+        assert_eq!(result.value, Some(900.0));
+    }
+}
+
+#[test]
+fn invalid_sources_are_reported_in_the_results() {
+    let mut test_info = types::TestInfo::new();
+    test_info.thresholds = vec![threshold("http_req_duration", "p(95)")];
+
+    let mut manager = TestInfoManager::new(Some(&test_info));
+    manager.process_interval(&interval(0, 100.0)).unwrap();
+
+    let results = &manager.threshold_results;
+    assert_eq!(results.len(), 1);
+    assert!(!results[0].passed);
+    assert!(results[0].error.is_some());
+}
+
+#[test]
+fn earlier_breach_still_fails_after_recovery() {
+    let mut manager = TestInfoManager::new(None);
+    manager
+        .process_threshold(&threshold("http_req_duration", "p(95)<500"))
+        .unwrap();
+
+    manager.process_interval(&interval(0, 900.0)).unwrap();
+    manager.process_interval(&interval(1, 100.0)).unwrap();
+    manager.process_interval(&interval(2, 100.0)).unwrap();
+
+    let results = manager.threshold_results();
+    assert_eq!(results.len(), 2);
+
+    for result in results.iter() {
+        assert!(!result.passed);
+        assert_eq!(result.period, 0);
+        assert_eq!(result.value, Some(900.0));
+    }
+}
+
+#[test]
+fn results_follow_the_newest_interval_until_a_breach() {
+    let mut manager = TestInfoManager::new(None);
+    manager
+        .process_threshold(&threshold("http_req_duration", "p(95)<500"))
+        .unwrap();
+
+    manager.process_interval(&interval(0, 100.0)).unwrap();
+    manager.process_interval(&interval(1, 200.0)).unwrap();
+
+    let result = &manager.threshold_results()[0];
+    assert!(result.passed);
+    assert_eq!(result.period, 1);
+    assert_eq!(result.value, Some(200.0));
+
+    manager.process_interval(&interval(2, 600.0)).unwrap();
+    manager.process_interval(&interval(3, 700.0)).unwrap();
+
+    // The first breach is kept
+    let result = &manager.threshold_results()[0];
+    assert!(!result.passed);
+    assert_eq!(result.period, 2);
+    assert_eq!(result.value, Some(600.0));
+}
+
+#[test]
+fn late_breaches_are_evaluated() {
+    let mut manager = manager(vec![abort_threshold("p(95)<500", None)]);
+
+    manager.process_interval(&interval(0, 100.0)).unwrap();
+    manager.process_interval(&interval(2, 100.0)).unwrap();
+    manager.process_interval(&interval(1, 900.0)).unwrap();
+
+    // The late breach is kept
+    let result = &manager.threshold_results()[0];
+    assert!(!result.passed);
+    assert_eq!(result.period, 1);
+    assert_eq!(result.value, Some(900.0));
+    assert_eq!(manager.abort_decision.unwrap().period, 1);
+}
+
+#[test]
+fn late_intervals_that_pass_keep_the_newest_results() {
+    let mut manager = manager(vec![threshold("http_req_duration", "p(95)<500")]);
+
+    manager.process_interval(&interval(0, 100.0)).unwrap();
+    manager.process_interval(&interval(2, 200.0)).unwrap();
+    manager.process_interval(&interval(1, 300.0)).unwrap();
+
+    let result = &manager.threshold_results()[0];
+    assert!(result.passed);
+    assert_eq!(result.period, 2);
+    assert_eq!(result.value, Some(200.0));
+}
+
+#[test]
+fn loaded_and_added_thresholds_are_evaluated_over_the_whole_run() {
+    let mut test_info = types::TestInfo::new();
+    test_info.intervals = vec![interval(0, 900.0), interval(1, 100.0)];
+    test_info.thresholds = vec![threshold("http_req_duration", "p(95)<500")];
+
+    let mut manager = TestInfoManager::new(Some(&test_info));
+    assert!(manager
+        .threshold_results()
+        .iter()
+        .all(|x| !x.passed && x.period == 0));
+
+    manager
+        .process_threshold(&threshold("http_req_duration", "max<800"))
+        .unwrap();
+
+    let added_results: Vec<_> = manager
+        .threshold_results()
+        .iter()
+        .filter(|x| x.source == "max<800")
+        .collect();
+    assert_eq!(added_results.len(), 2);
+    assert!(added_results.iter().all(|x| !x.passed && x.period == 0));
+}
+
+#[test]
+fn records_the_first_breach_of_abort_thresholds() {
+    let mut manager = manager(vec![abort_threshold("p(95)<500", None)]);
+
+    manager.process_interval(&interval(0, 100.0)).unwrap();
+    assert!(manager.abort_decision.is_none());
+
+    manager.process_interval(&interval(1, 600.0)).unwrap();
+    manager.process_interval(&interval(2, 700.0)).unwrap();
+
+    let abort_decision = manager.abort_decision.unwrap();
+    assert_eq!(abort_decision.metric, "http_req_duration");
+    assert_eq!(abort_decision.source, "p(95)<500");
+    assert_eq!(abort_decision.period, 1);
+    assert_eq!(abort_decision.value, Some(600.0));
+}
+
+#[test]
+fn only_abort_thresholds_abort() {
+    let mut manager = manager(vec![threshold("http_req_duration", "p(95)<500")]);
+
+    manager.process_interval(&interval(0, 900.0)).unwrap();
+
+    assert!(manager.threshold_results.iter().all(|x| !x.passed));
+    assert!(manager.abort_decision.is_none());
+}
+
+#[test]
+fn abort_waits_for_delay_abort_eval() {
+    let mut manager = manager(vec![abort_threshold("p(95)<500", Some("30s"))]);
+
+    // Periods are 6 seconds wide, so period 4 is the first to end after 30s
+    for period in 0..4 {
+        manager.process_interval(&interval(period, 900.0)).unwrap();
+        assert!(manager.abort_decision.is_none());
+    }
+
+    manager.process_interval(&interval(4, 900.0)).unwrap();
+    assert_eq!(manager.abort_decision.unwrap().period, 4);
+}
+
+#[test]
+fn breaches_before_the_delay_do_not_abort() {
+    let mut manager = TestInfoManager::new(None);
+    manager
+        .process_threshold(&abort_threshold("p(95)<500", Some("12s")))
+        .unwrap();
+
+    manager.process_interval(&interval(0, 900.0)).unwrap();
+    manager.process_interval(&interval(1, 100.0)).unwrap();
+    manager.process_interval(&interval(2, 100.0)).unwrap();
+
+    assert!(manager.abort_decision().is_none());
+}
+
+#[test]
+fn invalid_delay_abort_eval_never_aborts() {
+    let mut manager = manager(vec![abort_threshold("p(95)<500", Some("soon"))]);
+
+    manager.process_interval(&interval(0, 900.0)).unwrap();
+
+    assert!(manager.abort_decision.is_none());
+}
+
+#[test]
+fn negative_periods_count_as_the_first_period() {
+    let mut manager = TestInfoManager::new(None);
+    manager
+        .process_threshold(&abort_threshold("p(95)<500", Some("6s")))
+        .unwrap();
+
+    manager.process_interval(&interval(-3, 900.0)).unwrap();
+
+    assert_eq!(manager.abort_decision().unwrap().period, -3);
+}
+
+#[test]
+fn abort_delay_is_measured_from_the_run_start_time() {
+    let mut manager = manager(vec![abort_threshold("p(95)<500", Some("30s"))]);
+    manager
+        .set_run_info(
+            Some(Utc.with_ymd_and_hms(2023, 5, 1, 12, 0, 0).unwrap()),
+            Some(10),
+        )
+        .unwrap();
+
+    // Periods before the start of the run haven't passed the delay
+    for period in [-3, 0, 1] {
+        manager.process_interval(&interval(period, 900.0)).unwrap();
+        assert!(manager.abort_decision.is_none());
+    }
+
+    // Periods are 10 seconds wide, so period 2 ends 30s into the run
+    manager.process_interval(&interval(2, 900.0)).unwrap();
+    assert_eq!(manager.abort_decision.unwrap().period, 2);
+}
+
+#[test]
+fn parses_durations() {
+    assert_eq!(parse_duration_seconds("10s").unwrap(), 10.0);
+    assert_eq!(parse_duration_seconds("1m30s").unwrap(), 90.0);
+    assert_eq!(parse_duration_seconds("500ms").unwrap(), 0.5);
+    assert_eq!(parse_duration_seconds("1h").unwrap(), 3600.0);
+    assert_eq!(parse_duration_seconds("1.5m").unwrap(), 90.0);
+
+    assert!(parse_duration_seconds("").is_err());
+    assert!(parse_duration_seconds("10").is_err());
+    assert!(parse_duration_seconds("10d").is_err());
+}
+
+#[test]
+fn summary_results_evaluate_the_whole_run() {
+    let mut manager = TestInfoManager::new(None);
+    assert!(manager.summary_threshold_results().is_empty());
+
+    manager
+        .process_threshold(&threshold("http_req_duration", "avg<200"))
+        .unwrap();
+
+    manager.process_interval(&interval(0, 600.0)).unwrap();
+    manager.process_interval(&interval(1, 100.0)).unwrap();
+
+    // The average over the run is 175, though the first interval's was 300
+    let results = manager.summary_threshold_results();
+    assert_eq!(results.len(), 2);
+    assert!(results
+        .iter()
+        .all(|x| x.passed && x.period == 1 && x.value == Some(175.0)));
+}
@@ -0,0 +1,264 @@
+//! Tests for querying and downsizing time series.
+
+mod common;
+
+use common::{interval, sink};
+use datapeak::{
+    intervals::time_series::{get_time_series, query_time_series, DownsizingMethod},
+    types,
+};
+
+// A flat latency series with a single spike
+fn intervals(count: i32, spike_period: i32) -> Vec<types::Interval> {
+    (0..count)
+        .map(|period| {
+            let p95 = if period == spike_period {
+                900.0
+            } else {
+                100.0 + (period % 3) as f64
+            };
+
+            interval(
+                period,
+                vec![(
+                    "global::http_req_duration",
+                    sink(types::SinkType::Trend, &[("p(95)", p95)]),
+                )],
+            )
+        })
+        .collect()
+}
+
+fn series(intervals: &[types::Interval], downsizing_method: DownsizingMethod) -> Vec<(f64, f64)> {
+    get_time_series(
+        intervals,
+        "global::http_req_duration",
+        "p(95)",
+        downsizing_method,
+    )
+    .unwrap()
+}
+
+#[test]
+fn lttb_keeps_spikes_and_end_points() {
+    let intervals = intervals(500, 237);
+
+    let data = series(
+        &intervals,
+        DownsizingMethod::Lttb {
+            max_data_points: Some(50),
+        },
+    );
+
+    assert_eq!(data.len(), 50);
+    assert_eq!(data[0], (0.0, 100.0));
+    assert_eq!(data[49].0, 499.0);
+    assert!(data.contains(&(237.0, 900.0)));
+    assert!(data.windows(2).all(|x| x[0].0 < x[1].0));
+}
+
+#[test]
+fn min_max_keeps_spikes() {
+    let intervals = intervals(500, 237);
+
+    let data = series(
+        &intervals,
+        DownsizingMethod::MinMax {
+            max_data_points: Some(50),
+        },
+    );
+
+    assert!(data.len() <= 50);
+    assert!(data.contains(&(237.0, 900.0)));
+    assert!(data.windows(2).all(|x| x[0].0 < x[1].0));
+
+    // Fixed intervals can step over the spike
+    let fixed = series(
+        &intervals,
+        DownsizingMethod::FixedIntervals {
+            max_data_points: Some(50),
+        },
+    );
+    assert!(!fixed.contains(&(237.0, 900.0)));
+}
+
+#[test]
+fn short_series_are_not_downsized() {
+    let intervals = intervals(10, 5);
+
+    for downsizing_method in [
+        DownsizingMethod::Lttb {
+            max_data_points: Some(50),
+        },
+        DownsizingMethod::MinMax {
+            max_data_points: Some(50),
+        },
+    ] {
+        assert_eq!(series(&intervals, downsizing_method).len(), 10);
+    }
+
+    let data = series(
+        &intervals,
+        DownsizingMethod::Lttb {
+            max_data_points: Some(2),
+        },
+    );
+    assert_eq!(data, vec![(0.0, 100.0), (9.0, 100.0)]);
+}
+
+// Request rates in two zones, where eu-west1 only starts at period 2
+fn zone_intervals(count: i32) -> Vec<types::Interval> {
+    (0..count)
+        .map(|period| {
+            let mut interval = types::Interval::new();
+            interval.period = period;
+
+            for (zone, offset) in [("us-east1", 0.0), ("us-west1", 10.0), ("eu-west1", 20.0)] {
+                if zone == "eu-west1" && period < 2 {
+                    continue;
+                }
+
+                interval.sinks.insert(
+                    format!("{}::http_reqs", zone),
+                    sink(
+                        types::SinkType::Counter,
+                        &[("rate", offset + (period % 7) as f64)],
+                    ),
+                );
+            }
+
+            interval
+        })
+        .collect()
+}
+
+#[test]
+fn queries_match_sinks_by_glob() {
+    let intervals = zone_intervals(5);
+
+    let names = |pattern: &str| -> Vec<String> {
+        query_time_series(&intervals, pattern, DownsizingMethod::None)
+            .unwrap()
+            .into_iter()
+            .map(|x| x.name)
+            .collect()
+    };
+
+    assert_eq!(
+        names("us-*::http_reqs::rate"),
+        vec!["us-east1::http_reqs", "us-west1::http_reqs"]
+    );
+    assert_eq!(names("*::http_reqs::rate").len(), 3);
+
+    assert!(query_time_series(&intervals, "*::http_reqs::p(95)", DownsizingMethod::None).is_err());
+    assert!(
+        query_time_series(&intervals, "ap-*::http_reqs::rate", DownsizingMethod::None).is_err()
+    );
+    assert!(query_time_series(&intervals, "http_reqs", DownsizingMethod::None).is_err());
+}
+
+#[test]
+fn queried_series_share_a_period_axis() {
+    let intervals = zone_intervals(5);
+
+    let series =
+        query_time_series(&intervals, "*::http_reqs::rate", DownsizingMethod::None).unwrap();
+
+    assert_eq!(series[0].name, "eu-west1::http_reqs");
+    assert!(series[0].data[0].1.is_nan());
+    assert!(series[0].data[1].1.is_nan());
+    assert_eq!(series[0].data[2], (2.0, 22.0));
+
+    for time_series in series.iter() {
+        let periods: Vec<f64> = time_series.data.iter().map(|x| x.0).collect();
+        assert_eq!(periods, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    }
+}
+
+#[test]
+fn queried_series_are_downsampled_together() {
+    let intervals = zone_intervals(500);
+
+    for downsizing_method in [
+        DownsizingMethod::Lttb {
+            max_data_points: Some(60),
+        },
+        DownsizingMethod::MinMax {
+            max_data_points: Some(60),
+        },
+        DownsizingMethod::FixedIntervals {
+            max_data_points: Some(60),
+        },
+    ] {
+        let series =
+            query_time_series(&intervals, "*::http_reqs::rate", downsizing_method).unwrap();
+
+        let periods: Vec<Vec<f64>> = series
+            .iter()
+            .map(|x| x.data.iter().map(|x| x.0).collect())
+            .collect();
+
+        assert!(periods[0].len() <= 60);
+        assert!(periods.iter().all(|x| *x == periods[0]));
+        assert!(periods[0].windows(2).all(|x| x[0] < x[1]));
+    }
+}
+
+#[test]
+fn moving_mean_averages_each_window() {
+    let intervals = zone_intervals(5);
+
+    let moving_mean = |window_size: Option<usize>| {
+        query_time_series(
+            &intervals,
+            "us-east1::http_reqs::rate",
+            DownsizingMethod::MovingMean {
+                window_size,
+                max_data_points: None,
+            },
+        )
+        .unwrap()
+        .remove(0)
+        .data
+    };
+
+    assert_eq!(
+        moving_mean(Some(2)),
+        vec![(1.0, 0.5), (2.0, 1.5), (3.0, 2.5), (4.0, 3.5)]
+    );
+
+    // Short series get a window of a single point, ending at the last one
+    assert_eq!(
+        moving_mean(None),
+        vec![(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0), (4.0, 4.0)]
+    );
+}
+
+#[test]
+fn series_can_be_downsized_to_one_point_or_none() {
+    let intervals = zone_intervals(10);
+
+    for max_data_points in [0, 1] {
+        for downsizing_method in [
+            DownsizingMethod::FixedIntervals {
+                max_data_points: Some(max_data_points),
+            },
+            DownsizingMethod::MovingMean {
+                window_size: None,
+                max_data_points: Some(max_data_points),
+            },
+            DownsizingMethod::Lttb {
+                max_data_points: Some(max_data_points),
+            },
+            DownsizingMethod::MinMax {
+                max_data_points: Some(max_data_points),
+            },
+        ] {
+            let series =
+                query_time_series(&intervals, "us-*::http_reqs::rate", downsizing_method).unwrap();
+
+            assert_eq!(series.len(), 2);
+            assert!(series.iter().all(|x| x.data.len() == max_data_points));
+        }
+    }
+}
@@ -1,13 +1,387 @@
 //! Test suite for the Web and headless browsers.
 
-#![cfg(target_arch = "wasm32")]
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+mod common;
 
 extern crate wasm_bindgen_test;
+use wasm_bindgen::JsValue;
 use wasm_bindgen_test::*;
 
+use common::{interval, sink};
+use datapeak::{
+    types,
+    wasm::{
+        self, accessors, checks, console_messages, intervals, locations, subscriptions, summary,
+        time_series,
+    },
+};
+use protobuf::Message;
+
 wasm_bindgen_test_configure!(run_in_browser);
 
+fn interval_data_point(period: i32, check_pass: f64) -> types::DataPoint {
+    let interval = interval(
+        period,
+        vec![
+            (
+                "us-east1::http_reqs",
+                sink(types::SinkType::Counter, &[("count", 10.0 * (period + 1) as f64)]),
+            ),
+            (
+                "us-east1::checks::check::status is 200",
+                sink(
+                    types::SinkType::Rate,
+                    &[("pass", check_pass), ("total", 10.0), ("rate", check_pass / 10.0)],
+                ),
+            ),
+        ],
+    );
+
+    let mut data_point = types::DataPoint::new();
+    data_point.set_interval(interval);
+    data_point
+}
+
+fn console_message_data_point(message: &str) -> types::DataPoint {
+    let mut console_message = types::ConsoleMessage::new();
+    console_message.message = message.to_string();
+    console_message.level = "info".to_string();
+    console_message.count.insert("us-east1".to_string(), 1);
+
+    let mut data_point = types::DataPoint::new();
+    data_point.set_console_message(console_message);
+    data_point
+}
+
+fn threshold_data_point(metric: &str, source: &str) -> types::DataPoint {
+    let mut threshold = types::Threshold::new();
+    threshold.metric = metric.to_string();
+    threshold.source = source.to_string();
+
+    let mut data_point = types::DataPoint::new();
+    data_point.set_threshold(threshold);
+    data_point
+}
+
+fn add_data_points(test_info_id: &str, data_points: Vec<types::DataPoint>) {
+    let mut streamed_data = types::StreamedData::new();
+    streamed_data.data_points = data_points;
+
+    wasm::add_streamed_data(test_info_id, streamed_data.write_to_bytes().unwrap()).unwrap();
+}
+
+fn new_test_info_id() -> String {
+    wasm::init_test_data(None).unwrap().as_string().unwrap()
+}
+
+fn array_length(value: &JsValue) -> u32 {
+    js_sys::Array::from(value).length()
+}
+
+#[wasm_bindgen_test]
+fn intervals_state_changes_with_intervals() {
+    let test_info_id = new_test_info_id();
+    let initial = intervals::get_intervals_state(&test_info_id).unwrap();
+
+    add_data_points(&test_info_id, vec![interval_data_point(0, 10.0)]);
+    let after_first = intervals::get_intervals_state(&test_info_id).unwrap();
+    assert!(after_first > initial);
+
+    add_data_points(&test_info_id, vec![interval_data_point(0, 10.0)]);
+    assert_eq!(intervals::get_intervals_state(&test_info_id).unwrap(), after_first);
+
+    add_data_points(&test_info_id, vec![interval_data_point(1, 10.0)]);
+    assert!(intervals::get_intervals_state(&test_info_id).unwrap() > after_first);
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn checks_state_changes_with_check_rates() {
+    let test_info_id = new_test_info_id();
+
+    add_data_points(&test_info_id, vec![interval_data_point(0, 10.0)]);
+    let after_first = checks::get_checks_state(&test_info_id).unwrap();
+    assert!(after_first > 0.0);
+
+    add_data_points(&test_info_id, vec![interval_data_point(1, 10.0)]);
+    assert_eq!(checks::get_checks_state(&test_info_id).unwrap(), after_first);
+
+    add_data_points(&test_info_id, vec![interval_data_point(2, 5.0)]);
+    assert!(checks::get_checks_state(&test_info_id).unwrap() > after_first);
+
+    let checks = checks::get_checks(&test_info_id).unwrap();
+    let named = js_sys::Reflect::get(&checks, &JsValue::from_str("named")).unwrap();
+    assert_eq!(array_length(&named), 1);
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn summary_state_changes_with_intervals() {
+    let test_info_id = new_test_info_id();
+    assert!(summary::get_summary(&test_info_id).unwrap().is_null());
+
+    add_data_points(&test_info_id, vec![interval_data_point(0, 10.0)]);
+    let after_first = summary::get_summary_state(&test_info_id).unwrap();
+    assert!(after_first > 0.0);
+    assert!(summary::get_summary(&test_info_id).unwrap().is_object());
+
+    add_data_points(&test_info_id, vec![interval_data_point(1, 10.0)]);
+    assert!(summary::get_summary_state(&test_info_id).unwrap() > after_first);
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+}
+
 #[wasm_bindgen_test]
-fn pass() {
-    assert_eq!(1 + 1, 2);
+fn time_series_reads_interval_labels() {
+    let test_info_id = new_test_info_id();
+
+    add_data_points(
+        &test_info_id,
+        vec![interval_data_point(0, 10.0), interval_data_point(1, 10.0)],
+    );
+
+    let downsizing_method = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &downsizing_method,
+        &JsValue::from_str("type"),
+        &JsValue::from_str("none"),
+    )
+    .unwrap();
+
+    let time_series = time_series::interval_time_series(
+        &test_info_id,
+        "us-east1::http_reqs::count",
+        JsValue::from(downsizing_method),
+        JsValue::UNDEFINED,
+    )
+    .unwrap();
+    assert_eq!(array_length(&time_series), 2);
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn queried_and_expression_time_series_take_a_window() {
+    let test_info_id = new_test_info_id();
+
+    add_data_points(
+        &test_info_id,
+        vec![
+            interval_data_point(0, 10.0),
+            interval_data_point(1, 10.0),
+            interval_data_point(2, 10.0),
+        ],
+    );
+
+    let downsizing_method = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &downsizing_method,
+        &JsValue::from_str("type"),
+        &JsValue::from_str("none"),
+    )
+    .unwrap();
+
+    let window = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &window,
+        &JsValue::from_str("startPeriod"),
+        &JsValue::from_f64(1.0),
+    )
+    .unwrap();
+
+    let time_series = time_series::query_time_series(
+        &test_info_id,
+        "us-east1::http_reqs::count",
+        JsValue::from(downsizing_method.clone()),
+        JsValue::from(window.clone()),
+    )
+    .unwrap();
+    let data = js_sys::Reflect::get(
+        &js_sys::Array::from(&time_series).get(0),
+        &JsValue::from_str("data"),
+    )
+    .unwrap();
+    assert_eq!(array_length(&data), 2);
+
+    let time_series = time_series::expression_time_series(
+        &test_info_id,
+        "delta(us-east1::http_reqs::count)",
+        JsValue::from(downsizing_method),
+        JsValue::from(window),
+    )
+    .unwrap();
+    let data = js_sys::Reflect::get(&time_series, &JsValue::from_str("data")).unwrap();
+    assert_eq!(array_length(&data), 2);
+
+    // The run has no start time to give timestamps from
+    assert!(js_sys::Reflect::get(&time_series, &JsValue::from_str("timestamps"))
+        .unwrap()
+        .is_null());
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn console_messages_state_changes_with_each_message() {
+    let test_info_id = new_test_info_id();
+
+    add_data_points(&test_info_id, vec![console_message_data_point("hello")]);
+    let after_first = console_messages::get_console_messages_state(&test_info_id).unwrap();
+    assert!(after_first > 0.0);
+
+    add_data_points(&test_info_id, vec![console_message_data_point("hello")]);
+    assert!(console_messages::get_console_messages_state(&test_info_id).unwrap() > after_first);
+
+    let console_messages = console_messages::get_console_messages(&test_info_id).unwrap();
+    assert_eq!(array_length(&console_messages), 1);
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn thresholds_and_messages_accessors_return_added_data() {
+    let test_info_id = new_test_info_id();
+
+    add_data_points(
+        &test_info_id,
+        vec![threshold_data_point("http_reqs", "count<100")],
+    );
+    wasm::add_message(&test_info_id, "limit reached").unwrap();
+
+    let thresholds = accessors::get_thresholds(&test_info_id).unwrap();
+    assert_eq!(array_length(&thresholds), 1);
+
+    let messages = accessors::get_messages(&test_info_id).unwrap();
+    assert_eq!(array_length(&messages), 1);
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn accessors_reject_unknown_test_info_ids() {
+    assert!(accessors::get_thresholds("unknown").is_err());
+    assert!(accessors::get_messages("unknown").is_err());
+    assert!(intervals::get_intervals_state("unknown").is_err());
+    assert!(checks::get_checks("unknown").is_err());
+    assert!(summary::get_summary("unknown").is_err());
+    assert!(console_messages::get_console_messages("unknown").is_err());
+}
+
+// Records the topics of each call in a global array
+fn recording_callback(name: &str) -> js_sys::Function {
+    js_sys::Function::new_with_args(
+        "topics",
+        &format!(
+            "globalThis.{0} = (globalThis.{0} || []).concat([topics])",
+            name
+        ),
+    )
+}
+
+fn recorded_calls(name: &str) -> js_sys::Array {
+    let calls = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str(name)).unwrap();
+
+    if calls.is_undefined() {
+        return js_sys::Array::new();
+    }
+
+    js_sys::Array::from(&calls)
+}
+
+#[wasm_bindgen_test]
+fn subscribers_are_notified_once_with_their_changed_topics() {
+    let test_info_id = wasm::init_test_data(None).unwrap().as_string().unwrap();
+    let callback = recording_callback("messagesCalls");
+
+    subscriptions::subscribe(
+        &test_info_id,
+        JsValue::from_str("messages"),
+        callback.clone(),
+    )
+    .unwrap();
+    subscriptions::subscribe(
+        &test_info_id,
+        JsValue::from_str("locations"),
+        callback.clone(),
+    )
+    .unwrap();
+
+    wasm::add_message(&test_info_id, "limit reached").unwrap();
+
+    let calls = recorded_calls("messagesCalls");
+    assert_eq!(calls.length(), 1);
+
+    let topics = js_sys::Array::from(&calls.get(0));
+    assert_eq!(topics.length(), 1);
+    assert_eq!(topics.get(0).as_string().unwrap(), "messages");
+}
+
+#[wasm_bindgen_test]
+fn unsubscribed_callbacks_are_not_notified() {
+    let test_info_id = wasm::init_test_data(None).unwrap().as_string().unwrap();
+    let callback = recording_callback("unsubscribedCalls");
+
+    subscriptions::subscribe(
+        &test_info_id,
+        JsValue::from_str("messages"),
+        callback.clone(),
+    )
+    .unwrap();
+    subscriptions::unsubscribe(&test_info_id, JsValue::from_str("messages"), callback).unwrap();
+
+    wasm::add_message(&test_info_id, "limit reached").unwrap();
+
+    assert_eq!(recorded_calls("unsubscribedCalls").length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn subscribing_to_missing_test_info_fails() {
+    let callback = recording_callback("missingCalls");
+
+    assert!(subscriptions::subscribe("missing", JsValue::from_str("messages"), callback).is_err());
+}
+
+#[wasm_bindgen_test]
+fn exported_test_data_can_be_loaded_again() {
+    let test_info_id = new_test_info_id();
+
+    add_data_points(
+        &test_info_id,
+        vec![interval_data_point(0, 10.0), console_message_data_point("hello")],
+    );
+
+    let bytes = wasm::export_test_data(&test_info_id).unwrap();
+    let loaded_id = wasm::init_test_data(Some(bytes)).unwrap().as_string().unwrap();
+
+    assert_eq!(
+        array_length(&console_messages::get_console_messages(&loaded_id).unwrap()),
+        1
+    );
+    assert!(summary::get_summary(&loaded_id).unwrap().is_object());
+
+    wasm::delete_test_data(&test_info_id).unwrap();
+    wasm::delete_test_data(&loaded_id).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn locations_must_be_strings() {
+    let test_info_id = new_test_info_id();
+
+    let invalid = js_sys::Array::new();
+    invalid.push(&JsValue::from_str("us-east1"));
+    invalid.push(&JsValue::from_f64(1.0));
+    assert!(locations::set_locations(&test_info_id, invalid).is_err());
+
+    let valid = js_sys::Array::new();
+    valid.push(&JsValue::from_str("us-east1"));
+    locations::set_locations(&test_info_id, valid).unwrap();
+
+    let stored = js_sys::Array::from(&locations::get_locations(&test_info_id).unwrap());
+    assert_eq!(stored.length(), 1);
+    assert_eq!(stored.get(0).as_string().unwrap(), "us-east1");
+
+    wasm::delete_test_data(&test_info_id).unwrap();
 }
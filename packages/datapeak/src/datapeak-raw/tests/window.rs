@@ -0,0 +1,199 @@
+//! Tests for queries limited to a window of periods.
+
+mod common;
+
+use common::sink;
+use datapeak::{
+    intervals::{time_series::DownsizingMethod, window::Window},
+    manager::TestInfoManager,
+    types,
+};
+
+// Ramps up for 2 periods, holds at 60 requests per period until period 7 and
+// then ramps down, with slow requests and failing checks outside the hold
+fn manager() -> TestInfoManager {
+    let mut manager = TestInfoManager::new(None);
+    let mut count = 0.0;
+
+    for period in 0..10 {
+        let steady = (2..8).contains(&period);
+        count += if steady { 60.0 } else { 12.0 };
+
+        let mut interval = types::Interval::new();
+        interval.period = period;
+        interval.sinks.insert(
+            "us-east1::http_reqs".to_string(),
+            sink(types::SinkType::Counter, &[("count", count)]),
+        );
+        interval.sinks.insert(
+            "us-east1::http_req_duration".to_string(),
+            sink(
+                types::SinkType::Trend,
+                &[("p(95)", if steady { 100.0 } else { 500.0 })],
+            ),
+        );
+        interval.sinks.insert(
+            "us-east1::checks::check::status is 200".to_string(),
+            sink(
+                types::SinkType::Rate,
+                &[("pass", if steady { 10.0 } else { 0.0 }), ("total", 10.0)],
+            ),
+        );
+
+        manager.process_interval(&interval).unwrap();
+    }
+
+    manager
+}
+
+fn steady_state() -> Window {
+    Window::new(Some(2), Some(7))
+}
+
+#[test]
+fn time_series_are_limited_to_the_window() {
+    let manager = manager();
+
+    let time_series = manager
+        .window_time_series(
+            "global::http_req_duration::p(95)",
+            DownsizingMethod::None,
+            &steady_state(),
+        )
+        .unwrap();
+
+    assert_eq!(time_series.data.len(), 6);
+    assert_eq!(time_series.data[0], (2.0, 100.0));
+    assert_eq!(time_series.data[5], (7.0, 100.0));
+
+    let time_series = manager
+        .window_time_series(
+            "global::http_req_duration::p(95)",
+            DownsizingMethod::None,
+            &Window::new(Some(8), None),
+        )
+        .unwrap();
+
+    assert_eq!(time_series.data, vec![(8.0, 500.0), (9.0, 500.0)]);
+}
+
+#[test]
+fn queried_time_series_are_limited_to_the_window() {
+    let manager = manager();
+
+    let time_series = manager
+        .query_time_series(
+            "*::http_req_duration::p(95)",
+            DownsizingMethod::None,
+            &steady_state(),
+        )
+        .unwrap();
+
+    assert_eq!(time_series.len(), 2);
+    for time_series in time_series.iter() {
+        assert_eq!(time_series.data.len(), 6);
+        assert_eq!(time_series.data[0], (2.0, 100.0));
+    }
+
+    assert!(manager
+        .query_time_series(
+            "*::http_req_duration::p(95)",
+            DownsizingMethod::None,
+            &Window::new(Some(5), Some(4)),
+        )
+        .is_err());
+}
+
+#[test]
+fn expression_time_series_are_limited_to_the_window() {
+    let manager = manager();
+
+    // The delta of the first period in the window uses the period before it
+    let time_series = manager
+        .expression_time_series(
+            "delta(http_reqs::count)",
+            DownsizingMethod::None,
+            &steady_state(),
+        )
+        .unwrap();
+
+    assert_eq!(time_series.data.len(), 6);
+    assert!(time_series.data.iter().all(|(_, value)| *value == 60.0));
+    assert_eq!(time_series.data[0].0, 2.0);
+
+    // Without an interval before the window there is nothing to compare with
+    let time_series = manager
+        .expression_time_series(
+            "delta(http_reqs::count)",
+            DownsizingMethod::None,
+            &Window::new(None, Some(1)),
+        )
+        .unwrap();
+
+    assert_eq!(time_series.data.len(), 2);
+    assert!(time_series.data[0].1.is_nan());
+    assert_eq!(time_series.data[1], (1.0, 12.0));
+
+    assert!(manager
+        .expression_time_series(
+            "http_reqs::count",
+            DownsizingMethod::None,
+            &Window::new(Some(5), Some(4)),
+        )
+        .is_err());
+}
+
+#[test]
+fn window_summaries_only_count_the_window() {
+    let manager = manager();
+
+    let summary = manager.window_summary(&steady_state()).unwrap().unwrap();
+
+    assert_eq!(summary.period, 7);
+
+    let http_reqs = &summary.sinks["global::http_reqs"].labels;
+    assert_eq!(http_reqs["count"], 360.0);
+    assert_eq!(http_reqs["rate"], 10.0);
+
+    let p95 = summary.sinks["global::http_req_duration"].labels["p(95)"];
+    assert_eq!(p95, 100.0);
+
+    // The whole run matches the stored summary
+    let whole_run = manager.window_summary(&Window::default()).unwrap().unwrap();
+    assert_eq!(
+        whole_run.sinks["global::http_reqs"].labels["count"],
+        manager.summary().unwrap().sinks["global::http_reqs"].labels["count"]
+    );
+}
+
+#[test]
+fn window_checks_cover_the_window() {
+    let manager = manager();
+
+    let checks = manager.window_checks(&steady_state()).unwrap();
+    let rate = &checks.named[0].rates["global"];
+    assert_eq!((rate.pass, rate.total, rate.rate), (60, 60, 1.0));
+
+    let checks = manager.window_checks(&Window::default()).unwrap();
+    let rate = &checks.named[0].rates["global"];
+    assert_eq!((rate.pass, rate.total), (60, 100));
+}
+
+#[test]
+fn empty_and_invalid_windows() {
+    let manager = manager();
+
+    assert!(manager
+        .window_summary(&Window::new(Some(20), None))
+        .unwrap()
+        .is_none());
+    assert!(manager
+        .window_checks(&Window::new(Some(20), None))
+        .unwrap()
+        .named
+        .is_empty());
+
+    assert!(manager
+        .window_summary(&Window::new(Some(5), Some(4)))
+        .is_err());
+}
@@ -1,6 +1,7 @@
 import {
   rawAddStreamedData,
   rawDeleteTestData,
+  rawExportTestData,
   rawInitTestData,
 } from './datapeak-raw/pkg/datapeak'
 
@@ -8,6 +9,9 @@ export const initTestData = rawInitTestData as (testInfo?: Uint8Array) => string
 
 export const deleteTestData = rawDeleteTestData
 
+/** Serializes the test data, including the summary and locations, so it can be passed to initTestData */
+export const exportTestData = rawExportTestData as (testInfoId: string) => Uint8Array
+
 export const addStreamedData = rawAddStreamedData as (
   testInfoId: string,
   bytes: Uint8Array
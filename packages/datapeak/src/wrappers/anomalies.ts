@@ -0,0 +1,78 @@
+import {
+  rawGetAnomalies,
+  rawGetAnomaliesState,
+  rawSetAnomalyOptions,
+  rawTestInfoIdExists,
+} from '../datapeak-raw/pkg/datapeak'
+
+export type AnomalyKind = 'latencySpike' | 'errorRateSpike' | 'throughputDrop'
+
+export type Anomaly = {
+  sinkName: string
+  label: string
+  /** Either a location or 'global' for all locations combined */
+  location: string
+  kind: AnomalyKind
+  period: number
+  value: number
+  /** Mean of the preceding window */
+  expected: number
+  /** Standard deviations from the window mean, positive is always worse */
+  zScore: number
+}
+
+export type AnomalyOptions = {
+  /** Number of preceding intervals compared against, defaults to 10 */
+  windowSize?: number
+  /** Preceding intervals needed before anything is flagged, defaults to 5 */
+  minWindowSize?: number
+  /** Defaults to 3 */
+  zScoreThreshold?: number
+}
+
+export const getAnomalies = rawGetAnomalies as (testInfoId: string) => Anomaly[]
+
+/** Replaces the anomaly detection options and re-detects anomalies in every interval */
+export const setAnomalyOptions = rawSetAnomalyOptions as (
+  testInfoId: string,
+  options?: AnomalyOptions
+) => void
+
+export class AnomaliesPoller {
+  private state: number | null = null
+  private intervalId: NodeJS.Timeout | null = null
+  private readonly pollInterval: number
+  private readonly callback: (anomalies: Anomaly[]) => void
+  private readonly testInfoId: string
+
+  constructor(
+    testInfoId: string,
+    callback: (anomalies: Anomaly[]) => void,
+    pollInterval = 1000
+  ) {
+    if (!rawTestInfoIdExists(testInfoId)) {
+      throw new Error(`Test info id ${testInfoId} does not exist`)
+    }
+
+    this.testInfoId = testInfoId
+    this.callback = callback
+    this.pollInterval = pollInterval
+    this.poll()
+  }
+
+  private async poll() {
+    const newState = rawGetAnomaliesState(this.testInfoId)
+    if (newState !== this.state) {
+      this.state = newState
+      this.callback(getAnomalies(this.testInfoId))
+    }
+    this.intervalId = setTimeout(() => this.poll(), this.pollInterval)
+  }
+
+  public destroy(): void {
+    if (this.intervalId) {
+      clearInterval(this.intervalId)
+      this.intervalId = null
+    }
+  }
+}
@@ -0,0 +1,20 @@
+import { rawGetChangesSince } from '../datapeak-raw/pkg/datapeak'
+
+import type { ConsoleMessage } from './console-messages'
+import type { Interval } from './intervals'
+import type { Threshold } from './thresholds'
+
+export type Changes = {
+  /** Pass to the next call to only receive newer changes */
+  version: number
+  intervals: Interval[]
+  consoleMessages: ConsoleMessage[]
+  thresholds: Threshold[]
+  messages: string[]
+}
+
+/** Returns the data added or modified after the given version, use 0 for everything */
+export const getChangesSince = rawGetChangesSince as (
+  testInfoId: string,
+  version: number
+) => Changes
@@ -1,5 +1,10 @@
-import { rawGetChecks, rawGetChecksState } from 'src/datapeak-raw/pkg/datapeak'
+import {
+  rawGetChecks,
+  rawGetChecksState,
+  rawGetWindowChecks,
+} from 'src/datapeak-raw/pkg/datapeak'
 
+import type { Window } from './intervals'
 import type { Threshold } from './thresholds'
 
 export type CheckCollection = {
@@ -24,8 +29,14 @@ export type CheckRate = {
 
 export const getChecks = rawGetChecks as (testInfoId: string) => CheckCollection
 
+/** Check rates over the intervals inside the window */
+export const getWindowChecks = rawGetWindowChecks as (
+  testInfoId: string,
+  window?: Window
+) => CheckCollection
+
 export class ChecksPoller {
-  private state = ''
+  private state: number | null = null
   private intervalId: NodeJS.Timeout | null = null
   private readonly pollInterval: number
   private readonly callback: (checks: CheckCollection) => void
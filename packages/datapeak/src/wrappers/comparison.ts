@@ -0,0 +1,75 @@
+import {
+  rawAlignedTimeSeries,
+  rawCompareTestData,
+  rawCompareTestDataBytes,
+} from '../datapeak-raw/pkg/datapeak'
+
+export type ComparisonOptions = {
+  /** Relative increase in a p(95) flagged as a regression, defaults to 0.1 */
+  p95Tolerance?: number
+  /** Increase in an error rate flagged as a regression, defaults to 0.01 */
+  errorRateTolerance?: number
+  /** p-value below which a difference is reported as significant, defaults to 0.05 */
+  significanceLevel?: number
+}
+
+export type LabelDelta = {
+  sinkName: string
+  label: string
+  baseline: number
+  candidate: number
+  absoluteDelta: number
+  /** Undefined when the baseline is zero */
+  relativeDelta?: number
+  regression: boolean
+}
+
+export type SignificanceResult = {
+  sinkName: string
+  label: string
+  test: 'mannWhitneyU' | 'twoProportionZ'
+  /** U for the candidate, or z for proportions */
+  statistic: number
+  pValue: number
+  /** Probability a candidate interval is higher than a baseline one, or the difference in proportions */
+  effect: number
+  significant: boolean
+}
+
+export type Comparison = {
+  deltas: LabelDelta[]
+  baselineOnly: string[]
+  candidateOnly: string[]
+  regressed: boolean
+  significance: SignificanceResult[]
+}
+
+export type AlignedTimeSeries = {
+  name: string
+  data: {
+    period: number
+    baseline?: number
+    candidate?: number
+  }[]
+}
+
+/** Compares the summaries of two loaded runs */
+export const compareTestData = rawCompareTestData as (
+  baselineId: string,
+  candidateId: string,
+  options?: ComparisonOptions
+) => Comparison
+
+/** Compares two runs exported with exportTestData, without loading them */
+export const compareTestDataBytes = rawCompareTestDataBytes as (
+  baseline: Uint8Array,
+  candidate: Uint8Array,
+  options?: ComparisonOptions
+) => Comparison
+
+/** Gets a `<sink>::<label>` time series from both runs, paired by period */
+export const getAlignedTimeSeries = rawAlignedTimeSeries as (
+  baselineId: string,
+  candidateId: string,
+  sinkPath: string
+) => AlignedTimeSeries
@@ -18,7 +18,7 @@ export const getConsoleMessages = rawGetConsoleMessages as (
 ) => ConsoleMessage[]
 
 export class ConsoleMessagesPoller {
-  private state = ''
+  private state: number | null = null
   private intervalId: NodeJS.Timeout | null = null
   private readonly pollInterval: number
   private readonly callback: (messages: ConsoleMessage[]) => void
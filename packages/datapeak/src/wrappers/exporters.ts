@@ -0,0 +1,29 @@
+import {
+  rawExportCsv,
+  rawExportInfluxLineProtocol,
+  rawExportOpenMetrics,
+} from '../datapeak-raw/pkg/datapeak'
+
+export type CsvExportOptions = {
+  /** Globs matched against `<sink>::<label>` columns without the location, eg. `*::p(95)`, all columns are included if omitted */
+  columns?: string[]
+  /** Start of the run in milliseconds since the epoch, adds a timestamp column. Defaults to the recorded start time */
+  startTime?: number
+}
+
+/** Exports intervals as CSV with one row per period and location */
+export const exportCsv = rawExportCsv as (
+  testInfoId: string,
+  options?: CsvExportOptions
+) => string
+
+/** Renders the summary and latest interval as OpenMetrics text, for serving to a Prometheus scraper */
+export const exportOpenMetrics = rawExportOpenMetrics as (
+  testInfoId: string
+) => string
+
+/** Exports every interval as InfluxDB line protocol, startTime is the start of the run in milliseconds since the epoch and defaults to the recorded start time */
+export const exportInfluxLineProtocol = rawExportInfluxLineProtocol as (
+  testInfoId: string,
+  startTime?: number
+) => string
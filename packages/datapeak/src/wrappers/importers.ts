@@ -0,0 +1,23 @@
+import { rawImportJMeterCsv, rawImportK6Json } from '../datapeak-raw/pkg/datapeak'
+
+export type K6JsonImportOptions = {
+  /** Location for points without a load_zone tag, defaults to `local` */
+  location?: string
+}
+
+/** Imports the NDJSON written by `k6 run --out json` as new test data, returning its testInfoId */
+export const importK6Json = rawImportK6Json as (
+  ndjson: string,
+  options?: K6JsonImportOptions
+) => string
+
+export type JMeterCsvImportOptions = {
+  /** Location given to every sample, defaults to `local` */
+  location?: string
+}
+
+/** Imports a JMeter .jtl results file saved as CSV with a header row as new test data, returning its testInfoId */
+export const importJMeterCsv = rawImportJMeterCsv as (
+  csv: string,
+  options?: JMeterCsvImportOptions
+) => string
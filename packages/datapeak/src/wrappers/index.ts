@@ -4,3 +4,10 @@ export * from './thresholds'
 export * from './intervals'
 export * from './messages'
 export * from './checks'
+export * from './subscriptions'
+export * from './changes'
+export * from './exporters'
+export * from './importers'
+export * from './comparison'
+export * from './anomalies'
+export * from './run-info'
@@ -44,9 +44,18 @@ export type Interval = {
   sinks: Record<string, Sink>
 }
 
+/** Periods to include, both inclusive, where a missing end reaches the start or end of the run */
+export type Window = {
+  startPeriod?: number
+  endPeriod?: number
+  /** Milliseconds since the epoch, only periods wholly between the times are included. Needs the start time of the run */
+  startTime?: number
+  endTime?: number
+}
+
 /** Unlike other pollers, IntervalsPoller's callback doesn't return anything as data output is customized. */
 export class IntervalsPoller {
-  private state = ''
+  private state: number | null = null
   private intervalId: NodeJS.Timeout | null = null
   private readonly pollInterval: number
   private readonly callback: () => void
@@ -2,14 +2,24 @@ import {
   rawGetSummaryState,
   rawTestInfoIdExists,
   rawGetSummary,
+  rawGetWindowSummary,
 } from '../../datapeak-raw/pkg/datapeak'
 
-import type { Interval } from '.'
+import type { Interval, Window } from '.'
 
 export const getSummary = rawGetSummary as (testInfoId: string) => Interval
 
+/**
+ * Summarises only the intervals inside the window, e.g. to exclude ramp-up and
+ * ramp-down. Counter counts and rates only include what was counted in it
+ */
+export const getWindowSummary = rawGetWindowSummary as (
+  testInfoId: string,
+  window?: Window
+) => Interval | null
+
 export class SummaryPoller {
-  private state = ''
+  private state: number | null = null
   private intervalId: NodeJS.Timeout | null = null
   private readonly pollInterval: number
   private readonly callback: (summary: Interval | null) => void
@@ -31,7 +41,7 @@ export class SummaryPoller {
   }
 
   private async poll() {
-    const newState = JSON.stringify(rawGetSummaryState(this.testInfoId))
+    const newState = rawGetSummaryState(this.testInfoId)
     if (newState !== this.state) {
       this.state = newState
       this.callback(getSummary(this.testInfoId))
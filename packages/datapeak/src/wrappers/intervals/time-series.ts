@@ -1,4 +1,10 @@
-import { rawIntervalTimeSeries } from '../../datapeak-raw/pkg/datapeak'
+import {
+  rawExpressionTimeSeries,
+  rawIntervalTimeSeries,
+  rawQueryTimeSeries,
+} from '../../datapeak-raw/pkg/datapeak'
+
+import type { Window } from '.'
 
 export type DownsizingMethod =
   | {
@@ -10,22 +16,75 @@ export type DownsizingMethod =
       windowSize?: number
       maxDataPoints?: number
     }
+  | {
+      /** Largest-Triangle-Three-Buckets, keeps the shape of the line including spikes */
+      type: 'lttb'
+      maxDataPoints?: number
+    }
+  | {
+      /** Keeps the lowest and highest point of each bucket */
+      type: 'minMax'
+      maxDataPoints?: number
+    }
   | {
       type: 'none'
     }
 
+/** Points are [period, value] */
 export type IntervalTimeSeries = {
   name: string
-  data: [number, number][]
+  /** Values are null where the sink has no value for that period */
+  data: [number, number | null][]
+  /** Start of the period of each point in milliseconds since the epoch, null when the start time of the run isn't known */
+  timestamps: number[] | null
 }
 
 export const intervalTimeSeries = (
   testInfoId: string,
   sinkPath: string,
-  downsizingMethod: DownsizingMethod
+  downsizingMethod: DownsizingMethod,
+  window?: Window
 ): IntervalTimeSeries =>
   rawIntervalTimeSeries(
     testInfoId,
     sinkPath,
-    downsizingMethod
+    downsizingMethod,
+    window
+  ) as IntervalTimeSeries
+
+/**
+ * Gets a series for every sink matching a pattern like
+ * `*::http_req_duration::p(95)`, all on the same period axis, limited to
+ * the window if one is given
+ */
+export const queryTimeSeries = (
+  testInfoId: string,
+  pattern: string,
+  downsizingMethod: DownsizingMethod,
+  window?: Window
+): IntervalTimeSeries[] =>
+  rawQueryTimeSeries(
+    testInfoId,
+    pattern,
+    downsizingMethod,
+    window
+  ) as IntervalTimeSeries[]
+
+/**
+ * Evaluates an expression for every period in the window, or the whole run, e.g.
+ * `http_req_failed::fail / http_reqs::count` or `rate(iterations::count)`.
+ * Paths without a location are global, and `-` must be surrounded by spaces
+ * as sink names can contain it
+ */
+export const expressionTimeSeries = (
+  testInfoId: string,
+  expression: string,
+  downsizingMethod: DownsizingMethod,
+  window?: Window
+): IntervalTimeSeries =>
+  rawExpressionTimeSeries(
+    testInfoId,
+    expression,
+    downsizingMethod,
+    window
   ) as IntervalTimeSeries
@@ -15,7 +15,7 @@ export const setLocations = rawSetLocations as (
 ) => void
 
 export class LocationPoller {
-  private state = ''
+  private state: number | null = null
   private intervalId: NodeJS.Timeout | null = null
   private readonly pollInterval: number
   private readonly callback: (locations: Locations) => void
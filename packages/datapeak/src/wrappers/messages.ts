@@ -13,7 +13,7 @@ export const addMessage = rawAddMessage as (
 ) => void
 
 export class MessagesPoller {
-  private state = ''
+  private state: number | null = null
   private intervalId: NodeJS.Timeout | null = null
   private readonly pollInterval: number
   private readonly callback: (messages: string[]) => void
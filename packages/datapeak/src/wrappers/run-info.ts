@@ -0,0 +1,16 @@
+import { rawGetRunInfo, rawSetRunInfo } from '../datapeak-raw/pkg/datapeak'
+
+export type RunInfo = {
+  /** When period 0 started in milliseconds since the epoch, null if it wasn't recorded */
+  startTime: number | null
+  intervalSeconds: number
+}
+
+export const getRunInfo = rawGetRunInfo as (testInfoId: string) => RunInfo
+
+/** Omitted values are left unchanged, changing intervalSeconds recalculates counter rates */
+export const setRunInfo = rawSetRunInfo as (
+  testInfoId: string,
+  startTime?: number,
+  intervalSeconds?: number
+) => void
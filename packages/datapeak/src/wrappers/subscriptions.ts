@@ -0,0 +1,38 @@
+import {
+  rawSubscribe,
+  rawUnsubscribe,
+  rawTestInfoIdExists,
+} from '../datapeak-raw/pkg/datapeak'
+
+export type Topic =
+  | 'intervals'
+  | 'checks'
+  | 'thresholds'
+  | 'consoleMessages'
+  | 'locations'
+  | 'messages'
+  | 'summary'
+  | 'abort'
+  | 'anomalies'
+
+/**
+ * Calls the callback once per batch of streamed data with the subscribed topics
+ * that changed. Returns a function that removes the subscription.
+ */
+export const subscribe = (
+  testInfoId: string,
+  topics: Topic[],
+  callback: (changedTopics: Topic[]) => void
+): (() => void) => {
+  if (!rawTestInfoIdExists(testInfoId)) {
+    throw new Error(`Test info id ${testInfoId} does not exist`)
+  }
+
+  topics.forEach((topic) => rawSubscribe(testInfoId, topic, callback))
+
+  return () => {
+    if (rawTestInfoIdExists(testInfoId)) {
+      topics.forEach((topic) => rawUnsubscribe(testInfoId, topic, callback))
+    }
+  }
+}
@@ -1,5 +1,8 @@
 import {
+  rawGetAbortDecision,
+  rawGetAbortState,
   rawGetThresholds,
+  rawGetThresholdResults,
   rawGetThresholdsState,
   rawTestInfoIdExists,
 } from '../datapeak-raw/pkg/datapeak'
@@ -11,12 +14,38 @@ export type Threshold = {
   delayAbortEval?: string
 }
 
+export type ThresholdResult = {
+  metric: string
+  source: string
+  /** Either a location or 'global' for all locations combined */
+  location: string
+  period: number
+  passed: boolean
+  value: number | null
+  error: string | null
+}
+
+export type AbortDecision = {
+  metric: string
+  source: string
+  period: number
+  value: number | null
+}
+
 export const getThresholds = rawGetThresholds as (
   testInfoId: string
 ) => Threshold[]
 
+export const getThresholdResults = rawGetThresholdResults as (
+  testInfoId: string
+) => ThresholdResult[]
+
+export const getAbortDecision = rawGetAbortDecision as (
+  testInfoId: string
+) => AbortDecision | null
+
 export class ThresholdsPoller {
-  private state = ''
+  private state: number | null = null
   private intervalId: NodeJS.Timeout | null = null
   private readonly pollInterval: number
   private readonly callback: (thresholds: Threshold[]) => void
@@ -53,3 +82,42 @@ export class ThresholdsPoller {
     }
   }
 }
+
+export class AbortPoller {
+  private state: number | null = null
+  private intervalId: NodeJS.Timeout | null = null
+  private readonly pollInterval: number
+  private readonly callback: (abortDecision: AbortDecision | null) => void
+  private readonly testInfoId: string
+
+  constructor(
+    testInfoId: string,
+    callback: (abortDecision: AbortDecision | null) => void,
+    pollInterval = 1000
+  ) {
+    if (!rawTestInfoIdExists(testInfoId)) {
+      throw new Error(`Test info id ${testInfoId} does not exist`)
+    }
+
+    this.testInfoId = testInfoId
+    this.callback = callback
+    this.pollInterval = pollInterval
+    this.poll()
+  }
+
+  private async poll() {
+    const newState = rawGetAbortState(this.testInfoId)
+    if (newState !== this.state) {
+      this.state = newState
+      this.callback(getAbortDecision(this.testInfoId))
+    }
+    this.intervalId = setTimeout(() => this.poll(), this.pollInterval)
+  }
+
+  public destroy(): void {
+    if (this.intervalId) {
+      clearInterval(this.intervalId)
+      this.intervalId = null
+    }
+  }
+}